@@ -0,0 +1,934 @@
+import { describe, test, expect } from 'bun:test';
+import {
+  WebviewApplicationEvent,
+  Theme,
+  Webview,
+  WebView,
+  Application,
+  BrowserWindow,
+  Profile,
+  WebViewBuilder,
+  BackdropEffect,
+  checkWebviewRuntime,
+  CacheMode,
+  ScrollBehavior,
+  PageLoadEvent,
+  ScrollRestoration,
+  DragDropEvent,
+  TlsVersion,
+  PermissionDecision,
+  PermissionKind,
+  Menu,
+  Submenu,
+  Window,
+} from '../index';
+import type {
+  ApplicationEvent,
+  AboutPanel,
+  WebviewOptions,
+  DownloadProgress,
+  ApplicationOptions,
+  MessageDelivery,
+  Modifiers,
+  WebviewRuntimeStatus,
+  BrowserWindowOptions,
+  TitlebarOverlay,
+  CustomProtocolResponse,
+  HeaderData,
+  PageLoadEventData,
+  IpcMessage,
+  DragDropEventData,
+  Position,
+  CustomDecorations,
+  CookieData,
+  PermissionRequest,
+  MenuEvent,
+  MenuItemOptions,
+  Dimensions,
+  WindowHandle,
+  WindowGeometry,
+  ProxyConfig,
+} from '../index';
+
+// Smoke tests for the API surface. They run without creating an application or window, so
+// they also run in CI. Interfaces only exist at type level; their tests build typed values
+// that fail type checking when a field is renamed or retyped.
+
+describe('Theme changes', () => {
+  test('WebviewApplicationEvent has ApplicationCloseRequested and ThemeChanged', () => {
+    expect(WebviewApplicationEvent.ApplicationCloseRequested).toBe(1);
+    expect(WebviewApplicationEvent.ThemeChanged).toBe(2);
+  });
+
+  test('ApplicationEvent has theme', () => {
+    const applicationEvent: ApplicationEvent = { event: WebviewApplicationEvent.ThemeChanged, theme: Theme.Light };
+    expect(applicationEvent.theme).toBe(Theme.Light);
+  });
+});
+
+describe('Relative webview bounds', () => {
+  test('Webview exposes setBoundsRelative', () => {
+    expect(typeof Webview.prototype.setBoundsRelative).toBe('function');
+  });
+
+  test('WebView exposes setBounds', () => {
+    expect(typeof WebView.prototype.setBounds).toBe('function');
+  });
+});
+
+describe('Pinch zoom and swipe navigation', () => {
+  test('Webview exposes setPinchZoomEnabled and setSwipeNavigationEnabled', () => {
+    for (const name of ['setPinchZoomEnabled', 'setSwipeNavigationEnabled'] as const) {
+      expect(typeof Webview.prototype[name]).toBe('function');
+    }
+  });
+});
+
+describe('About panel', () => {
+  test('Application exposes setAboutPanel and showAboutPanel', () => {
+    for (const name of ['setAboutPanel', 'showAboutPanel'] as const) {
+      expect(typeof Application.prototype[name]).toBe('function');
+    }
+  });
+
+  test('AboutPanel has applicationName, version, credits, copyright and icon', () => {
+    const aboutPanel: AboutPanel = {
+      applicationName: 'Example',
+      version: '1.0.0',
+      credits: 'Example credits',
+      copyright: 'Copyright Example',
+      icon: Buffer.from([255, 255, 255, 255]),
+    };
+    expect(aboutPanel.applicationName).toBe('Example');
+  });
+});
+
+describe('Windows from saved attributes', () => {
+  test('Application exposes createBrowserWindowFromAttributes', () => {
+    expect(typeof Application.prototype.createBrowserWindowFromAttributes).toBe('function');
+  });
+});
+
+describe('Fullscreen requests', () => {
+  test('BrowserWindow exposes setFullscreen', () => {
+    expect(typeof BrowserWindow.prototype.setFullscreen).toBe('function');
+  });
+
+  test('Webview exposes onFullscreenRequest', () => {
+    expect(typeof Webview.prototype.onFullscreenRequest).toBe('function');
+  });
+});
+
+describe('Blocked URL patterns', () => {
+  test('WebviewOptions has blockedUrlPatterns', () => {
+    const options: WebviewOptions = { blockedUrlPatterns: ['*://ads.example.com/*'] };
+    expect(options.blockedUrlPatterns).toEqual(['*://ads.example.com/*']);
+  });
+});
+
+describe('Download progress', () => {
+  test('Webview exposes onDownloadProgress', () => {
+    expect(typeof Webview.prototype.onDownloadProgress).toBe('function');
+  });
+
+  test('DownloadProgress has url, receivedBytes and totalBytes', () => {
+    const downloadProgress: DownloadProgress = { url: 'https://example.com/', receivedBytes: 512, totalBytes: 1024 };
+    expect(downloadProgress.url).toBe('https://example.com/');
+  });
+});
+
+describe('Named profiles', () => {
+  test('Application exposes profile', () => {
+    expect(typeof Application.prototype.profile).toBe('function');
+  });
+
+  test('BrowserWindow exposes createWebview', () => {
+    expect(typeof BrowserWindow.prototype.createWebview).toBe('function');
+  });
+
+  test('Profile exposes the name getter', () => {
+    expect(typeof Object.getOwnPropertyDescriptor(Profile.prototype, 'name')?.get).toBe('function');
+  });
+
+  test('Profile exposes the path getter', () => {
+    expect(typeof Object.getOwnPropertyDescriptor(Profile.prototype, 'path')?.get).toBe('function');
+  });
+
+  test('WebViewBuilder exposes withWebContext', () => {
+    expect(typeof WebViewBuilder.prototype.withWebContext).toBe('function');
+  });
+
+  test('ApplicationOptions has appId', () => {
+    const options: ApplicationOptions = { appId: 'com.example.app' };
+    expect(options.appId).toBe('com.example.app');
+  });
+});
+
+describe('Paint placeholder', () => {
+  test('WebviewOptions has paintPlaceholderColor', () => {
+    const options: WebviewOptions = { paintPlaceholderColor: Buffer.from([255, 255, 255, 255]) };
+    expect(options.paintPlaceholderColor).toEqual(Buffer.from([255, 255, 255, 255]));
+  });
+});
+
+describe('Load timeout', () => {
+  test('Webview exposes setLoadTimeout', () => {
+    expect(typeof Webview.prototype.setLoadTimeout).toBe('function');
+  });
+
+  test('WebviewApplicationEvent has LoadTimeout', () => {
+    expect(WebviewApplicationEvent.LoadTimeout).toBe(3);
+  });
+
+  test('ApplicationEvent has url', () => {
+    const applicationEvent: ApplicationEvent = {
+      event: WebviewApplicationEvent.LoadTimeout,
+      url: 'https://example.com/',
+    };
+    expect(applicationEvent.url).toBe('https://example.com/');
+  });
+});
+
+describe('Window backdrop effects', () => {
+  test('BrowserWindow exposes setBackdrop', () => {
+    expect(typeof BrowserWindow.prototype.setBackdrop).toBe('function');
+  });
+
+  test('BackdropEffect values', () => {
+    expect(BackdropEffect.None).toBe(0);
+    expect(BackdropEffect.Blur).toBe(1);
+    expect(BackdropEffect.Acrylic).toBe(2);
+    expect(BackdropEffect.Mica).toBe(3);
+    expect(BackdropEffect.Tabbed).toBe(4);
+  });
+});
+
+describe('Webview labels', () => {
+  test('BrowserWindow exposes webviewByLabel', () => {
+    expect(typeof BrowserWindow.prototype.webviewByLabel).toBe('function');
+  });
+
+  test('WebviewOptions has label', () => {
+    const options: WebviewOptions = { label: 'main' };
+    expect(options.label).toBe('main');
+  });
+});
+
+describe('Messages to all webviews', () => {
+  test('BrowserWindow exposes postMessageAll', () => {
+    expect(typeof BrowserWindow.prototype.postMessageAll).toBe('function');
+  });
+
+  test('MessageDelivery has label, delivered and error', () => {
+    const messageDelivery: MessageDelivery = { label: 'main', delivered: true, error: 'Webview is not initialized' };
+    expect(messageDelivery.label).toBe('main');
+  });
+});
+
+describe('Keyboard modifiers', () => {
+  test('Application exposes currentModifiers', () => {
+    expect(typeof Application.prototype.currentModifiers).toBe('function');
+  });
+
+  test('Modifiers has shift, ctrl, alt and meta', () => {
+    const modifiers: Modifiers = { shift: true, ctrl: true, alt: true, meta: true };
+    expect(modifiers.shift).toBe(true);
+  });
+});
+
+describe('Webview runtime check', () => {
+  test('checkWebviewRuntime reports whether the runtime is available', () => {
+    const status = checkWebviewRuntime();
+    expect(typeof status.available).toBe('boolean');
+    if (status.available) {
+      expect(typeof status.version).toBe('string');
+    }
+  });
+
+  test('WebviewRuntimeStatus has available, version and installUrl', () => {
+    const webviewRuntimeStatus: WebviewRuntimeStatus = {
+      available: true,
+      version: '1.0.0',
+      installUrl: 'https://example.com/install',
+    };
+    expect(webviewRuntimeStatus.available).toBe(true);
+  });
+});
+
+describe('Window tabbing', () => {
+  test('BrowserWindow exposes selectNextTab, selectPreviousTab and mergeAllWindows', () => {
+    for (const name of ['selectNextTab', 'selectPreviousTab', 'mergeAllWindows'] as const) {
+      expect(typeof BrowserWindow.prototype[name]).toBe('function');
+    }
+  });
+
+  test('WebviewApplicationEvent has TabSelected', () => {
+    expect(WebviewApplicationEvent.TabSelected).toBe(4);
+  });
+
+  test('BrowserWindowOptions has tabbingIdentifier', () => {
+    const options: BrowserWindowOptions = { tabbingIdentifier: 'documents' };
+    expect(options.tabbingIdentifier).toBe('documents');
+  });
+});
+
+describe('Window capture', () => {
+  test('BrowserWindow exposes captureWindow', () => {
+    expect(typeof BrowserWindow.prototype.captureWindow).toBe('function');
+  });
+});
+
+describe('Accept-Language', () => {
+  test('WebViewBuilder exposes withAcceptLanguage', () => {
+    expect(typeof WebViewBuilder.prototype.withAcceptLanguage).toBe('function');
+  });
+
+  test('WebviewOptions has acceptLanguage', () => {
+    const options: WebviewOptions = { acceptLanguage: 'de-DE,de;q=0.9,en;q=0.8' };
+    expect(options.acceptLanguage).toBe('de-DE,de;q=0.9,en;q=0.8');
+  });
+});
+
+describe('Window shape', () => {
+  test('BrowserWindow exposes setShape', () => {
+    expect(typeof BrowserWindow.prototype.setShape).toBe('function');
+  });
+});
+
+describe('HTTP cache mode', () => {
+  test('CacheMode values', () => {
+    expect(CacheMode.Default).toBe(0);
+    expect(CacheMode.NoCache).toBe(1);
+    expect(CacheMode.OnlyFromCache).toBe(2);
+  });
+
+  test('WebviewOptions has cacheMode', () => {
+    const options: WebviewOptions = { cacheMode: CacheMode.Default };
+    expect(options.cacheMode).toBe(CacheMode.Default);
+  });
+});
+
+describe('Title changes', () => {
+  test('Webview exposes onTitleChanged', () => {
+    expect(typeof Webview.prototype.onTitleChanged).toBe('function');
+  });
+});
+
+describe('Titlebar overlay', () => {
+  test('BrowserWindow exposes setTitlebarOverlay', () => {
+    expect(typeof BrowserWindow.prototype.setTitlebarOverlay).toBe('function');
+  });
+
+  test('BrowserWindow exposes the titlebarOverlay getter', () => {
+    expect(typeof Object.getOwnPropertyDescriptor(BrowserWindow.prototype, 'titlebarOverlay')?.get).toBe('function');
+  });
+
+  test('TitlebarOverlay has height and buttonInset', () => {
+    const titlebarOverlay: TitlebarOverlay = { height: 32, buttonInset: 8 };
+    expect(titlebarOverlay.height).toBe(32);
+  });
+});
+
+describe('Scrolling', () => {
+  test('Webview exposes scrollTo and scrollBy', () => {
+    for (const name of ['scrollTo', 'scrollBy'] as const) {
+      expect(typeof Webview.prototype[name]).toBe('function');
+    }
+  });
+
+  test('ScrollBehavior values', () => {
+    expect(ScrollBehavior.Auto).toBe(0);
+    expect(ScrollBehavior.Smooth).toBe(1);
+    expect(ScrollBehavior.Instant).toBe(2);
+  });
+});
+
+describe('Window size and position', () => {
+  test('BrowserWindow exposes innerSize, setInnerSize, outerPosition and setOuterPosition', () => {
+    for (const name of ['innerSize', 'setInnerSize', 'outerPosition', 'setOuterPosition'] as const) {
+      expect(typeof BrowserWindow.prototype[name]).toBe('function');
+    }
+  });
+});
+
+describe('Native window id', () => {
+  test('BrowserWindow exposes nativeWindowId', () => {
+    expect(typeof BrowserWindow.prototype.nativeWindowId).toBe('function');
+  });
+});
+
+describe('Webview reset', () => {
+  test('Webview exposes reset and clearAllBrowsingData', () => {
+    for (const name of ['reset', 'clearAllBrowsingData'] as const) {
+      expect(typeof Webview.prototype[name]).toBe('function');
+    }
+  });
+
+  test('WebView exposes clearAllBrowsingData', () => {
+    expect(typeof WebView.prototype.clearAllBrowsingData).toBe('function');
+  });
+});
+
+describe('Exclusive fullscreen', () => {
+  test('BrowserWindow exposes setExclusiveFullscreen', () => {
+    expect(typeof BrowserWindow.prototype.setExclusiveFullscreen).toBe('function');
+  });
+});
+
+describe('GPU acceleration', () => {
+  test('WebviewOptions has disableGpu', () => {
+    const options: WebviewOptions = { disableGpu: true };
+    expect(options.disableGpu).toBe(true);
+  });
+});
+
+describe('Custom protocols', () => {
+  test('Webview exposes onCustomProtocol', () => {
+    expect(typeof Webview.prototype.onCustomProtocol).toBe('function');
+  });
+
+  test('WebviewOptions has customProtocols', () => {
+    const options: WebviewOptions = { customProtocols: ['app'] };
+    expect(options.customProtocols).toEqual(['app']);
+  });
+
+  test('CustomProtocolResponse has status, headers and body', () => {
+    const customProtocolResponse: CustomProtocolResponse = {
+      status: 200,
+      headers: [{ key: 'X-Example', value: '1' }],
+      body: Buffer.from('<p>Hello</p>'),
+    };
+    expect(customProtocolResponse.status).toBe(200);
+  });
+});
+
+describe('Navigation history', () => {
+  test('Webview exposes goBack, goForward, canGoBack and canGoForward', () => {
+    for (const name of ['goBack', 'goForward', 'canGoBack', 'canGoForward'] as const) {
+      expect(typeof Webview.prototype[name]).toBe('function');
+    }
+  });
+});
+
+describe('Window destruction', () => {
+  test('BrowserWindow exposes destroy', () => {
+    expect(typeof BrowserWindow.prototype.destroy).toBe('function');
+  });
+});
+
+describe('Effective transparency', () => {
+  test('BrowserWindow exposes isTransparentEffective', () => {
+    expect(typeof BrowserWindow.prototype.isTransparentEffective).toBe('function');
+  });
+});
+
+describe('Window z-order', () => {
+  test('BrowserWindow exposes raise and lower', () => {
+    for (const name of ['raise', 'lower'] as const) {
+      expect(typeof BrowserWindow.prototype[name]).toBe('function');
+    }
+  });
+});
+
+describe('Window size constraints', () => {
+  test('BrowserWindow exposes setMinInnerSize and setMaxInnerSize', () => {
+    for (const name of ['setMinInnerSize', 'setMaxInnerSize'] as const) {
+      expect(typeof BrowserWindow.prototype[name]).toBe('function');
+    }
+  });
+
+  test('BrowserWindowOptions has minWidth, minHeight, maxWidth and maxHeight', () => {
+    const options: BrowserWindowOptions = { minWidth: 320, minHeight: 240, maxWidth: 1920, maxHeight: 1080 };
+    expect(options.minWidth).toBe(320);
+  });
+});
+
+describe('Page load events', () => {
+  test('Webview exposes onPageLoad', () => {
+    expect(typeof Webview.prototype.onPageLoad).toBe('function');
+  });
+
+  test('PageLoadEventData has event and url', () => {
+    const pageLoadEventData: PageLoadEventData = { event: PageLoadEvent.Started, url: 'https://example.com/' };
+    expect(pageLoadEventData.event).toBe(PageLoadEvent.Started);
+  });
+});
+
+describe('IPC message source', () => {
+  test('Webview exposes onIpcMessageWithSource', () => {
+    expect(typeof Webview.prototype.onIpcMessageWithSource).toBe('function');
+  });
+
+  test('IpcMessage has windowId and label', () => {
+    const ipcMessage: IpcMessage = {
+      body: Buffer.from('ping'),
+      method: 'POST',
+      headers: [{ key: 'X-Example', value: '1' }],
+      uri: 'ipc://localhost/',
+      windowId: 'main',
+      label: 'main',
+    };
+    expect(ipcMessage.windowId).toBe('main');
+  });
+});
+
+describe('Navigation handler', () => {
+  test('Webview exposes onNavigation', () => {
+    expect(typeof Webview.prototype.onNavigation).toBe('function');
+  });
+});
+
+describe('Scroll restoration', () => {
+  test('Webview exposes saveScrollPosition and restoreScrollPosition', () => {
+    for (const name of ['saveScrollPosition', 'restoreScrollPosition'] as const) {
+      expect(typeof Webview.prototype[name]).toBe('function');
+    }
+  });
+
+  test('ScrollRestoration values', () => {
+    expect(ScrollRestoration.Auto).toBe(0);
+    expect(ScrollRestoration.Manual).toBe(1);
+  });
+
+  test('WebviewOptions has scrollRestoration', () => {
+    const options: WebviewOptions = { scrollRestoration: ScrollRestoration.Auto };
+    expect(options.scrollRestoration).toBe(ScrollRestoration.Auto);
+  });
+});
+
+describe('Drag and drop', () => {
+  test('Webview exposes onDragDrop', () => {
+    expect(typeof Webview.prototype.onDragDrop).toBe('function');
+  });
+
+  test('DragDropEventData has kind, paths and position', () => {
+    const dragDropEventData: DragDropEventData = {
+      kind: DragDropEvent.Entered,
+      paths: ['/tmp/example.txt'],
+      position: { x: 10, y: 20 },
+    };
+    expect(dragDropEventData.kind).toBe(DragDropEvent.Entered);
+  });
+});
+
+describe('Hung webview recovery', () => {
+  test('Webview exposes isResponsive and restart', () => {
+    for (const name of ['isResponsive', 'restart'] as const) {
+      expect(typeof Webview.prototype[name]).toBe('function');
+    }
+  });
+
+  test('WebView exposes url', () => {
+    expect(typeof WebView.prototype.url).toBe('function');
+  });
+});
+
+describe('Webview bounds', () => {
+  test('Webview exposes setBounds and bounds', () => {
+    for (const name of ['setBounds', 'bounds'] as const) {
+      expect(typeof Webview.prototype[name]).toBe('function');
+    }
+  });
+});
+
+describe('Window close events', () => {
+  test('ApplicationEvent has windowId', () => {
+    const applicationEvent: ApplicationEvent = {
+      event: WebviewApplicationEvent.WindowCloseRequested,
+      windowId: 'main',
+    };
+    expect(applicationEvent.windowId).toBe('main');
+  });
+});
+
+describe('Window size getters', () => {
+  test('BrowserWindow exposes outerSize and scaleFactor', () => {
+    for (const name of ['outerSize', 'scaleFactor'] as const) {
+      expect(typeof BrowserWindow.prototype[name]).toBe('function');
+    }
+  });
+});
+
+describe('Opacity animation', () => {
+  test('BrowserWindow exposes animateOpacity', () => {
+    expect(typeof BrowserWindow.prototype.animateOpacity).toBe('function');
+  });
+
+  test('WebviewApplicationEvent has OpacityAnimationFinished', () => {
+    expect(WebviewApplicationEvent.OpacityAnimationFinished).toBe(5);
+  });
+});
+
+describe('Window opacity and blur', () => {
+  test('BrowserWindow exposes setWindowOpacity and setBlur', () => {
+    for (const name of ['setWindowOpacity', 'setBlur'] as const) {
+      expect(typeof BrowserWindow.prototype[name]).toBe('function');
+    }
+  });
+});
+
+describe('Monitors', () => {
+  test('Application exposes availableMonitors and primaryMonitor', () => {
+    for (const name of ['availableMonitors', 'primaryMonitor'] as const) {
+      expect(typeof Application.prototype[name]).toBe('function');
+    }
+  });
+});
+
+describe('Custom decorations', () => {
+  test('BrowserWindowOptions has customDecorations', () => {
+    const options: BrowserWindowOptions = { customDecorations: {} };
+    expect(options.customDecorations).toEqual({});
+  });
+
+  test('CustomDecorations has resizeBorder, shadow and roundedCorners', () => {
+    const customDecorations: CustomDecorations = { resizeBorder: 6, shadow: true, roundedCorners: true };
+    expect(customDecorations.resizeBorder).toBe(6);
+  });
+});
+
+describe('IPC replies', () => {
+  test('Webview exposes reply', () => {
+    expect(typeof Webview.prototype.reply).toBe('function');
+  });
+
+  test('IpcMessage has requestId', () => {
+    const ipcMessage: IpcMessage = {
+      body: Buffer.from('ping'),
+      method: 'POST',
+      headers: [{ key: 'X-Example', value: '1' }],
+      uri: 'ipc://localhost/',
+      requestId: '1',
+    };
+    expect(ipcMessage.requestId).toBe('1');
+  });
+});
+
+describe('System sleep events', () => {
+  test('WebviewApplicationEvent has SystemSuspend and SystemResume', () => {
+    expect(WebviewApplicationEvent.SystemSuspend).toBe(6);
+    expect(WebviewApplicationEvent.SystemResume).toBe(7);
+  });
+});
+
+describe('Minimum TLS version', () => {
+  test('TlsVersion values', () => {
+    expect(TlsVersion.Tls12).toBe(0);
+    expect(TlsVersion.Tls13).toBe(1);
+  });
+
+  test('WebviewOptions has minTlsVersion', () => {
+    const options: WebviewOptions = { minTlsVersion: TlsVersion.Tls12 };
+    expect(options.minTlsVersion).toBe(TlsVersion.Tls12);
+  });
+});
+
+describe('Page capture', () => {
+  test('Webview exposes capturePage', () => {
+    expect(typeof Webview.prototype.capturePage).toBe('function');
+  });
+});
+
+describe('Cookies', () => {
+  test('Webview exposes getCookies, setCookie and deleteCookie', () => {
+    for (const name of ['getCookies', 'setCookie', 'deleteCookie'] as const) {
+      expect(typeof Webview.prototype[name]).toBe('function');
+    }
+  });
+
+  test('CookieData has name, value, domain, path, secure, httpOnly and expires', () => {
+    const cookieData: CookieData = {
+      name: 'session',
+      value: '1',
+      domain: 'example.com',
+      path: '/',
+      secure: true,
+      httpOnly: true,
+      expires: 1767225600,
+    };
+    expect(cookieData.name).toBe('session');
+  });
+});
+
+describe('Named windows', () => {
+  test('Application exposes createNamedWindow, getWindow, hasWindow and closeNamedWindow', () => {
+    for (const name of ['createNamedWindow', 'getWindow', 'hasWindow', 'closeNamedWindow'] as const) {
+      expect(typeof Application.prototype[name]).toBe('function');
+    }
+  });
+});
+
+describe('Permission requests', () => {
+  test('Webview exposes onPermissionRequest', () => {
+    expect(typeof Webview.prototype.onPermissionRequest).toBe('function');
+  });
+
+  test('PermissionDecision values', () => {
+    expect(PermissionDecision.Grant).toBe(0);
+    expect(PermissionDecision.Deny).toBe(1);
+    expect(PermissionDecision.Default).toBe(2);
+  });
+
+  test('PermissionKind values', () => {
+    expect(PermissionKind.Camera).toBe(0);
+    expect(PermissionKind.Microphone).toBe(1);
+    expect(PermissionKind.CameraAndMicrophone).toBe(2);
+    expect(PermissionKind.Geolocation).toBe(3);
+    expect(PermissionKind.Notifications).toBe(4);
+    expect(PermissionKind.Other).toBe(5);
+  });
+
+  test('PermissionRequest has origin and kind', () => {
+    const permissionRequest: PermissionRequest = { origin: 'https://example.com', kind: PermissionKind.Camera };
+    expect(permissionRequest.origin).toBe('https://example.com');
+  });
+});
+
+describe('Data directory', () => {
+  test('WebviewOptions has dataDirectory', () => {
+    const options: WebviewOptions = { dataDirectory: './data' };
+    expect(options.dataDirectory).toBe('./data');
+  });
+});
+
+describe('Windows above fullscreen apps', () => {
+  test('BrowserWindow exposes setAboveFullscreen', () => {
+    expect(typeof BrowserWindow.prototype.setAboveFullscreen).toBe('function');
+  });
+});
+
+describe('Zoom', () => {
+  test('Webview exposes zoom and getZoom', () => {
+    for (const name of ['zoom', 'getZoom'] as const) {
+      expect(typeof Webview.prototype[name]).toBe('function');
+    }
+  });
+});
+
+describe('Loading state', () => {
+  test('BrowserWindow exposes setLoadingState', () => {
+    expect(typeof BrowserWindow.prototype.setLoadingState).toBe('function');
+  });
+});
+
+describe('Page HTML and selection', () => {
+  test('Webview exposes getHtml and getSelectedText', () => {
+    for (const name of ['getHtml', 'getSelectedText'] as const) {
+      expect(typeof Webview.prototype[name]).toBe('function');
+    }
+  });
+});
+
+describe('Window state store', () => {
+  test('Application exposes setStateStore', () => {
+    expect(typeof Application.prototype.setStateStore).toBe('function');
+  });
+
+  test('BrowserWindowOptions has stateKey', () => {
+    const options: BrowserWindowOptions = { stateKey: 'main' };
+    expect(options.stateKey).toBe('main');
+  });
+});
+
+describe('Menus', () => {
+  test('exports the Menu class', () => {
+    expect(typeof Menu).toBe('function');
+  });
+
+  test('exports the Submenu class', () => {
+    expect(typeof Submenu).toBe('function');
+  });
+
+  test('Application exposes onMenuEvent', () => {
+    expect(typeof Application.prototype.onMenuEvent).toBe('function');
+  });
+
+  test('BrowserWindow exposes setMenu and popupMenu', () => {
+    for (const name of ['setMenu', 'popupMenu'] as const) {
+      expect(typeof BrowserWindow.prototype[name]).toBe('function');
+    }
+  });
+
+  test('Menu exposes appendItem, appendSeparator and appendSubmenu', () => {
+    for (const name of ['appendItem', 'appendSeparator', 'appendSubmenu'] as const) {
+      expect(typeof Menu.prototype[name]).toBe('function');
+    }
+  });
+
+  test('Submenu exposes appendItem, appendSeparator and appendSubmenu', () => {
+    for (const name of ['appendItem', 'appendSeparator', 'appendSubmenu'] as const) {
+      expect(typeof Submenu.prototype[name]).toBe('function');
+    }
+  });
+
+  test('MenuEvent has id', () => {
+    const menuEvent: MenuEvent = { id: 'reload' };
+    expect(menuEvent.id).toBe('reload');
+  });
+
+  test('MenuItemOptions has id, text, accelerator and enabled', () => {
+    const menuItemOptions: MenuItemOptions = {
+      id: 'reload',
+      text: 'Reload',
+      accelerator: 'CmdOrCtrl+R',
+      enabled: true,
+    };
+    expect(menuItemOptions.id).toBe('reload');
+  });
+});
+
+describe('Global shortcuts', () => {
+  test('Application exposes registerGlobalShortcut and unregisterGlobalShortcut', () => {
+    for (const name of ['registerGlobalShortcut', 'unregisterGlobalShortcut'] as const) {
+      expect(typeof Application.prototype[name]).toBe('function');
+    }
+  });
+});
+
+describe('Window focus and resize events', () => {
+  test('WebviewApplicationEvent has WindowFocused, WindowBlurred, WindowResized and WindowMoved', () => {
+    expect(WebviewApplicationEvent.WindowFocused).toBe(8);
+    expect(WebviewApplicationEvent.WindowBlurred).toBe(9);
+    expect(WebviewApplicationEvent.WindowResized).toBe(10);
+    expect(WebviewApplicationEvent.WindowMoved).toBe(11);
+  });
+
+  test('ApplicationEvent has size and position', () => {
+    const applicationEvent: ApplicationEvent = {
+      event: WebviewApplicationEvent.WindowResized,
+      size: { width: 800, height: 600 },
+      position: { x: 10, y: 20 },
+    };
+    expect(applicationEvent.size).toEqual({ width: 800, height: 600 });
+  });
+});
+
+describe('Raw window handle', () => {
+  test('BrowserWindow exposes rawWindowHandle', () => {
+    expect(typeof BrowserWindow.prototype.rawWindowHandle).toBe('function');
+  });
+
+  test('WindowHandle has platform, handle and display', () => {
+    const windowHandle: WindowHandle = { platform: 'x11', handle: 1n, display: 1n };
+    expect(windowHandle.platform).toBe('x11');
+  });
+});
+
+describe('Open windows', () => {
+  test('Application exposes getOpenWindowIds and windowCount', () => {
+    for (const name of ['getOpenWindowIds', 'windowCount'] as const) {
+      expect(typeof Application.prototype[name]).toBe('function');
+    }
+  });
+});
+
+describe('Asset root', () => {
+  test('Webview exposes loadHtmlWithBaseUrl', () => {
+    expect(typeof Webview.prototype.loadHtmlWithBaseUrl).toBe('function');
+  });
+
+  test('WebviewOptions has assetRoot', () => {
+    const options: WebviewOptions = { assetRoot: './dist' };
+    expect(options.assetRoot).toBe('./dist');
+  });
+});
+
+describe('Request headers', () => {
+  test('WebViewBuilder exposes withHeader', () => {
+    expect(typeof WebViewBuilder.prototype.withHeader).toBe('function');
+  });
+
+  test('WebviewOptions has defaultHeaders', () => {
+    const options: WebviewOptions = { defaultHeaders: [{ key: 'X-Example', value: '1' }] };
+    expect(options.defaultHeaders).toEqual([{ key: 'X-Example', value: '1' }]);
+  });
+});
+
+describe('Control flow', () => {
+  test('Application exposes setControlFlow', () => {
+    expect(typeof Application.prototype.setControlFlow).toBe('function');
+  });
+});
+
+describe('Page state queries', () => {
+  test('Webview exposes currentUrl, currentTitle and isLoading', () => {
+    for (const name of ['currentUrl', 'currentTitle', 'isLoading'] as const) {
+      expect(typeof Webview.prototype[name]).toBe('function');
+    }
+  });
+});
+
+describe('Window geometry', () => {
+  test('BrowserWindow exposes getGeometry and applyGeometry', () => {
+    for (const name of ['getGeometry', 'applyGeometry'] as const) {
+      expect(typeof BrowserWindow.prototype[name]).toBe('function');
+    }
+  });
+
+  test('WindowGeometry has x, y, width, height, maximized and fullscreen', () => {
+    const windowGeometry: WindowGeometry = { x: 10, y: 20, width: 800, height: 600, maximized: true, fullscreen: true };
+    expect(windowGeometry.x).toBe(10);
+  });
+});
+
+describe('Visible on all workspaces', () => {
+  test('BrowserWindow exposes setVisibleOnAllWorkspaces', () => {
+    expect(typeof BrowserWindow.prototype.setVisibleOnAllWorkspaces).toBe('function');
+  });
+
+  test('Window exposes setVisibleOnAllWorkspaces', () => {
+    expect(typeof Window.prototype.setVisibleOnAllWorkspaces).toBe('function');
+  });
+});
+
+describe('Window dragging', () => {
+  test('BrowserWindow exposes startDrag and startResizeDrag', () => {
+    for (const name of ['startDrag', 'startResizeDrag'] as const) {
+      expect(typeof BrowserWindow.prototype[name]).toBe('function');
+    }
+  });
+
+  test('Window exposes dragResizeWindow', () => {
+    expect(typeof Window.prototype.dragResizeWindow).toBe('function');
+  });
+});
+
+describe('Badge count', () => {
+  test('BrowserWindow exposes setBadgeCount', () => {
+    expect(typeof BrowserWindow.prototype.setBadgeCount).toBe('function');
+  });
+});
+
+describe('Script results', () => {
+  test('Webview exposes evaluateScriptForResult', () => {
+    expect(typeof Webview.prototype.evaluateScriptForResult).toBe('function');
+  });
+});
+
+describe('Favicon changes', () => {
+  test('Webview exposes onFaviconChanged', () => {
+    expect(typeof Webview.prototype.onFaviconChanged).toBe('function');
+  });
+});
+
+describe('Proxy', () => {
+  test('WebviewOptions has proxy', () => {
+    const options: WebviewOptions = { proxy: { type: 'Http', field0: 'proxy.example:3128' } };
+    expect(options.proxy).toEqual({ type: 'Http', field0: 'proxy.example:3128' });
+  });
+});
+
+describe('Background color', () => {
+  test('WebviewOptions has backgroundColor', () => {
+    const options: WebviewOptions = { backgroundColor: Buffer.from([255, 255, 255, 255]) };
+    expect(options.backgroundColor).toEqual(Buffer.from([255, 255, 255, 255]));
+  });
+});
+
+describe('Webview focus', () => {
+  test('Webview exposes focus and isFocused', () => {
+    for (const name of ['focus', 'isFocused'] as const) {
+      expect(typeof Webview.prototype[name]).toBe('function');
+    }
+  });
+});
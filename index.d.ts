@@ -2,17 +2,133 @@
 /* eslint-disable */
 export declare class Application {
   constructor(options?: ApplicationOptions | undefined | null)
+  /**
+   * Changes the loop mode of `run` while it is running, e.g. to `WaitUntil` with a long wait
+   * time to save power while idle. `waitTimeMs` defaults to 100.
+   */
+  setControlFlow(flow: ControlFlow, waitTimeMs?: number | undefined | null): void
   onEvent(handler?: (((err: Error | null, arg: ApplicationEvent) => any)) | undefined | null): void
+  /**
+   * Sets the handler called with the id of a menu item when it is selected in any menu of
+   * this application.
+   */
+  onMenuEvent(handler?: (((err: Error | null, arg: MenuEvent) => any)) | undefined | null): void
+  /**
+   * Registers a system-wide shortcut such as `Ctrl+Shift+K` or `CmdOrCtrl+Alt+Space` that calls
+   * `handler` even while another application has focus. Fails if the accelerator cannot be
+   * parsed, is already registered by this application or is taken by another application.
+   */
+  registerGlobalShortcut(accelerator: string, handler: ((err: Error | null) => any)): void
+  /**
+   * Unregisters a shortcut registered with `registerGlobalShortcut`. Does nothing if it is not
+   * registered.
+   */
+  unregisterGlobalShortcut(accelerator: string): void
   bind(handler?: (((err: Error | null, arg: ApplicationEvent) => any)) | undefined | null): void
   createBrowserWindow(options?: BrowserWindowOptions | undefined | null): BrowserWindow
+  /**
+   * Creates a browser window from a `WindowAttributes` snapshot.
+   *
+   * The icon must be square RGBA pixel data. It is applied on Windows and Linux only.
+   * The theme is honored on all platforms. The `menubar` flag is currently ignored.
+   */
+  createBrowserWindowFromAttributes(attrs: WindowAttributes): BrowserWindow
+  /**
+   * Sets the key-value store that windows created with `stateKey` load their geometry from and
+   * save it to. `get(key)` returns the stored string or nothing; `set(key, value)` stores it.
+   * Both are called synchronously. Values are JSON objects of the form
+   * `{"x": 10, "y": 20, "width": 800, "height": 600, "maximized": false}`, with the position and
+   * size of the unmaximized window in logical pixels. Geometry is saved 500 ms after the window
+   * stops moving or resizing, and when it is asked to close.
+   */
+  setStateStore(get: (arg: string) => string | null, set: (arg0: string, arg1: string) => void): void
+  /**
+   * Returns the modifier keys held down as of the last keyboard event received by any window
+   * of this application.
+   */
+  currentModifiers(): Modifiers
+  /**
+   * Returns the persistent profile with the given name, creating its directory if needed.
+   *
+   * Webviews created with the same profile share cookies and storage. Profiles are stored in
+   * `<data dir>/<appId>/profiles/<name>`, where the data directory is `$XDG_DATA_HOME` (or
+   * `~/.local/share`) on Linux, `~/Library/Application Support` on macOS and `%LOCALAPPDATA%`
+   * on Windows. Fails unless the application was created with `ApplicationOptions.appId`.
+   */
+  profile(name: string): Profile
+  /** Sets the information shown in the standard about panel. Ignored on platforms other than macOS. */
+  setAboutPanel(info: AboutPanel): void
+  /** Opens the standard about panel. Does nothing on platforms other than macOS. */
+  showAboutPanel(): void
+  /**
+   * Creates a window addressed by `name`, e.g. `main` or `settings`, for apps with one window
+   * per purpose. Fails if a window with that name is still open.
+   */
+  createNamedWindow(name: string, options?: BrowserWindowOptions | undefined | null): BrowserWindow
+  /** The window created with `createNamedWindow` under `name`, unless it has been closed. */
+  getWindow(name: string): BrowserWindow | null
+  /** Whether a window created with `createNamedWindow` under `name` is open. */
+  hasWindow(name: string): boolean
+  /**
+   * Destroys the window named `name` and frees the name. Returns whether such a window was
+   * open.
+   */
+  closeNamedWindow(name: string): boolean
+  /**
+   * The `BrowserWindow.id` of every window that has been created and not yet closed or
+   * destroyed. Windows that are still queued are not included.
+   */
+  getOpenWindowIds(): Array<string>
+  /** The number of windows listed by `getOpenWindowIds`. */
+  windowCount(): number
+  /**
+   * Lists the connected monitors, with sizes and positions in physical pixels. Unlike
+   * `BrowserWindow.getAvailableMonitors` this needs no window, so it can be used to place the
+   * first one. Fails while `run` is driving the event loop.
+   */
+  availableMonitors(): Array<Monitor>
+  /**
+   * The primary monitor, if the platform can tell which one it is. Fails while `run` is driving
+   * the event loop.
+   */
+  primaryMonitor(): Monitor | null
   exit(): void
   run(): void
   runIteration(): boolean
 }
 
 export declare class BrowserWindow {
+  /**
+   * Identifier of the window, as reported in `ApplicationEvent.windowId`. `uninitialized`
+   * until the window is built.
+   */
   get id(): string
-  createWebview(options?: WebviewOptions | undefined | null): Webview
+  /**
+   * The raw tao `WindowId` of this window as a `u64`, matching the low-level `Window.id`, for
+   * correlating with events from other tao integrations. Fails before the window is created.
+   */
+  nativeWindowId(): bigint
+  /**
+   * Returns the platform handle of the window, for compositing content drawn by another
+   * library next to the webviews. The handle belongs to the event loop thread: only use it there
+   * and only while the window exists. Fails before the window is created.
+   */
+  rawWindowHandle(): WindowHandle
+  /**
+   * Returns the position, size, maximized and fullscreen state of the window, for restoring it
+   * later with `applyGeometry`. Fails before the window is created.
+   */
+  getGeometry(): WindowGeometry
+  /**
+   * Restores a geometry returned by `getGeometry`. A window that would not be reachable on any
+   * connected monitor, e.g. one saved on a monitor that was unplugged since, is moved and if
+   * needed shrunk onto the primary monitor. Fails before the window is created.
+   */
+  applyGeometry(geometry: WindowGeometry): void
+  createWebview(options?: WebviewOptions | undefined | null, profile?: Profile | undefined | null): Webview
+  /** Sends a message to every webview in this window, like `Webview.send`. */
+  postMessageAll(json: string): Array<MessageDelivery>
+  webviewByLabel(label: string): Webview | null
   get isChild(): boolean
   isFocused(): boolean
   isVisible(): boolean
@@ -29,20 +145,186 @@ export declare class BrowserWindow {
   get theme(): Theme
   set theme(theme: Theme)
   setWindowIcon(icon: Buffer | string, width: number, height: number): void
+  /**
+   * Shows `menu` as the window's menu bar, with accelerators active while the window has focus.
+   * On macOS the menu becomes the application menu shared by all windows. Selections are
+   * reported to `Application.onMenuEvent`. Fails before the window is created.
+   */
+  setMenu(menu: Menu): void
+  /**
+   * Shows `menu` as a context menu at `x`, `y` in logical pixels relative to the window, or at
+   * the cursor when no position is given. Selections are reported to `Application.onMenuEvent`.
+   */
+  popupMenu(menu: Menu, x?: number | undefined | null, y?: number | undefined | null): void
+  /**
+   * Shows a placeholder title, and optionally an icon, while a page loads, so the window does not
+   * keep showing the previous page's title. The icon must be square RGBA pixel data and is
+   * applied on Windows and Linux only. The next title reported by one of the window's webviews
+   * (see `Webview.onTitleChanged`) replaces the placeholder title, and the window icon set
+   * before is restored.
+   */
+  setLoadingState(title: string, icon?: Buffer | undefined | null): void
   removeWindowIcon(): void
   setVisible(visible: boolean): void
   setProgressBar(state: ProgressBarState): void
+  /**
+   * Sets the window backdrop effect. The window must be created with `transparent: true`.
+   *
+   * Requires Windows 11 22H2 or macOS 10.14. Returns an error on other platforms and when the
+   * system rejects the effect.
+   */
+  setBackdrop(effect: BackdropEffect): void
+  /**
+   * Sets the opacity of the whole window, including its frame, from 0 (invisible) to 1 (opaque).
+   * Cancels a running `animateOpacity` animation. On Linux the effect requires a compositing
+   * window manager.
+   */
+  setWindowOpacity(opacity: number): void
+  /**
+   * Turns the blurred backdrop behind a transparent window on or off. Shorthand for
+   * `setBackdrop(BackdropEffect.Blur)` and `setBackdrop(BackdropEffect.None)`, with the same
+   * platform requirements.
+   */
+  setBlur(enabled: boolean): void
+  /**
+   * Fades the window from opacity `from` to `to` (0-1) over `durationMs`, driven by the event
+   * loop so the JS thread is not blocked. Cancels any animation already running on this window.
+   * An `OpacityAnimationFinished` application event is emitted when the target is reached; a
+   * canceled animation emits nothing. On Linux the effect requires a compositing window manager.
+   */
+  animateOpacity(from: number, to: number, durationMs: number): void
+  /**
+   * Reserves an overlay title bar of `height` with the window buttons inset by `button_inset`
+   * from the left edge, so web content can draw its own title bar underneath.
+   *
+   * On macOS the content view is extended under a transparent title bar and the traffic lights
+   * are moved, vertically centered in `height`. Linux has no native overlay; combine with
+   * `decorations: false` there. Unsupported on Windows, where the caption buttons cannot be
+   * drawn over web content. The values are exposed to every page loaded in the window as the
+   * `--titlebar-overlay-height` and `--titlebar-button-inset` CSS variables.
+   */
+  setTitlebarOverlay(height: number, buttonInset: number): void
+  /** The overlay title bar set with `setTitlebarOverlay`, if any. */
+  get titlebarOverlay(): TitlebarOverlay | null
   setMaximized(value: boolean): void
   setMinimized(value: boolean): void
   focus(): void
+  /**
+   * Moves the window with the mouse, e.g. from a custom HTML titlebar of an undecorated window.
+   * Only works while a mouse button is held, so call it from a `mousedown` handler.
+   */
+  startDrag(): void
+  /**
+   * Resizes the window with the mouse from the edge or corner in `direction`. Like `startDrag`,
+   * only works while a mouse button is held.
+   */
+  startResizeDrag(direction: ResizeDirection): void
+  /**
+   * Shows `count` as an unread badge: the dock badge on macOS, a taskbar overlay icon on Windows
+   * and the launcher count on Linux desktops implementing the Unity launcher API, which need the
+   * application's `desktopFile`, e.g. `application://my-app.desktop`. `0` or no count clears
+   * the badge.
+   */
+  setBadgeCount(count?: number | undefined | null, desktopFile?: string | undefined | null): void
+  /** Size of the window's client area in physical pixels. */
+  innerSize(): Dimensions
+  /** Size of the whole window, including its frame, in physical pixels. */
+  outerSize(): Dimensions
+  /**
+   * Ratio of physical to logical pixels on the window's current monitor. Returns 1 before the
+   * window is created.
+   */
+  scaleFactor(): number
+  /** Resizes the window's client area, in physical pixels. */
+  setInnerSize(width: number, height: number): void
+  /** Sets the smallest client area the user can resize the window to, in logical pixels. */
+  setMinInnerSize(width: number, height: number): void
+  /** Sets the largest client area the user can resize the window to, in logical pixels. */
+  setMaxInnerSize(width: number, height: number): void
+  /** Position of the window's top-left corner, including decorations, in logical pixels. */
+  outerPosition(): Position
+  /** Moves the window's top-left corner, including decorations, in logical pixels. */
+  setOuterPosition(x: number, y: number): void
+  /**
+   * Whether the window was created with `transparent: true` and can actually be transparent.
+   *
+   * On Linux this also requires a running compositor and an RGBA visual on the window's screen;
+   * without them transparent areas render black. Windows and macOS always support transparency,
+   * so the requested flag is returned there. Returns `false` until the window is created.
+   */
+  isTransparentEffective(): boolean
+  /**
+   * Restricts the visible and clickable area of the window to the union of `regions`, given in
+   * logical pixels relative to the window. Pass an empty list to restore the full window.
+   *
+   * Uses `SetWindowRgn` on Windows and GTK shape regions on Linux. Many Wayland compositors
+   * ignore the visible shape and only honor the input region. Not supported on macOS.
+   */
+  setShape(regions: Array<Rectangle>): void
+  /**
+   * Captures the whole window as PNG bytes.
+   *
+   * Uses `PrintWindow` on Windows, `CGWindowListCreateImage` on macOS (which needs the screen
+   * recording permission) and GDK on Linux, where only the client area is captured and Wayland
+   * sessions are unsupported.
+   */
+  captureWindow(): Promise<Buffer>
+  /**
+   * Brings the window in front of the other windows without focusing it or making it
+   * topmost. Windows and X11 may keep it behind another application's active window, and
+   * Wayland compositors ignore restacking requests.
+   */
+  raise(): void
+  /**
+   * Sends the window behind the other windows without minimizing it. On Windows and X11 this
+   * also places it behind other applications' windows; on macOS it stays within its window
+   * level.
+   */
+  lower(): void
+  /** Selects the next native tab. Only supported on macOS. */
+  selectNextTab(): void
+  /** Selects the previous native tab. Only supported on macOS. */
+  selectPreviousTab(): void
+  /** Merges all windows into tabs of this window. Only supported on macOS. */
+  mergeAllWindows(): void
   getAvailableMonitors(): Array<Monitor>
   getPrimaryMonitor(): Monitor | null
   setContentProtection(enabled: boolean): void
   setAlwaysOnTop(enabled: boolean): void
   setAlwaysOnBottom(enabled: boolean): void
+  /**
+   * Shows the window on every workspace or virtual desktop, or only on the current one. Supported
+   * on macOS and Linux; ignored on Windows.
+   */
+  setVisibleOnAllWorkspaces(enabled: boolean): void
+  /**
+   * Keeps the window above other apps' fullscreen spaces, e.g. for a recording indicator, and
+   * shows it on every space. Only supported on macOS, where it raises the window to the screen
+   * saver level; disabling it returns the window to the normal level. Some macOS versions only
+   * honor this for apps with accessibility or screen recording permissions.
+   */
+  setAboveFullscreen(enabled: boolean): void
   setDecorations(enabled: boolean): void
   get fullscreen(): FullscreenType | null
+  /**
+   * Enters fullscreen with the given type, or leaves fullscreen when `None` is passed.
+   * Exclusive fullscreen uses the current monitor's first video mode; use
+   * `setExclusiveFullscreen` to pick one.
+   */
+  setFullscreen(fullscreen?: FullscreenType | undefined | null): void
+  /**
+   * Enters exclusive fullscreen with the video mode matching `width`x`height` at
+   * `refresh_rate` Hz on the monitor named `monitor_name`, or on the current monitor when no
+   * name is given. Fails with the list of available modes when none matches.
+   */
+  setExclusiveFullscreen(monitorName: string | undefined | null, width: number, height: number, refreshRate: number): void
   show(): void
+  /**
+   * Closes the window for good, unlike hiding it. Its webviews are dropped first, then the
+   * native window is released and the application stops tracking it. Webviews that were not
+   * created yet are discarded, and later calls on this window and its webviews do nothing.
+   */
+  destroy(): void
 }
 
 /** Event loop for handling window events. */
@@ -78,6 +360,36 @@ export declare class EventLoopWindowTarget {
 
 }
 
+/** A menu bar or context menu. */
+export declare class Menu {
+  /** Creates an empty menu. */
+  constructor()
+  /** Appends an item. */
+  appendItem(item: MenuItemOptions): void
+  /** Appends a separator line. */
+  appendSeparator(): void
+  /** Appends a submenu. On macOS, items of a menu bar must all be submenus. */
+  appendSubmenu(submenu: Submenu): void
+}
+
+/** A named, persistent browsing profile shared by the webviews created with it. */
+export declare class Profile {
+  get name(): string
+  get path(): string
+}
+
+/** A nested menu, shown as a menu bar entry or as a cascading item. */
+export declare class Submenu {
+  /** Creates an empty submenu titled `text`. */
+  constructor(text: string, enabled?: boolean | undefined | null)
+  /** Appends an item. */
+  appendItem(item: MenuItemOptions): void
+  /** Appends a separator line. */
+  appendSeparator(): void
+  /** Appends a nested submenu. */
+  appendSubmenu(submenu: Submenu): void
+}
+
 /** The web context for a webview. */
 export declare class WebContext {
   /** Creates a new web context with the given data directory. */
@@ -91,15 +403,255 @@ export declare class Webview {
   get label(): string
   onIpcMessage(handler?: IpcHandler | undefined | null): void
   on(handler: IpcHandler): void
+  /**
+   * Adds a listener for IPC messages that receives the full request together with the
+   * `windowId` and `label` of the sending webview, for routing messages from several webviews.
+   */
+  onIpcMessageWithSource(handler: ((err: Error | null, arg: IpcMessage) => any)): void
+  /**
+   * Answers a message the page sent with `window.__webview_invoke__(message)`, resolving the
+   * returned promise with `body` decoded as UTF-8. `requestId` is the `IpcMessage.requestId`
+   * delivered to `onIpcMessageWithSource` handlers; such requests are not passed to
+   * `onIpcMessage` handlers, which cannot reply. The page's promise is rejected instead when no
+   * `onIpcMessageWithSource` handler is registered or a handler throws.
+   */
+  reply(requestId: string, body: Buffer): void
   send(message: string): void
   loadUrl(url: string): void
+  /**
+   * Checks whether the page still runs scripts by asking it to answer over IPC. Resolves to
+   * `false` if no answer arrives within `timeoutMs` (default 1000), e.g. because the page is
+   * stuck in a long-running script. Combine with `restart` to recover a hung webview.
+   */
+  isResponsive(timeoutMs?: number | undefined | null): Promise<boolean>
+  /**
+   * Resolves to the HTML of the whole document, `document.documentElement.outerHTML`. Pages
+   * larger than 16 MiB are returned too, with a warning from `process.emitWarning`. Rejects if
+   * the HTML is not returned within `timeoutMs`, 5000 by default. Fails if the webview has not
+   * been created yet.
+   */
+  getHtml(timeoutMs?: number | undefined | null): Promise<string>
+  /**
+   * Evaluates `js` in the page and resolves to its result serialized as JSON, e.g. `"\"Home\""`
+   * for `document.title`; parse it with `JSON.parse`. Rejects if there is no result within
+   * `timeoutMs`, 5000 by default. The engine answers on the event loop thread, so a blocking
+   * variant would deadlock; await the promise instead. Fails if the webview has not been created
+   * yet.
+   */
+  evaluateScriptForResult(js: string, timeoutMs?: number | undefined | null): Promise<string>
+  /**
+   * Resolves to the text currently selected in the page, or an empty string if there is no
+   * selection. Rejects if the text is not returned within `timeoutMs`, 5000 by default. Fails
+   * if the webview has not been created yet.
+   */
+  getSelectedText(timeoutMs?: number | undefined | null): Promise<string>
+  /**
+   * Tears down the webview and builds it again with its original options, loading the page it
+   * was showing. Use it to recover from a hung or crashed page. All in-page state, such as the
+   * DOM, scripts and unsaved form input, is lost; handlers registered on this object are kept.
+   */
+  restart(): void
+  /**
+   * Resets the webview to a blank page so it can be reused instead of creating a new one.
+   *
+   * Clears the session storage of the current page, then navigates to `about:blank`, which
+   * drops the document, its scripts and any injected CSS or gesture settings. Actions queued
+   * before the webview was created are discarded. Cookies, local storage, IndexedDB, the HTTP
+   * cache, initialization scripts and registered handlers are kept; call
+   * `clearAllBrowsingData` as well to remove stored data.
+   */
+  reset(): void
   loadHtml(html: string): void
+  /**
+   * Loads `html` with relative URLs resolved against `base_url`, by inserting a `<base>` element
+   * into its head. Pair with `WebviewOptions.assetRoot` to reference local files.
+   */
+  loadHtmlWithBaseUrl(html: string, baseUrl: string): void
   evaluateScript(js: string): void
   openDevtools(): void
   closeDevtools(): void
+  /**
+   * Lists the cookies of the webview's profile, or only those sent to `url` when given. Fails if
+   * the webview has not been created yet.
+   */
+  getCookies(url?: string | undefined | null): Array<CookieData>
+  /**
+   * Stores a cookie in the webview's profile, replacing one with the same name, domain and path.
+   * Cookies with an `expires` time persist across launches when the profile does. Fails if the
+   * webview has not been created yet.
+   */
+  setCookie(cookie: CookieData): void
+  /**
+   * Removes the cookie matching the name, domain and path of `cookie`. Fails if the webview has
+   * not been created yet.
+   */
+  deleteCookie(cookie: CookieData): void
+  /**
+   * Clears all browsing data of the webview's profile, including cookies, storage and the cache.
+   * Fails if the webview has not been created yet.
+   */
+  clearAllBrowsingData(): void
   isDevtoolsOpen(): boolean
   reload(): void
+  /** Navigates back in the session history, like `history.back()`. */
+  goBack(): void
+  /** Navigates forward in the session history, like `history.forward()`. */
+  goForward(): void
+  /**
+   * URL of the current page, including changes made with `history.pushState`. `null` before the
+   * webview is built; the URL of the last page load event if the engine cannot report it.
+   */
+  currentUrl(): string | null
+  /** Title of the current document as last reported by the engine. */
+  currentTitle(): string | null
+  /**
+   * Moves keyboard focus into the webview, e.g. when it hosts a dialog that was just opened.
+   * Falls back to `window.focus()` in the page if the engine refuses. Fails if the webview has
+   * not been created yet or the fallback fails too.
+   */
+  focus(): void
+  /** Whether the page has keyboard focus, as last reported by its `focus` and `blur` events. */
+  isFocused(): boolean
+  /** Whether a page load has started and not finished or failed yet. */
+  isLoading(): boolean
+  /**
+   * Whether there is a previous history entry, as reported by the page asynchronously after each
+   * navigation. `null` until the page has reported it, and on engines without the Navigation
+   * API, e.g. WebKitGTK and older WebKit on macOS, where the position is not observable.
+   */
+  canGoBack(): boolean | null
+  /** Whether there is a next history entry. `null` when unknown, like `canGoBack`. */
+  canGoForward(): boolean | null
   print(): void
+  /**
+   * Moves and resizes the webview, in logical pixels relative to the window's client area.
+   * Replaces any bounds set with `setBoundsRelative`, so the webview keeps this position and size
+   * when the window is resized.
+   */
+  setBounds(x: number, y: number, width: number, height: number): void
+  /**
+   * The position and size of the webview, in logical pixels relative to the window's client
+   * area.
+   */
+  bounds(): Rect
+  /**
+   * Captures the visible part of the webview as PNG bytes, e.g. for visual regression tests.
+   * The engine renders the snapshot itself (`CapturePreview` on WebView2, `takeSnapshot` on
+   * WKWebView and `webkit_web_view_get_snapshot` on WebKitGTK), so windows covering the webview
+   * are not included and no screen recording permission is needed. The engine answers on the
+   * event loop thread, so await the promise instead of blocking. Rejects if there is no snapshot
+   * within `timeoutMs`, 5000 by default. Fails if the webview has not been created yet.
+   */
+  capturePage(timeoutMs?: number | undefined | null): Promise<Buffer>
+  /**
+   * Sets the webview bounds as fractions (0-1) of the window's inner size.
+   * The bounds are recomputed whenever the window is resized.
+   */
+  setBoundsRelative(x: number, y: number, width: number, height: number): void
+  /**
+   * Sets a handler called with `true` when web content enters fullscreen (HTML5 Fullscreen API)
+   * and `false` when it leaves it.
+   */
+  onFullscreenRequest(handler?: (((err: Error | null, arg: boolean) => any)) | undefined | null): void
+  /**
+   * Sets a handler notified about download progress, at most every 250 ms per download and
+   * once more when a download has finished successfully.
+   *
+   * Progress comes from the engine's download events on Windows and Linux, where `totalBytes`
+   * is set when the server sent the size. WKWebView does not report progress, so on macOS it is
+   * approximated from the size of the destination file and `totalBytes` is only set in the
+   * final notification; there, downloads are only tracked by webviews built while a handler is
+   * set, so set it before the window is created, or call `restart` afterwards.
+   */
+  onDownloadProgress(handler?: (((err: Error | null, arg: DownloadProgress) => any)) | undefined | null): void
+  /**
+   * Sets a handler deciding whether the webview may navigate to a URL. Returning `false` cancels
+   * the navigation, e.g. to open external links in the system browser instead. The handler runs
+   * synchronously while the engine waits for the decision; if it throws, the navigation is
+   * allowed.
+   */
+  onNavigation(handler?: ((arg: string) => boolean) | undefined | null): void
+  /**
+   * Sets a handler for files dragged over the webview, receiving the real file paths that the
+   * page itself cannot see. Returning `true` consumes the event so the page does not receive
+   * it; like `onNavigation`, the handler runs synchronously.
+   */
+  onDragDrop(handler?: ((arg: DragDropEventData) => boolean) | undefined | null): void
+  /**
+   * Sets a handler deciding camera, microphone, geolocation and notification requests from web
+   * content, e.g. to always allow the camera for the app's own origin. Like `onNavigation` the
+   * handler runs synchronously; returning `Default`, throwing or having no handler leaves the
+   * request to the engine. WebView2 asks for the camera and the microphone separately, so
+   * `CameraAndMicrophone` is only reported on Linux. Not supported on macOS, where WKWebView
+   * requests are not routed to the handler, so setting one fails; clearing it with `null` always
+   * succeeds.
+   */
+  onPermissionRequest(handler?: ((arg: PermissionRequest) => PermissionDecision) | undefined | null): void
+  /**
+   * Sets a handler called when a page starts loading and again when it has finished loading,
+   * with the page URL.
+   */
+  onPageLoad(handler?: (((err: Error | null, arg: PageLoadEventData) => any)) | undefined | null): void
+  /**
+   * Sets a handler called with the new `document.title` whenever it changes, e.g. to mirror it
+   * with `BrowserWindow.setTitle`.
+   */
+  onTitleChanged(handler?: (((err: Error | null, arg: string) => any)) | undefined | null): void
+  /**
+   * Sets a handler called with the absolute URL of the page's icon whenever it changes: the
+   * first `<link rel="icon">`, else `/favicon.ico` for http and https pages, else an empty
+   * string. Tracked by a script injected into every page, so it is not reported for pages
+   * whose content security policy blocks it.
+   */
+  onFaviconChanged(handler?: (((err: Error | null, arg: string) => any)) | undefined | null): void
+  /**
+   * Sets the handler serving the schemes listed in `customProtocols`. It receives the request and
+   * returns the response status, headers and body. Requests fail with status 404 while no
+   * handler is set, and with 500 when the handler throws or returns invalid headers.
+   */
+  onCustomProtocol(handler?: (((err: Error | null, arg: IpcMessage) => CustomProtocolResponse)) | undefined | null): void
+  /**
+   * Emits a `LoadTimeout` application event when a page has not finished loading within
+   * `ms` milliseconds of starting to load. `0` disables the guard.
+   */
+  setLoadTimeout(ms: number): void
+  /**
+   * Scrolls the document to `x`, `y` with `window.scrollTo`. Fails if the webview has not been
+   * created yet.
+   */
+  scrollTo(x: number, y: number, behavior?: ScrollBehavior | undefined | null): void
+  /**
+   * Scrolls the document by `dx`, `dy` with `window.scrollBy`. Fails if the webview has not
+   * been created yet.
+   */
+  scrollBy(dx: number, dy: number, behavior?: ScrollBehavior | undefined | null): void
+  /**
+   * Remembers the scroll position of the current page, keyed by its URL. Positions are kept in
+   * session storage, so they last for the lifetime of the webview.
+   */
+  saveScrollPosition(): void
+  /** Scrolls the current page back to the position saved for its URL, if any. */
+  restoreScrollPosition(): void
+  /**
+   * Sets the zoom level, where `1.0` is 100%. The level must be between `0.25` and `5.0` and is
+   * kept across navigations and reloads. Applied once the page has loaded if the webview has
+   * not been created yet.
+   */
+  zoom(scaleFactor: number): void
+  /** Returns the zoom level last set with `zoom`, `1.0` by default. */
+  getZoom(): number
+  /**
+   * Enables or disables pinch-to-zoom, also for pages loaded later.
+   * Engines only expose this at build time, so it is enforced by a script that
+   * cancels pinch gestures.
+   */
+  setPinchZoomEnabled(enabled: boolean): void
+  /**
+   * Enables or disables trackpad swipe navigation, also for pages loaded later.
+   * Engines only expose this at build time, so it is enforced by disabling
+   * horizontal overscroll.
+   */
+  setSwipeNavigationEnabled(enabled: boolean): void
 }
 
 /** The main webview struct. */
@@ -118,12 +670,18 @@ export declare class WebView {
   isDevtoolsOpen(): boolean
   /** Reloads the current page. */
   reload(): void
+  /** Clears all browsing data, including cookies, storage and the cache. */
+  clearAllBrowsingData(): void
+  /** Gets the URL of the current page. */
+  url(): string
   /** Prints the current page. */
   print(): void
   /** Loads a new URL in the webview. */
   loadUrl(url: string): void
   /** Loads HTML content in the webview. */
   loadHtml(html: string): void
+  /** Sets the position and size of the webview in logical pixels. */
+  setBounds(bounds: Rect): void
   /** Registers a callback for IPC messages. */
   on(callback: (error: Error | null, message: string) => void): void
   /**
@@ -199,6 +757,21 @@ export declare class WebViewBuilder {
   withIpcHandler(callback: (error: Error | null, message: string) => void): this
   /** Adds multiple IPC handlers for the webview. */
   withIpcHandlers(handlers: Array<IpcHandler>): this
+  /**
+   * Sets the `Accept-Language` value, e.g. `de-DE,de;q=0.9,en;q=0.8`.
+   *
+   * WebView2 and WebKitGTK apply it to every request. WebKitGTK sets it on the web context, so
+   * it also affects webviews sharing that context. Other backends only send it with the initial
+   * page request.
+   */
+  withAcceptLanguage(acceptLanguage: string): this
+  /**
+   * Adds an HTTP header sent with the initial page request, e.g. an `Authorization` token.
+   * Later navigations and subresource requests do not carry it.
+   */
+  withHeader(name: string, value: string): this
+  /** Sets the web context, sharing its data directory with other webviews using it. */
+  withWebContext(context: WebContext): this
   /** Builds the webview on an existing window. */
   buildOnWindow(window: Window, label: string, ipcListenersOverride?: Array<IpcHandler> | undefined | null): WebView
   /** Builds the webview. */
@@ -247,11 +820,13 @@ export declare class Window {
   isAlwaysOnTop(): boolean
   /** Sets whether the window is always on top. */
   setAlwaysOnTop(alwaysOnTop: boolean): void
+  /** Sets whether the window is shown on all workspaces. Ignored on Windows. */
+  setVisibleOnAllWorkspaces(visible: boolean): void
   /** Gets whether the window is focused. */
   isFocused(): boolean
   /** Requests the window to be focused. */
   requestFocus(): void
-  /** Gets the current cursor icon. */
+  /** Gets the cursor icon last set with `setCursorIcon`. */
   cursorIcon(): CursorIcon
   /** Sets the cursor icon. */
   setCursorIcon(cursor: CursorIcon): void
@@ -261,6 +836,11 @@ export declare class Window {
   cursorPosition(): Position
   /** Drags the window. */
   dragWindow(): boolean
+  /**
+   * Resizes the window with the mouse from the edge or corner in `direction`, while a button
+   * is held.
+   */
+  dragResizeWindow(direction: ResizeDirection): boolean
   /** Sets the window theme. */
   setTheme(theme: TaoTheme): void
   /** Gets the window theme. */
@@ -271,7 +851,10 @@ export declare class Window {
   setIgnoreCursorEvents(ignore: boolean): void
   /** Requests a redrawing of the window. */
   requestRedraw(): void
-  /** Closes the window. */
+  /**
+   * Closes the window. tao destroys a window when it is dropped, so the window is hidden right
+   * away and destroyed once no other handle to it remains.
+   */
   close(): void
 }
 
@@ -309,19 +892,62 @@ export declare class WindowBuilder {
   build(eventLoop: EventLoop): Window
 }
 
+/** Information shown in the standard about panel. Only used on macOS. */
+export interface AboutPanel {
+  applicationName?: string
+  version?: string
+  credits?: string
+  copyright?: string
+  /** Encoded image (e.g. PNG) used as the panel icon. */
+  icon?: Buffer
+}
+
 export interface ApplicationEvent {
   event: WebviewApplicationEvent
+  /** The new window theme, set for `ThemeChanged` events. */
+  theme?: Theme
+  /** The URL of the page that did not finish loading, set for `LoadTimeout` events. */
+  url?: string
+  /**
+   * The `BrowserWindow.id` of the window the event belongs to, set for all window events and
+   * `LoadTimeout`.
+   */
+  windowId?: string
+  /** The new inner size in logical pixels, set for `WindowResized` events. */
+  size?: Dimensions
+  /** The new outer position in logical pixels, set for `WindowMoved` events. */
+  position?: Position
 }
 
 export interface ApplicationOptions {
+  /** Loop mode of `Application.run`. Unset, the loop sleeps until the next event. */
   controlFlow?: ControlFlow
+  /** Interval in milliseconds between wake-ups with `ControlFlow.WaitUntil`. Defaults to 100. */
   waitTime?: number
+  /** Exit code used with `ControlFlow.ExitWithCode`. */
   exitCode?: number
+  /**
+   * Identifier naming the application's data directory, e.g. `com.example.editor`. Required by
+   * `Application.profile`, as the executable is `node` or `bun` for every script.
+   */
+  appId?: string
 }
 
 /** Returns a list of all available monitors. */
 export declare function availableMonitors(): Array<MonitorInfo>
 
+/**
+ * Translucent window backdrop. Mapped to `DWMWA_SYSTEMBACKDROP_TYPE` on Windows and to an
+ * `NSVisualEffectView` material on macOS.
+ */
+export declare const enum BackdropEffect {
+  None = 0,
+  Blur = 1,
+  Acrylic = 2,
+  Mica = 3,
+  Tabbed = 4
+}
+
 /** Background throttling policy for webviews. */
 export declare const enum BackgroundThrottlingPolicy {
   /** Throttling is suspended when the page is in the background. */
@@ -361,8 +987,49 @@ export interface BrowserWindowOptions {
   focused?: boolean
   transparent?: boolean
   fullscreen?: FullscreenType
+  /** Windows sharing a tabbing identifier are merged into native tabs. Only used on macOS. */
+  tabbingIdentifier?: string
+  /** Minimum client area width the user can resize the window to, in logical pixels. */
+  minWidth?: number
+  /** Minimum client area height, in logical pixels. */
+  minHeight?: number
+  /** Maximum client area width the user can resize the window to, in logical pixels. */
+  maxWidth?: number
+  /** Maximum client area height, in logical pixels. */
+  maxHeight?: number
+  /**
+   * Replaces the system title bar and frame with chrome drawn by the app. Overrides
+   * `decorations`.
+   */
+  customDecorations?: CustomDecorations
+  /**
+   * Key under which the window geometry is kept in the store set with
+   * `Application.setStateStore`. Saved geometry overrides `x`, `y`, `width`, `height` and
+   * `maximized`.
+   */
+  stateKey?: string
+}
+
+/**
+ * HTTP cache policy of a webview.
+ *
+ * `NoCache` shrinks the WebView2 disk cache to a single byte and uses the WebKitGTK document
+ * viewer cache model, which applies to the whole web context. WKWebView has no per-webview cache
+ * policy, and no backend can be restricted to cached responses, so those combinations fail with
+ * an unsupported error.
+ */
+export declare const enum CacheMode {
+  Default = 0,
+  NoCache = 1,
+  OnlyFromCache = 2
 }
 
+/**
+ * Checks whether the system webview runtime is installed. On Windows a missing WebView2
+ * runtime is reported as unavailable together with its download URL.
+ */
+export declare function checkWebviewRuntime(): WebviewRuntimeStatus
+
 export declare const enum ControlFlow {
   Poll = 0,
   WaitUntil = 1,
@@ -370,6 +1037,19 @@ export declare const enum ControlFlow {
   ExitWithCode = 3
 }
 
+/** An HTTP cookie stored by a webview's profile. */
+export interface CookieData {
+  name: string
+  value: string
+  /** Domain the cookie is sent to. Required by WebKitGTK when setting a cookie. */
+  domain?: string
+  path?: string
+  secure?: boolean
+  httpOnly?: boolean
+  /** Expiry as seconds since the Unix epoch. Unset for session cookies. */
+  expires?: number
+}
+
 /** Cursor icon change details. */
 export interface CursorChangeDetails {
   /** The new cursor icon. */
@@ -415,6 +1095,44 @@ export interface CursorPosition {
   y: number
 }
 
+/**
+ * Window chrome drawn by the app instead of the system. The window is created without
+ * decorations; the page draws its own title bar and the window stays movable and resizable.
+ *
+ * Elements with a `data-webview-drag-region` attribute move the window when dragged and toggle
+ * maximize when double-clicked. Only the element carrying the attribute reacts, so buttons
+ * inside a drag region keep working.
+ */
+export interface CustomDecorations {
+  /**
+   * Width of the invisible resize border along the window edges, in CSS pixels. Defaults to 8;
+   * 0 disables resizing from the edges.
+   */
+  resizeBorder?: number
+  /**
+   * Keeps the native drop shadow. Defaults to `true`. Supported on Windows and macOS; on Linux
+   * the window manager decides.
+   */
+  shadow?: boolean
+  /**
+   * Rounds the window corners with the system radius. Defaults to `true`. Only supported on
+   * Windows 11; ignored elsewhere.
+   */
+  roundedCorners?: boolean
+}
+
+/** Response returned by the handler set with `Webview.onCustomProtocol`. */
+export interface CustomProtocolResponse {
+  /** HTTP status code. Defaults to 200. */
+  status?: number
+  /**
+   * Response headers. Without a `Content-Type` header, the type is derived from the file
+   * extension of the URL.
+   */
+  headers?: Array<HeaderData>
+  body?: Buffer
+}
+
 /** Device event type. */
 export type DeviceEvent =
   | { type: 'MouseMotion', deltaX: number, deltaY: number }
@@ -433,6 +1151,13 @@ export interface Dimensions {
   height: number
 }
 
+export interface DownloadProgress {
+  url: string
+  receivedBytes: number
+  /** Total size of the download, when known. */
+  totalBytes?: number
+}
+
 /** Drag drop event. */
 export declare const enum DragDropEvent {
   /** The drag has entered the webview area. */
@@ -445,6 +1170,14 @@ export declare const enum DragDropEvent {
   Dropped = 3
 }
 
+export interface DragDropEventData {
+  kind: DragDropEvent
+  /** Paths of the dragged files. Only set for `Entered` and `Dropped`. */
+  paths: Array<string>
+  /** Cursor position relative to the webview in physical pixels. Not set for `Left`. */
+  position?: Position
+}
+
 /** Element state for input devices. */
 export declare const enum ElementState {
   Pressed = 0,
@@ -550,6 +1283,15 @@ export interface IpcMessage {
   method: string
   headers: Array<HeaderData>
   uri: string
+  /** `BrowserWindow.id` of the window hosting the sending webview. */
+  windowId?: string
+  /** Label of the sending webview. */
+  label?: string
+  /**
+   * Set when the page sent the message with `window.__webview_invoke__`, which waits for an
+   * answer. Pass it to `Webview.reply` to resolve the page's promise.
+   */
+  requestId?: string
 }
 
 /** Keyboard key. */
@@ -917,6 +1659,36 @@ export declare const enum KeyLocation {
   Numpad = 3
 }
 
+/** Selection of a menu item, passed to the handler set with `Application.onMenuEvent`. */
+export interface MenuEvent {
+  /** The `id` the item was created with. */
+  id: string
+}
+
+/** A clickable menu entry. */
+export interface MenuItemOptions {
+  /** Identifier reported as `MenuEvent.id` when the item is selected. */
+  id: string
+  text: string
+  /** Keyboard shortcut, e.g. `CmdOrCtrl+S` or `Alt+Shift+F4`. */
+  accelerator?: string
+  /** Defaults to `true`. */
+  enabled?: boolean
+}
+
+export interface MessageDelivery {
+  label: string
+  delivered: boolean
+  error?: string
+}
+
+export interface Modifiers {
+  shift: boolean
+  ctrl: boolean
+  alt: boolean
+  meta: boolean
+}
+
 /** Modifier key state. */
 export declare const enum ModifiersState {
   /** The Shift key is pressed. */
@@ -1049,6 +1821,41 @@ export declare const enum PageLoadEvent {
   Completed = 1
 }
 
+export interface PageLoadEventData {
+  event: PageLoadEvent
+  url: string
+}
+
+/** Answer to a permission request. */
+export declare const enum PermissionDecision {
+  Grant = 0,
+  Deny = 1,
+  /** Leaves the request to the engine, which prompts or denies as it normally would. */
+  Default = 2
+}
+
+/** Kind of capability requested by web content. */
+export declare const enum PermissionKind {
+  Camera = 0,
+  Microphone = 1,
+  /**
+   * Camera and microphone requested together by one `getUserMedia` call. Only reported on
+   * Linux; WebView2 asks for them as two separate requests.
+   */
+  CameraAndMicrophone = 2,
+  Geolocation = 3,
+  Notifications = 4,
+  /** Any other capability, such as clipboard or pointer lock access. */
+  Other = 5
+}
+
+/** A capability requested by web content, passed to `Webview.onPermissionRequest`. */
+export interface PermissionRequest {
+  /** Origin of the requesting page, e.g. `https://example.com`. */
+  origin: string
+  kind: PermissionKind
+}
+
 /** 2D position. */
 export interface Position {
   /** The X coordinate. */
@@ -1084,7 +1891,10 @@ export declare const enum ProgressState {
   Error = 4
 }
 
-/** Proxy configuration. */
+/**
+ * Proxy configuration. Each proxy is given as `host:port`, e.g. `proxy.corp:3128` or
+ * `[::1]:1080`.
+ */
 export type ProxyConfig =
   | { type: 'None' }
   | { type: 'Http', field0: string }
@@ -1167,6 +1977,19 @@ export interface ScaleFactorChangeDetails {
   newInnerSize: Size
 }
 
+/** How `Webview.scrollTo` and `Webview.scrollBy` animate, mirroring `ScrollOptions.behavior`. */
+export declare const enum ScrollBehavior {
+  Auto = 0,
+  Smooth = 1,
+  Instant = 2
+}
+
+/** Value of `history.scrollRestoration` for every page loaded in a webview. */
+export declare const enum ScrollRestoration {
+  Auto = 0,
+  Manual = 1
+}
+
 /** 2D size. */
 export interface Size {
   /** The width. */
@@ -1235,6 +2058,24 @@ export interface ThemeChangeDetails {
   newTheme: TaoTheme
 }
 
+/** Space reserved for an overlay title bar, in logical pixels. */
+export interface TitlebarOverlay {
+  height: number
+  buttonInset: number
+}
+
+/**
+ * Oldest TLS version a webview accepts for its connections.
+ *
+ * Every supported engine already refuses TLS 1.0 and 1.1, so `Tls12` is always satisfied.
+ * `Tls13` is only enforced by WebView2; WebKitGTK and WKWebView expose no TLS settings, so it
+ * fails with an unsupported error there.
+ */
+export declare const enum TlsVersion {
+  Tls12 = 0,
+  Tls13 = 1
+}
+
 /** Touch event data. */
 export interface Touch {
   /** The touch identifier. */
@@ -1279,7 +2120,31 @@ export interface VideoMode {
 
 export declare const enum WebviewApplicationEvent {
   WindowCloseRequested = 0,
-  ApplicationCloseRequested = 1
+  ApplicationCloseRequested = 1,
+  ThemeChanged = 2,
+  LoadTimeout = 3,
+  /**
+   * Another native macOS tab of a tab group was selected. Not emitted when the window of the
+   * selected tab merely regains focus.
+   */
+  TabSelected = 4,
+  /** An animation started with `BrowserWindow.animateOpacity` reached its target opacity. */
+  OpacityAnimationFinished = 5,
+  /**
+   * The system is about to sleep. There is little time left to act; on Linux this requires
+   * systemd-logind on the system D-Bus.
+   */
+  SystemSuspend = 6,
+  /** The system woke up from sleep. On Linux this requires systemd-logind on the system D-Bus. */
+  SystemResume = 7,
+  /** A window gained keyboard focus. */
+  WindowFocused = 8,
+  /** A window lost keyboard focus. */
+  WindowBlurred = 9,
+  /** A window was resized; `size` holds its new inner size. */
+  WindowResized = 10,
+  /** A window was moved; `position` holds its new outer position. */
+  WindowMoved = 11
 }
 
 /** Attributes for creating a webview. */
@@ -1360,6 +2225,91 @@ export interface WebviewOptions {
   clipboard?: boolean
   autoplay?: boolean
   backForwardNavigationGestures?: boolean
+  /**
+   * URL patterns to block. `*` matches any sequence of characters and `?` matches a single
+   * character, e.g. `https://*.doubleclick.net/*`. Navigations are blocked natively on every
+   * platform; `fetch` and `XMLHttpRequest` requests are blocked by an injected script.
+   */
+  blockedUrlPatterns?: Array<string>
+  /**
+   * RGBA color painted over the webview until the first page has loaded, hiding the blank
+   * flash on startup. It is also used as the native background color. The alpha channel is
+   * honored, so transparent webviews stay see-through when a transparent color is given.
+   */
+  paintPlaceholderColor?: Buffer
+  /**
+   * Native background color shown before the page paints and behind transparent page content,
+   * as 3 or 4 RGBA bytes. Takes precedence over `paintPlaceholderColor` as the native
+   * background. An alpha below 255 only shows through with `transparent` enabled; WebView2
+   * only supports fully opaque or fully transparent colors.
+   */
+  backgroundColor?: Buffer
+  /**
+   * Label identifying the webview within its window. Must be unique per window; defaults to
+   * `webview-<n>`.
+   */
+  label?: string
+  /**
+   * `Accept-Language` sent to servers, e.g. `de-DE,de;q=0.9,en;q=0.8`. Applied natively on
+   * Windows and Linux; on macOS it is only sent with the initial page request.
+   */
+  acceptLanguage?: string
+  /**
+   * Headers sent with the initial page request, e.g. an `Authorization` token for a dashboard.
+   * Navigations started later by the page or with `loadUrl` do not carry them.
+   */
+  defaultHeaders?: Array<HeaderData>
+  /** HTTP cache policy. Defaults to the engine's normal caching. */
+  cacheMode?: CacheMode
+  /**
+   * Disables GPU acceleration as a compatibility escape hatch for blank or glitchy rendering in
+   * virtual machines and remote desktops. Rendering gets slower. Passes `--disable-gpu` to
+   * WebView2 and turns off hardware acceleration in WebKitGTK; ignored on macOS, where WKWebView
+   * cannot be configured.
+   */
+  disableGpu?: boolean
+  /**
+   * Schemes served by the handler set with `Webview.onCustomProtocol`, e.g. `app`, whose pages
+   * are loaded from `app://localhost/index.html`, or `http://app.localhost/index.html` on
+   * Windows, where WebView2 only serves custom schemes under that form. On Linux a scheme can
+   * only be registered once per profile.
+   */
+  customProtocols?: Array<string>
+  /**
+   * Sets `history.scrollRestoration` on every page. Use `Manual` together with
+   * `Webview.saveScrollPosition` and `Webview.restoreScrollPosition` for deterministic
+   * scroll positions across navigations.
+   */
+  scrollRestoration?: ScrollRestoration
+  /** Oldest TLS version accepted for connections. See `TlsVersion` for platform support. */
+  minTlsVersion?: TlsVersion
+  /**
+   * Proxy for the webview's traffic. On Linux it applies to the whole web context, shared with
+   * webviews of the same profile. Not supported on macOS.
+   */
+  proxy?: ProxyConfig
+  /**
+   * Directory storing cookies, local storage and the cache, so sessions survive restarts.
+   * Webviews of the same application given the same directory share one session. The directory
+   * is created if needed; on Windows it becomes the WebView2 user data folder. Cannot be
+   * combined with a profile. WebView2 applies `acceptLanguage`, `cacheMode`, `disableGpu`,
+   * `minTlsVersion` and `proxy` per user data folder, so webviews sharing a folder must use the
+   * same values for them; creating one with different values fails.
+   */
+  dataDirectory?: string
+  /**
+   * Directory served read-only under the `asset` scheme, e.g. `asset://localhost/index.html`
+   * (`http://asset.localhost/index.html` on Windows). Use it as the base URL of
+   * `Webview.loadHtmlWithBaseUrl` so relative scripts, styles and images resolve to local files.
+   */
+  assetRoot?: string
+}
+
+export interface WebviewRuntimeStatus {
+  available: boolean
+  version?: string
+  /** Where to download the runtime from, set when it is missing. */
+  installUrl?: string
 }
 
 /** Returns the version of the webview library. */
@@ -1445,6 +2395,28 @@ export interface WindowEventData {
   windowId: number
 }
 
+/** Window placement returned by `BrowserWindow.getGeometry`, in logical pixels. */
+export interface WindowGeometry {
+  /** Outer position of the window. */
+  x: number
+  y: number
+  /** Inner size of the window. */
+  width: number
+  height: number
+  maximized: boolean
+  fullscreen: boolean
+}
+
+/** Native handle of a window, for drawing into it with another library such as wgpu. */
+export interface WindowHandle {
+  /** `win32`, `appkit`, `xlib`, `xcb` or `wayland`. */
+  platform: string
+  /** The `HWND`, `NSView*`, X11 window id or `wl_surface*`. */
+  handle: bigint
+  /** The X11 `Display*` or `wl_display*` the window belongs to. Not set on Windows and macOS. */
+  display?: bigint
+}
+
 /** Window jump options. */
 export interface WindowJumpOptions {
   /** The window to jump. */
@@ -582,6 +582,9 @@ module.exports.EventLoop = nativeBinding.EventLoop
 module.exports.EventLoopBuilder = nativeBinding.EventLoopBuilder
 module.exports.EventLoopProxy = nativeBinding.EventLoopProxy
 module.exports.EventLoopWindowTarget = nativeBinding.EventLoopWindowTarget
+module.exports.Menu = nativeBinding.Menu
+module.exports.Profile = nativeBinding.Profile
+module.exports.Submenu = nativeBinding.Submenu
 module.exports.WebContext = nativeBinding.WebContext
 module.exports.Webview = nativeBinding.Webview
 module.exports.WebView = nativeBinding.WebView
@@ -589,8 +592,11 @@ module.exports.WebViewBuilder = nativeBinding.WebViewBuilder
 module.exports.Window = nativeBinding.Window
 module.exports.WindowBuilder = nativeBinding.WindowBuilder
 module.exports.availableMonitors = nativeBinding.availableMonitors
+module.exports.BackdropEffect = nativeBinding.BackdropEffect
 module.exports.BackgroundThrottlingPolicy = nativeBinding.BackgroundThrottlingPolicy
 module.exports.BadIcon = nativeBinding.BadIcon
+module.exports.CacheMode = nativeBinding.CacheMode
+module.exports.checkWebviewRuntime = nativeBinding.checkWebviewRuntime
 module.exports.ControlFlow = nativeBinding.ControlFlow
 module.exports.CursorIcon = nativeBinding.CursorIcon
 module.exports.DeviceEventFilter = nativeBinding.DeviceEventFilter
@@ -607,16 +613,21 @@ module.exports.ModifiersState = nativeBinding.ModifiersState
 module.exports.MouseButtonState = nativeBinding.MouseButtonState
 module.exports.NewWindowResponse = nativeBinding.NewWindowResponse
 module.exports.PageLoadEvent = nativeBinding.PageLoadEvent
+module.exports.PermissionDecision = nativeBinding.PermissionDecision
+module.exports.PermissionKind = nativeBinding.PermissionKind
 module.exports.primaryMonitor = nativeBinding.primaryMonitor
 module.exports.ProgressBarStatus = nativeBinding.ProgressBarStatus
 module.exports.ProgressState = nativeBinding.ProgressState
 module.exports.ResizeDirection = nativeBinding.ResizeDirection
+module.exports.ScrollBehavior = nativeBinding.ScrollBehavior
+module.exports.ScrollRestoration = nativeBinding.ScrollRestoration
 module.exports.StartCause = nativeBinding.StartCause
 module.exports.TaoControlFlow = nativeBinding.TaoControlFlow
 module.exports.TaoFullscreenType = nativeBinding.TaoFullscreenType
 module.exports.TaoTheme = nativeBinding.TaoTheme
 module.exports.taoVersion = nativeBinding.taoVersion
 module.exports.Theme = nativeBinding.Theme
+module.exports.TlsVersion = nativeBinding.TlsVersion
 module.exports.TouchPhase = nativeBinding.TouchPhase
 module.exports.UserAttentionType = nativeBinding.UserAttentionType
 module.exports.WebviewApplicationEvent = nativeBinding.WebviewApplicationEvent
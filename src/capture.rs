@@ -0,0 +1,635 @@
+//! Platform window capture used by `BrowserWindow::capture_window`, and engine snapshots used by
+//! `Webview::capture_page`.
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// A captured window as tightly packed RGBA pixels.
+pub(crate) struct CapturedImage {
+  pub(crate) width: u32,
+  pub(crate) height: u32,
+  pub(crate) rgba: Vec<u8>,
+}
+
+/// Encodes a captured image to PNG on the libuv thread pool.
+pub struct EncodePng {
+  image: CapturedImage,
+}
+
+impl EncodePng {
+  pub(crate) fn new(image: CapturedImage) -> Self {
+    Self { image }
+  }
+}
+
+#[napi]
+impl Task for EncodePng {
+  type Output = Vec<u8>;
+  type JsValue = Buffer;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, self.image.width, self.image.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+      .write_header()
+      .and_then(|mut writer| writer.write_image_data(&self.image.rgba))
+      .map_err(|e| {
+        napi::Error::new(
+          napi::Status::GenericFailure,
+          format!("Failed to encode PNG: {}", e),
+        )
+      })?;
+    Ok(png)
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output.into())
+  }
+}
+
+fn capture_error(message: &str) -> napi::Error {
+  napi::Error::new(napi::Status::GenericFailure, message.to_string())
+}
+
+/// Captures the whole window, including its frame, with `PrintWindow`.
+#[cfg(target_os = "windows")]
+pub(crate) fn capture_window(window: &tao::window::Window) -> Result<CapturedImage> {
+  use std::ffi::c_void;
+  use tao::platform::windows::WindowExtWindows;
+
+  #[repr(C)]
+  #[derive(Default)]
+  struct Rect {
+    left: i32,
+    top: i32,
+    right: i32,
+    bottom: i32,
+  }
+
+  #[repr(C)]
+  #[derive(Default)]
+  struct BitmapInfo {
+    size: u32,
+    width: i32,
+    height: i32,
+    planes: u16,
+    bit_count: u16,
+    compression: u32,
+    size_image: u32,
+    x_pels_per_meter: i32,
+    y_pels_per_meter: i32,
+    clr_used: u32,
+    clr_important: u32,
+    colors: [u32; 1],
+  }
+
+  #[link(name = "user32")]
+  extern "system" {
+    fn GetWindowRect(hwnd: isize, rect: *mut Rect) -> i32;
+    fn GetDC(hwnd: isize) -> isize;
+    fn ReleaseDC(hwnd: isize, hdc: isize) -> i32;
+    fn PrintWindow(hwnd: isize, hdc: isize, flags: u32) -> i32;
+  }
+
+  #[link(name = "gdi32")]
+  extern "system" {
+    fn CreateCompatibleDC(hdc: isize) -> isize;
+    fn CreateCompatibleBitmap(hdc: isize, width: i32, height: i32) -> isize;
+    fn SelectObject(hdc: isize, object: isize) -> isize;
+    fn GetDIBits(
+      hdc: isize,
+      bitmap: isize,
+      start: u32,
+      lines: u32,
+      bits: *mut c_void,
+      info: *mut BitmapInfo,
+      usage: u32,
+    ) -> i32;
+    fn DeleteObject(object: isize) -> i32;
+    fn DeleteDC(hdc: isize) -> i32;
+  }
+
+  const PW_RENDERFULLCONTENT: u32 = 2;
+
+  let hwnd = window.hwnd();
+  let mut rect = Rect::default();
+  if unsafe { GetWindowRect(hwnd, &mut rect) } == 0 {
+    return Err(capture_error("Failed to get the window bounds"));
+  }
+  let width = rect.right - rect.left;
+  let height = rect.bottom - rect.top;
+  if width <= 0 || height <= 0 {
+    return Err(capture_error("Cannot capture a window with an empty size"));
+  }
+
+  let mut pixels = vec![0u8; width as usize * height as usize * 4];
+  let captured = unsafe {
+    let screen_dc = GetDC(0);
+    let memory_dc = CreateCompatibleDC(screen_dc);
+    let bitmap = CreateCompatibleBitmap(screen_dc, width, height);
+    let previous = SelectObject(memory_dc, bitmap);
+
+    let printed = PrintWindow(hwnd, memory_dc, PW_RENDERFULLCONTENT) != 0;
+    let mut info = BitmapInfo {
+      size: (std::mem::size_of::<BitmapInfo>() - std::mem::size_of::<[u32; 1]>()) as u32,
+      width,
+      // A negative height requests a top-down bitmap.
+      height: -height,
+      planes: 1,
+      bit_count: 32,
+      ..Default::default()
+    };
+    let lines = GetDIBits(
+      memory_dc,
+      bitmap,
+      0,
+      height as u32,
+      pixels.as_mut_ptr() as *mut c_void,
+      &mut info,
+      0,
+    );
+
+    SelectObject(memory_dc, previous);
+    DeleteObject(bitmap);
+    DeleteDC(memory_dc);
+    ReleaseDC(0, screen_dc);
+    printed && lines == height
+  };
+  if !captured {
+    return Err(capture_error("Failed to capture the window"));
+  }
+
+  // GDI returns BGRX pixels.
+  for pixel in pixels.chunks_exact_mut(4) {
+    pixel.swap(0, 2);
+    pixel[3] = 255;
+  }
+
+  Ok(CapturedImage {
+    width: width as u32,
+    height: height as u32,
+    rgba: pixels,
+  })
+}
+
+/// Captures the window, including its title bar, with `CGWindowListCreateImage`.
+#[cfg(target_os = "macos")]
+pub(crate) fn capture_window(window: &tao::window::Window) -> Result<CapturedImage> {
+  use std::ffi::c_void;
+  use tao::platform::macos::WindowExtMacOS;
+
+  #[repr(C)]
+  struct CGRect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+  }
+
+  #[link(name = "CoreGraphics", kind = "framework")]
+  extern "C" {
+    fn CGWindowListCreateImage(
+      bounds: CGRect,
+      list_option: u32,
+      window_id: u32,
+      image_option: u32,
+    ) -> *mut c_void;
+    fn CGImageGetWidth(image: *mut c_void) -> usize;
+    fn CGImageGetHeight(image: *mut c_void) -> usize;
+    fn CGImageGetBitsPerPixel(image: *mut c_void) -> usize;
+    fn CGImageGetBytesPerRow(image: *mut c_void) -> usize;
+    fn CGImageGetDataProvider(image: *mut c_void) -> *mut c_void;
+    fn CGDataProviderCopyData(provider: *mut c_void) -> *mut c_void;
+    fn CGImageRelease(image: *mut c_void);
+  }
+
+  #[link(name = "CoreFoundation", kind = "framework")]
+  extern "C" {
+    fn CFDataGetBytePtr(data: *mut c_void) -> *const u8;
+    fn CFDataGetLength(data: *mut c_void) -> isize;
+    fn CFRelease(object: *mut c_void);
+  }
+
+  const LIST_OPTION_INCLUDING_WINDOW: u32 = 1 << 3;
+  const IMAGE_BOUNDS_IGNORE_FRAMING: u32 = 1 << 0;
+  // CGRectNull makes CoreGraphics use the bounds of the window itself.
+  let null_rect = CGRect {
+    x: f64::INFINITY,
+    y: f64::INFINITY,
+    width: 0.0,
+    height: 0.0,
+  };
+
+  let ns_window = unsafe { &*(window.ns_window() as *const objc2_app_kit::NSWindow) };
+  let window_id = ns_window.windowNumber() as u32;
+
+  unsafe {
+    let image = CGWindowListCreateImage(
+      null_rect,
+      LIST_OPTION_INCLUDING_WINDOW,
+      window_id,
+      IMAGE_BOUNDS_IGNORE_FRAMING,
+    );
+    if image.is_null() {
+      return Err(capture_error(
+        "Failed to capture the window. Screen recording permission may be required",
+      ));
+    }
+
+    let width = CGImageGetWidth(image);
+    let height = CGImageGetHeight(image);
+    let bytes_per_row = CGImageGetBytesPerRow(image);
+    if CGImageGetBitsPerPixel(image) != 32 {
+      CGImageRelease(image);
+      return Err(capture_error("Unsupported window image format"));
+    }
+
+    let data = CGDataProviderCopyData(CGImageGetDataProvider(image));
+    CGImageRelease(image);
+    if data.is_null() {
+      return Err(capture_error("Failed to read the window image"));
+    }
+    let bytes = std::slice::from_raw_parts(CFDataGetBytePtr(data), CFDataGetLength(data) as usize);
+
+    // Window images are BGRA rows, possibly padded past `width * 4` bytes.
+    let mut rgba = Vec::with_capacity(width * height * 4);
+    for row in bytes.chunks(bytes_per_row).take(height) {
+      for pixel in row[..width * 4].chunks_exact(4) {
+        rgba.extend_from_slice(&[pixel[2], pixel[1], pixel[0], pixel[3]]);
+      }
+    }
+    CFRelease(data);
+
+    Ok(CapturedImage {
+      width: width as u32,
+      height: height as u32,
+      rgba,
+    })
+  }
+}
+
+/// Captures the window contents through GDK. Window decorations drawn by the window manager are
+/// not included, and Wayland compositors do not allow reading window contents.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+pub(crate) fn capture_window(window: &tao::window::Window) -> Result<CapturedImage> {
+  use std::ffi::c_void;
+  use tao::platform::unix::WindowExtUnix;
+
+  extern "C" {
+    fn gtk_widget_get_window(widget: *mut c_void) -> *mut c_void;
+    fn gdk_window_get_width(window: *mut c_void) -> i32;
+    fn gdk_window_get_height(window: *mut c_void) -> i32;
+    fn gdk_pixbuf_get_from_window(
+      window: *mut c_void,
+      x: i32,
+      y: i32,
+      width: i32,
+      height: i32,
+    ) -> *mut c_void;
+    fn gdk_pixbuf_get_pixels(pixbuf: *mut c_void) -> *const u8;
+    fn gdk_pixbuf_get_rowstride(pixbuf: *mut c_void) -> i32;
+    fn gdk_pixbuf_get_n_channels(pixbuf: *mut c_void) -> i32;
+    fn g_object_unref(object: *mut c_void);
+  }
+
+  let window_ptr = window.gtk_window();
+  let window_ptr_raw = unsafe { *(window_ptr as *const _ as *const *mut c_void) };
+
+  unsafe {
+    let gdk_window = gtk_widget_get_window(window_ptr_raw);
+    if gdk_window.is_null() {
+      return Err(capture_error("Window is not realized"));
+    }
+    let width = gdk_window_get_width(gdk_window);
+    let height = gdk_window_get_height(gdk_window);
+    // Minimized or unmapped windows have no area to read.
+    if width <= 0 || height <= 0 {
+      return Err(capture_error("Cannot capture a window with an empty size"));
+    }
+    let pixbuf = gdk_pixbuf_get_from_window(gdk_window, 0, 0, width, height);
+    if pixbuf.is_null() {
+      return Err(capture_error(
+        "Window capture is not supported by this display server (Wayland does not allow reading window contents)",
+      ));
+    }
+
+    let rowstride = gdk_pixbuf_get_rowstride(pixbuf) as usize;
+    let channels = gdk_pixbuf_get_n_channels(pixbuf) as usize;
+    let (width, height) = (width as usize, height as usize);
+    let pixels = std::slice::from_raw_parts(
+      gdk_pixbuf_get_pixels(pixbuf),
+      rowstride * (height - 1) + width * channels,
+    );
+
+    let mut rgba = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+      let row = &pixels[y * rowstride..y * rowstride + width * channels];
+      for pixel in row.chunks_exact(channels) {
+        let alpha = if channels == 4 { pixel[3] } else { 255 };
+        rgba.extend_from_slice(&[pixel[0], pixel[1], pixel[2], alpha]);
+      }
+    }
+    g_object_unref(pixbuf);
+
+    Ok(CapturedImage {
+      width: width as u32,
+      height: height as u32,
+      rgba,
+    })
+  }
+}
+
+#[cfg(not(any(
+  target_os = "windows",
+  target_os = "macos",
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+)))]
+pub(crate) fn capture_window(_window: &tao::window::Window) -> Result<CapturedImage> {
+  Err(capture_error(
+    "Window capture is not supported on this platform",
+  ))
+}
+
+/// Result of an engine snapshot: PNG bytes or an error message.
+pub(crate) type SnapshotResult = std::result::Result<Vec<u8>, String>;
+
+/// Waits on the libuv thread pool for the PNG of a snapshot started by `snapshot_webview`.
+pub struct PageSnapshot {
+  receiver: std::sync::mpsc::Receiver<SnapshotResult>,
+  timeout: std::time::Duration,
+}
+
+impl PageSnapshot {
+  pub(crate) fn new(
+    receiver: std::sync::mpsc::Receiver<SnapshotResult>,
+    timeout: std::time::Duration,
+  ) -> Self {
+    Self { receiver, timeout }
+  }
+}
+
+#[napi]
+impl Task for PageSnapshot {
+  type Output = Vec<u8>;
+  type JsValue = Buffer;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    self
+      .receiver
+      .recv_timeout(self.timeout)
+      .map_err(|e| match e {
+        std::sync::mpsc::RecvTimeoutError::Timeout => capture_error(&format!(
+          "The webview did not return a snapshot within {:?}",
+          self.timeout
+        )),
+        std::sync::mpsc::RecvTimeoutError::Disconnected => {
+          capture_error("The webview was closed before the snapshot was taken")
+        }
+      })?
+      .map_err(|message| capture_error(&format!("Failed to capture the webview: {}", message)))
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output.into())
+  }
+}
+
+/// Starts a PNG snapshot of the visible part of the webview with WebView2's `CapturePreview`.
+#[cfg(target_os = "windows")]
+pub(crate) fn snapshot_webview(
+  webview: &wry::WebView,
+  sender: std::sync::mpsc::Sender<SnapshotResult>,
+) -> Result<()> {
+  use webview2_com::CapturePreviewCompletedHandler;
+  use webview2_com::Microsoft::Web::WebView2::Win32::COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG;
+  use windows::Win32::System::Com::{IStream, STREAM_SEEK_SET};
+  use windows::Win32::UI::Shell::SHCreateMemStream;
+  use wry::WebViewExtWindows;
+
+  fn read_stream(stream: &IStream) -> windows::core::Result<Vec<u8>> {
+    let mut png = Vec::new();
+    let mut buffer = [0u8; 64 * 1024];
+    unsafe {
+      stream.Seek(0, STREAM_SEEK_SET, None)?;
+      loop {
+        let mut read = 0;
+        stream
+          .Read(
+            buffer.as_mut_ptr() as *mut _,
+            buffer.len() as u32,
+            Some(&mut read),
+          )
+          .ok()?;
+        if read == 0 {
+          break;
+        }
+        png.extend_from_slice(&buffer[..read as usize]);
+      }
+    }
+    Ok(png)
+  }
+
+  let stream = unsafe { SHCreateMemStream(None) }
+    .ok_or_else(|| capture_error("Failed to allocate the snapshot buffer"))?;
+  let output = stream.clone();
+  unsafe {
+    webview.webview().CapturePreview(
+      COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG,
+      &stream,
+      &CapturePreviewCompletedHandler::create(Box::new(move |result| {
+        let png = result.and_then(|()| read_stream(&output));
+        let _ = sender.send(png.map_err(|e| e.to_string()));
+        Ok(())
+      })),
+    )
+  }
+  .map_err(|e| capture_error(&format!("Failed to capture the webview: {}", e)))
+}
+
+/// Starts a snapshot of the visible part of the webview with WKWebView's
+/// `takeSnapshotWithConfiguration:completionHandler:`.
+#[cfg(target_os = "macos")]
+pub(crate) fn snapshot_webview(
+  webview: &wry::WebView,
+  sender: std::sync::mpsc::Sender<SnapshotResult>,
+) -> Result<()> {
+  use objc2::rc::Retained;
+  use objc2::runtime::AnyObject;
+  use objc2::{class, msg_send};
+  use objc2_foundation::{NSData, NSDictionary, NSString};
+  use wry::WebViewExtMacOS;
+
+  const BITMAP_IMAGE_FILE_TYPE_PNG: usize = 4;
+
+  /// Encodes an `NSImage` to PNG through `NSBitmapImageRep`.
+  unsafe fn png_from_image(image: &AnyObject) -> Option<Vec<u8>> {
+    let tiff: Option<Retained<NSData>> = msg_send![image, TIFFRepresentation];
+    let bitmap: Option<Retained<AnyObject>> =
+      msg_send![class!(NSBitmapImageRep), imageRepWithData: &*tiff?];
+    let properties = NSDictionary::<AnyObject, AnyObject>::new();
+    let png: Option<Retained<NSData>> = msg_send![
+      &*bitmap?,
+      representationUsingType: BITMAP_IMAGE_FILE_TYPE_PNG,
+      properties: &*properties
+    ];
+    Some(png?.to_vec())
+  }
+
+  let handler = block2::RcBlock::new(move |image: *mut AnyObject, error: *mut AnyObject| {
+    let result = match unsafe { image.as_ref() } {
+      Some(image) => unsafe { png_from_image(image) }
+        .ok_or_else(|| "The snapshot could not be encoded as PNG".to_string()),
+      None => Err(match unsafe { error.as_ref() } {
+        Some(error) => {
+          let description: Retained<NSString> = unsafe { msg_send![error, localizedDescription] };
+          description.to_string()
+        }
+        None => "WKWebView returned no image".to_string(),
+      }),
+    };
+    let _ = sender.send(result);
+  });
+  let view = webview.webview();
+  // A nil configuration captures the visible bounds of the view.
+  let configuration: *const AnyObject = std::ptr::null();
+  unsafe {
+    let _: () = msg_send![
+      &*view,
+      takeSnapshotWithConfiguration: configuration,
+      completionHandler: &*handler
+    ];
+  }
+  Ok(())
+}
+
+/// Starts a PNG snapshot of the visible part of the webview with `webkit_web_view_get_snapshot`.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+pub(crate) fn snapshot_webview(
+  webview: &wry::WebView,
+  sender: std::sync::mpsc::Sender<SnapshotResult>,
+) -> Result<()> {
+  use std::ffi::{c_char, c_void, CStr};
+  use wry::WebViewExtUnix;
+
+  #[repr(C)]
+  struct GError {
+    domain: u32,
+    code: i32,
+    message: *const c_char,
+  }
+
+  extern "C" {
+    fn webkit_web_view_get_snapshot(
+      web_view: *mut c_void,
+      region: i32,
+      options: i32,
+      cancellable: *mut c_void,
+      callback: unsafe extern "C" fn(*mut c_void, *mut c_void, *mut c_void),
+      user_data: *mut c_void,
+    );
+    fn webkit_web_view_get_snapshot_finish(
+      web_view: *mut c_void,
+      result: *mut c_void,
+      error: *mut *mut GError,
+    ) -> *mut c_void;
+    fn cairo_surface_write_to_png_stream(
+      surface: *mut c_void,
+      write: unsafe extern "C" fn(*mut c_void, *const u8, u32) -> i32,
+      closure: *mut c_void,
+    ) -> i32;
+    fn cairo_surface_destroy(surface: *mut c_void);
+    fn g_error_free(error: *mut c_void);
+  }
+
+  const WEBKIT_SNAPSHOT_REGION_VISIBLE: i32 = 0;
+  const WEBKIT_SNAPSHOT_OPTIONS_NONE: i32 = 0;
+  const CAIRO_STATUS_SUCCESS: i32 = 0;
+
+  unsafe extern "C" fn write_png(closure: *mut c_void, data: *const u8, length: u32) -> i32 {
+    let png = &mut *(closure as *mut Vec<u8>);
+    png.extend_from_slice(std::slice::from_raw_parts(data, length as usize));
+    CAIRO_STATUS_SUCCESS
+  }
+
+  unsafe extern "C" fn on_snapshot(web_view: *mut c_void, result: *mut c_void, data: *mut c_void) {
+    let sender = Box::from_raw(data as *mut std::sync::mpsc::Sender<SnapshotResult>);
+    let mut error = std::ptr::null_mut();
+    let surface = webkit_web_view_get_snapshot_finish(web_view, result, &mut error);
+    let result = if surface.is_null() {
+      let message = if error.is_null() {
+        "WebKitGTK returned no image".to_string()
+      } else {
+        let message = CStr::from_ptr((*error).message)
+          .to_string_lossy()
+          .into_owned();
+        g_error_free(error as *mut c_void);
+        message
+      };
+      Err(message)
+    } else {
+      let mut png = Vec::new();
+      let status = cairo_surface_write_to_png_stream(
+        surface,
+        write_png,
+        &mut png as *mut Vec<u8> as *mut c_void,
+      );
+      cairo_surface_destroy(surface);
+      if status == CAIRO_STATUS_SUCCESS {
+        Ok(png)
+      } else {
+        Err("The snapshot could not be encoded as PNG".to_string())
+      }
+    };
+    let _ = sender.send(result);
+  }
+
+  let view = webview.webview();
+  let view_raw = unsafe { *(&view as *const _ as *const *mut c_void) };
+  unsafe {
+    webkit_web_view_get_snapshot(
+      view_raw,
+      WEBKIT_SNAPSHOT_REGION_VISIBLE,
+      WEBKIT_SNAPSHOT_OPTIONS_NONE,
+      std::ptr::null_mut(),
+      on_snapshot,
+      Box::into_raw(Box::new(sender)) as *mut c_void,
+    );
+  }
+  Ok(())
+}
+
+#[cfg(not(any(
+  target_os = "windows",
+  target_os = "macos",
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+)))]
+pub(crate) fn snapshot_webview(
+  _webview: &wry::WebView,
+  _sender: std::sync::mpsc::Sender<SnapshotResult>,
+) -> Result<()> {
+  Err(capture_error(
+    "Webview capture is not supported on this platform",
+  ))
+}
@@ -1,6 +1,7 @@
 use napi::bindgen_prelude::*;
 use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
 use napi_derive::napi;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 #[napi]
@@ -15,6 +16,7 @@ pub(crate) enum PendingWebviewAction {
   CloseDevtools,
   Reload,
   Print,
+  SetBounds(crate::wry::structs::Rect),
 }
 
 #[allow(unused_imports)]
@@ -33,25 +35,161 @@ use tao::platform::unix::WindowBuilderExtUnix;
 #[cfg(target_os = "windows")]
 use tao::platform::windows::WindowBuilderExtWindows;
 
+/// Information shown in the standard about panel. Only used on macOS.
+#[napi(object)]
+pub struct AboutPanel {
+  pub application_name: Option<String>,
+  pub version: Option<String>,
+  pub credits: Option<String>,
+  pub copyright: Option<String>,
+  /// Encoded image (e.g. PNG) used as the panel icon.
+  pub icon: Option<Buffer>,
+}
+
+#[derive(Default)]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+struct AboutPanelInfo {
+  application_name: Option<String>,
+  version: Option<String>,
+  credits: Option<String>,
+  copyright: Option<String>,
+  icon: Option<Vec<u8>>,
+}
+
 #[napi]
 pub enum WebviewApplicationEvent {
   WindowCloseRequested,
   ApplicationCloseRequested,
+  ThemeChanged,
+  LoadTimeout,
+  /// Another native macOS tab of a tab group was selected. Not emitted when the window of the
+  /// selected tab merely regains focus.
+  TabSelected,
+  /// An animation started with `BrowserWindow.animateOpacity` reached its target opacity.
+  OpacityAnimationFinished,
+  /// The system is about to sleep. There is little time left to act; on Linux this requires
+  /// systemd-logind on the system D-Bus.
+  SystemSuspend,
+  /// The system woke up from sleep. On Linux this requires systemd-logind on the system D-Bus.
+  SystemResume,
+  /// A window gained keyboard focus.
+  WindowFocused,
+  /// A window lost keyboard focus.
+  WindowBlurred,
+  /// A window was resized; `size` holds its new inner size.
+  WindowResized,
+  /// A window was moved; `position` holds its new outer position.
+  WindowMoved,
 }
 
 #[napi(object)]
 pub struct ApplicationEvent {
   pub event: WebviewApplicationEvent,
+  /// The new window theme, set for `ThemeChanged` events.
+  pub theme: Option<Theme>,
+  /// The URL of the page that did not finish loading, set for `LoadTimeout` events.
+  pub url: Option<String>,
+  /// The `BrowserWindow.id` of the window the event belongs to, set for all window events and
+  /// `LoadTimeout`.
+  pub window_id: Option<String>,
+  /// The new inner size in logical pixels, set for `WindowResized` events.
+  pub size: Option<Dimensions>,
+  /// The new outer position in logical pixels, set for `WindowMoved` events.
+  pub position: Option<Position>,
+}
+
+impl ApplicationEvent {
+  /// An event without details.
+  fn new(event: WebviewApplicationEvent) -> Self {
+    Self {
+      event,
+      theme: None,
+      url: None,
+      window_id: None,
+      size: None,
+      position: None,
+    }
+  }
+
+  /// An event belonging to the window `window_id`.
+  fn window(event: WebviewApplicationEvent, window_id: tao::window::WindowId) -> Self {
+    Self {
+      window_id: Some(window_id_string(window_id)),
+      ..Self::new(event)
+    }
+  }
 }
 
 #[napi(object)]
 pub struct ApplicationOptions {
+  /// Loop mode of `Application.run`. Unset, the loop sleeps until the next event.
   pub control_flow: Option<ControlFlow>,
+  /// Interval in milliseconds between wake-ups with `ControlFlow.WaitUntil`. Defaults to 100.
   pub wait_time: Option<u32>,
+  /// Exit code used with `ControlFlow.ExitWithCode`.
   pub exit_code: Option<i32>,
+  /// Identifier naming the application's data directory, e.g. `com.example.editor`. Required by
+  /// `Application.profile`, as the executable is `node` or `bun` for every script.
+  pub app_id: Option<String>,
+}
+
+/// Translucent window backdrop. Mapped to `DWMWA_SYSTEMBACKDROP_TYPE` on Windows and to an
+/// `NSVisualEffectView` material on macOS.
+#[napi]
+#[derive(Clone, Copy)]
+pub enum BackdropEffect {
+  None,
+  Blur,
+  Acrylic,
+  Mica,
+  Tabbed,
+}
+
+/// HTTP cache policy of a webview.
+///
+/// `NoCache` shrinks the WebView2 disk cache to a single byte and uses the WebKitGTK document
+/// viewer cache model, which applies to the whole web context. WKWebView has no per-webview cache
+/// policy, and no backend can be restricted to cached responses, so those combinations fail with
+/// an unsupported error.
+#[napi]
+#[derive(Clone, Copy, PartialEq)]
+pub enum CacheMode {
+  Default,
+  NoCache,
+  OnlyFromCache,
+}
+
+/// Oldest TLS version a webview accepts for its connections.
+///
+/// Every supported engine already refuses TLS 1.0 and 1.1, so `Tls12` is always satisfied.
+/// `Tls13` is only enforced by WebView2; WebKitGTK and WKWebView expose no TLS settings, so it
+/// fails with an unsupported error there.
+#[napi]
+#[derive(Clone, Copy, PartialEq)]
+pub enum TlsVersion {
+  Tls12,
+  Tls13,
+}
+
+/// How `Webview.scrollTo` and `Webview.scrollBy` animate, mirroring `ScrollOptions.behavior`.
+#[napi]
+#[derive(Clone, Copy)]
+pub enum ScrollBehavior {
+  Auto,
+  Smooth,
+  Instant,
+}
+
+/// Value of `history.scrollRestoration` for every page loaded in a webview.
+#[napi]
+#[derive(Clone, Copy)]
+pub enum ScrollRestoration {
+  Auto,
+  Manual,
 }
 
 #[napi]
+#[derive(Clone, Copy)]
 pub enum ControlFlow {
   Poll = 0,
   WaitUntil = 1,
@@ -72,6 +210,103 @@ pub enum FullscreenType {
 }
 
 #[napi(object)]
+pub struct DownloadProgress {
+  pub url: String,
+  pub received_bytes: f64,
+  /// Total size of the download, when known.
+  pub total_bytes: Option<f64>,
+}
+
+#[napi(object)]
+pub struct WebviewRuntimeStatus {
+  pub available: bool,
+  pub version: Option<String>,
+  /// Where to download the runtime from, set when it is missing.
+  pub install_url: Option<String>,
+}
+
+/// A capability requested by web content, passed to `Webview.onPermissionRequest`.
+#[napi(object)]
+pub struct PermissionRequest {
+  /// Origin of the requesting page, e.g. `https://example.com`.
+  pub origin: String,
+  pub kind: crate::wry::enums::PermissionKind,
+}
+
+#[napi(object)]
+pub struct DragDropEventData {
+  pub kind: crate::wry::enums::DragDropEvent,
+  /// Paths of the dragged files. Only set for `Entered` and `Dropped`.
+  pub paths: Vec<String>,
+  /// Cursor position relative to the webview in physical pixels. Not set for `Left`.
+  pub position: Option<Position>,
+}
+
+#[napi(object)]
+pub struct PageLoadEventData {
+  pub event: crate::wry::enums::PageLoadEvent,
+  pub url: String,
+}
+
+/// Space reserved for an overlay title bar, in logical pixels.
+#[napi(object)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct TitlebarOverlay {
+  pub height: f64,
+  pub button_inset: f64,
+}
+
+/// Window chrome drawn by the app instead of the system. The window is created without
+/// decorations; the page draws its own title bar and the window stays movable and resizable.
+///
+/// Elements with a `data-webview-drag-region` attribute move the window when dragged and toggle
+/// maximize when double-clicked. Only the element carrying the attribute reacts, so buttons
+/// inside a drag region keep working.
+#[napi(object)]
+#[derive(Clone, Copy)]
+pub struct CustomDecorations {
+  /// Width of the invisible resize border along the window edges, in CSS pixels. Defaults to 8;
+  /// 0 disables resizing from the edges.
+  pub resize_border: Option<f64>,
+  /// Keeps the native drop shadow. Defaults to `true`. Supported on Windows and macOS; on Linux
+  /// the window manager decides.
+  pub shadow: Option<bool>,
+  /// Rounds the window corners with the system radius. Defaults to `true`. Only supported on
+  /// Windows 11; ignored elsewhere.
+  pub rounded_corners: Option<bool>,
+}
+
+#[napi(object)]
+pub struct Modifiers {
+  pub shift: bool,
+  pub ctrl: bool,
+  pub alt: bool,
+  pub meta: bool,
+}
+
+#[napi(object)]
+pub struct MessageDelivery {
+  pub label: String,
+  pub delivered: bool,
+  pub error: Option<String>,
+}
+
+/// An HTTP cookie stored by a webview's profile.
+#[napi(object)]
+pub struct CookieData {
+  pub name: String,
+  pub value: String,
+  /// Domain the cookie is sent to. Required by WebKitGTK when setting a cookie.
+  pub domain: Option<String>,
+  pub path: Option<String>,
+  pub secure: Option<bool>,
+  pub http_only: Option<bool>,
+  /// Expiry as seconds since the Unix epoch. Unset for session cookies.
+  pub expires: Option<f64>,
+}
+
+#[napi(object)]
+#[derive(Clone)]
 pub struct HeaderData {
   pub key: String,
   pub value: Option<String>,
@@ -83,6 +318,24 @@ pub struct IpcMessage {
   pub method: String,
   pub headers: Vec<HeaderData>,
   pub uri: String,
+  /// `BrowserWindow.id` of the window hosting the sending webview.
+  pub window_id: Option<String>,
+  /// Label of the sending webview.
+  pub label: Option<String>,
+  /// Set when the page sent the message with `window.__webview_invoke__`, which waits for an
+  /// answer. Pass it to `Webview.reply` to resolve the page's promise.
+  pub request_id: Option<String>,
+}
+
+/// Response returned by the handler set with `Webview.onCustomProtocol`.
+#[napi(object)]
+pub struct CustomProtocolResponse {
+  /// HTTP status code. Defaults to 200.
+  pub status: Option<u16>,
+  /// Response headers. Without a `Content-Type` header, the type is derived from the file
+  /// extension of the URL.
+  pub headers: Option<Vec<HeaderData>>,
+  pub body: Option<Buffer>,
 }
 
 #[napi]
@@ -103,6 +356,7 @@ pub struct ProgressBarState {
 }
 
 #[napi]
+#[derive(Clone, Copy)]
 pub enum Theme {
   Light = 0,
   Dark = 1,
@@ -125,6 +379,30 @@ pub struct Monitor {
   pub video_modes: Vec<VideoMode>,
 }
 
+/// Native handle of a window, for drawing into it with another library such as wgpu.
+#[napi(object)]
+pub struct WindowHandle {
+  /// `win32`, `appkit`, `xlib`, `xcb` or `wayland`.
+  pub platform: String,
+  /// The `HWND`, `NSView*`, X11 window id or `wl_surface*`.
+  pub handle: BigInt,
+  /// The X11 `Display*` or `wl_display*` the window belongs to. Not set on Windows and macOS.
+  pub display: Option<BigInt>,
+}
+
+/// Window placement returned by `BrowserWindow.getGeometry`, in logical pixels.
+#[napi(object)]
+pub struct WindowGeometry {
+  /// Outer position of the window.
+  pub x: f64,
+  pub y: f64,
+  /// Inner size of the window.
+  pub width: f64,
+  pub height: f64,
+  pub maximized: bool,
+  pub fullscreen: bool,
+}
+
 #[napi(object)]
 pub struct BrowserWindowOptions {
   pub resizable: Option<bool>,
@@ -145,6 +423,23 @@ pub struct BrowserWindowOptions {
   pub focused: Option<bool>,
   pub transparent: Option<bool>,
   pub fullscreen: Option<FullscreenType>,
+  /// Windows sharing a tabbing identifier are merged into native tabs. Only used on macOS.
+  pub tabbing_identifier: Option<String>,
+  /// Minimum client area width the user can resize the window to, in logical pixels.
+  pub min_width: Option<f64>,
+  /// Minimum client area height, in logical pixels.
+  pub min_height: Option<f64>,
+  /// Maximum client area width the user can resize the window to, in logical pixels.
+  pub max_width: Option<f64>,
+  /// Maximum client area height, in logical pixels.
+  pub max_height: Option<f64>,
+  /// Replaces the system title bar and frame with chrome drawn by the app. Overrides
+  /// `decorations`.
+  pub custom_decorations: Option<CustomDecorations>,
+  /// Key under which the window geometry is kept in the store set with
+  /// `Application.setStateStore`. Saved geometry overrides `x`, `y`, `width`, `height` and
+  /// `maximized`.
+  pub state_key: Option<String>,
 }
 
 #[napi(object)]
@@ -166,54 +461,512 @@ pub struct WebviewOptions {
   pub clipboard: Option<bool>,
   pub autoplay: Option<bool>,
   pub back_forward_navigation_gestures: Option<bool>,
+  /// URL patterns to block. `*` matches any sequence of characters and `?` matches a single
+  /// character, e.g. `https://*.doubleclick.net/*`. Navigations are blocked natively on every
+  /// platform; `fetch` and `XMLHttpRequest` requests are blocked by an injected script.
+  pub blocked_url_patterns: Option<Vec<String>>,
+  /// RGBA color painted over the webview until the first page has loaded, hiding the blank
+  /// flash on startup. It is also used as the native background color. The alpha channel is
+  /// honored, so transparent webviews stay see-through when a transparent color is given.
+  pub paint_placeholder_color: Option<Buffer>,
+  /// Native background color shown before the page paints and behind transparent page content,
+  /// as 3 or 4 RGBA bytes. Takes precedence over `paintPlaceholderColor` as the native
+  /// background. An alpha below 255 only shows through with `transparent` enabled; WebView2
+  /// only supports fully opaque or fully transparent colors.
+  pub background_color: Option<Buffer>,
+  /// Label identifying the webview within its window. Must be unique per window; defaults to
+  /// `webview-<n>`.
+  pub label: Option<String>,
+  /// `Accept-Language` sent to servers, e.g. `de-DE,de;q=0.9,en;q=0.8`. Applied natively on
+  /// Windows and Linux; on macOS it is only sent with the initial page request.
+  pub accept_language: Option<String>,
+  /// Headers sent with the initial page request, e.g. an `Authorization` token for a dashboard.
+  /// Navigations started later by the page or with `loadUrl` do not carry them.
+  pub default_headers: Option<Vec<HeaderData>>,
+  /// HTTP cache policy. Defaults to the engine's normal caching.
+  pub cache_mode: Option<CacheMode>,
+  /// Disables GPU acceleration as a compatibility escape hatch for blank or glitchy rendering in
+  /// virtual machines and remote desktops. Rendering gets slower. Passes `--disable-gpu` to
+  /// WebView2 and turns off hardware acceleration in WebKitGTK; ignored on macOS, where WKWebView
+  /// cannot be configured.
+  pub disable_gpu: Option<bool>,
+  /// Schemes served by the handler set with `Webview.onCustomProtocol`, e.g. `app`, whose pages
+  /// are loaded from `app://localhost/index.html`, or `http://app.localhost/index.html` on
+  /// Windows, where WebView2 only serves custom schemes under that form. On Linux a scheme can
+  /// only be registered once per profile.
+  pub custom_protocols: Option<Vec<String>>,
+  /// Sets `history.scrollRestoration` on every page. Use `Manual` together with
+  /// `Webview.saveScrollPosition` and `Webview.restoreScrollPosition` for deterministic
+  /// scroll positions across navigations.
+  pub scroll_restoration: Option<ScrollRestoration>,
+  /// Oldest TLS version accepted for connections. See `TlsVersion` for platform support.
+  pub min_tls_version: Option<TlsVersion>,
+  /// Proxy for the webview's traffic. On Linux it applies to the whole web context, shared with
+  /// webviews of the same profile. Not supported on macOS.
+  pub proxy: Option<crate::wry::enums::ProxyConfig>,
+  /// Directory storing cookies, local storage and the cache, so sessions survive restarts.
+  /// Webviews of the same application given the same directory share one session. The directory
+  /// is created if needed; on Windows it becomes the WebView2 user data folder. Cannot be
+  /// combined with a profile. WebView2 applies `acceptLanguage`, `cacheMode`, `disableGpu`,
+  /// `minTlsVersion` and `proxy` per user data folder, so webviews sharing a folder must use the
+  /// same values for them; creating one with different values fails.
+  pub data_directory: Option<String>,
+  /// Directory served read-only under the `asset` scheme, e.g. `asset://localhost/index.html`
+  /// (`http://asset.localhost/index.html` on Windows). Use it as the base URL of
+  /// `Webview.loadHtmlWithBaseUrl` so relative scripts, styles and images resolve to local files.
+  pub asset_root: Option<String>,
+}
+
+// `Buffer` is not `Clone`, so the placeholder color is copied by hand.
+impl Clone for WebviewOptions {
+  fn clone(&self) -> Self {
+    Self {
+      url: self.url.clone(),
+      html: self.html.clone(),
+      width: self.width,
+      height: self.height,
+      x: self.x,
+      y: self.y,
+      enable_devtools: self.enable_devtools,
+      incognito: self.incognito,
+      user_agent: self.user_agent.clone(),
+      child: self.child,
+      preload: self.preload.clone(),
+      transparent: self.transparent,
+      theme: self.theme,
+      hotkeys_zoom: self.hotkeys_zoom,
+      clipboard: self.clipboard,
+      autoplay: self.autoplay,
+      back_forward_navigation_gestures: self.back_forward_navigation_gestures,
+      blocked_url_patterns: self.blocked_url_patterns.clone(),
+      paint_placeholder_color: self
+        .paint_placeholder_color
+        .as_ref()
+        .map(|color| Buffer::from(color.to_vec())),
+      background_color: self
+        .background_color
+        .as_ref()
+        .map(|color| Buffer::from(color.to_vec())),
+      label: self.label.clone(),
+      accept_language: self.accept_language.clone(),
+      default_headers: self.default_headers.clone(),
+      cache_mode: self.cache_mode,
+      disable_gpu: self.disable_gpu,
+      custom_protocols: self.custom_protocols.clone(),
+      scroll_restoration: self.scroll_restoration,
+      min_tls_version: self.min_tls_version,
+      proxy: self.proxy.clone(),
+      data_directory: self.data_directory.clone(),
+      asset_root: self.asset_root.clone(),
+    }
+  }
 }
 
 type PendingWindow = (
   BrowserWindowOptions,
   Arc<Mutex<Option<crate::tao::structs::Window>>>,
   Arc<Mutex<Vec<PendingWebview>>>,
+  Arc<Mutex<WindowState>>,
 );
 
-type PendingWebview = (
-  WebviewOptions,
-  Arc<Mutex<Option<crate::wry::structs::WebView>>>,
-  Arc<Mutex<Vec<crate::wry::structs::IpcHandler>>>,
-  Arc<Mutex<Vec<PendingWebviewAction>>>,
-);
+/// Per-window state maintained by the event loop.
+#[derive(Default)]
+pub(crate) struct WindowState {
+  pub(crate) theme: Option<Theme>,
+  pub(crate) tabbing_identifier: Option<String>,
+  /// Icon applied when the window is built, and restored after a loading state.
+  pub(crate) icon: Option<tao::window::Icon>,
+  /// Set by `BrowserWindow.setLoadingState` until a webview of the window reports its title.
+  pub(crate) loading_state: Option<LoadingState>,
+  pub(crate) persisted: Option<PersistedGeometry>,
+  /// Menu bar set with `BrowserWindow.setMenu`, kept alive while it is shown.
+  pub(crate) menu: Option<muda::Menu>,
+  /// Menu last shown with `BrowserWindow.popupMenu`.
+  pub(crate) context_menu: Option<muda::Menu>,
+  pub(crate) titlebar_overlay: Option<TitlebarOverlay>,
+  pub(crate) custom_decorations: Option<CustomDecorations>,
+  pub(crate) transparent: bool,
+  /// Set by `BrowserWindow.destroy`; a destroyed window is never built.
+  pub(crate) destroyed: bool,
+  pub(crate) opacity_animation: Option<OpacityAnimation>,
+  pub(crate) webviews: Vec<Webview>,
+  /// Scale factor of the monitor the window is on, for reporting sizes in logical pixels.
+  pub(crate) scale_factor: f64,
+}
+
+type PendingWebview = (WebviewOptions, Webview, Option<Profile>);
+
+/// Placeholder shown by `BrowserWindow.setLoadingState`.
+#[derive(Clone, Copy)]
+pub(crate) struct LoadingState {
+  /// Whether the window icon was replaced and must be restored.
+  replaced_icon: bool,
+}
+
+/// Delay after the last move or resize before a window's geometry is saved.
+const GEOMETRY_SAVE_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Window geometry kept in the application's state store.
+pub(crate) struct PersistedGeometry {
+  key: String,
+  window: Arc<Mutex<tao::window::Window>>,
+  /// Bounds of the window while neither maximized nor minimized, in logical pixels.
+  normal_bounds: Option<(tao::dpi::LogicalPosition<f64>, tao::dpi::LogicalSize<f64>)>,
+  /// When the pending save is due, if the window moved or resized since the last save.
+  save_at: Option<std::time::Instant>,
+}
+
+/// `get` and `set` callbacks passed to `Application.setStateStore`.
+struct StateStore {
+  get: SyncJsHandler<String, Option<String>>,
+  set: SyncJsHandler<FnArgs<(String, String)>, ()>,
+}
+
+/// Commands sent into the event loop through its proxy, so work queued from JS is picked up
+/// while `run` is blocked waiting for events.
+#[derive(Clone)]
+pub(crate) enum LoopCommand {
+  /// Windows or webviews were queued and should be created.
+  CreatePending,
+  /// Nothing to do; the loop only re-reads its settings, e.g. after `setControlFlow`.
+  Wake,
+  /// `Application.exit` was called.
+  Exit,
+  /// A menu item with the given id was selected.
+  MenuItemSelected(String),
+  /// The global shortcut with the given hotkey id was pressed.
+  GlobalShortcutPressed(u32),
+}
+
+/// Event loops of the live applications, by id.
+static EVENT_ROUTES: Mutex<Vec<(u64, tao::event_loop::EventLoopProxy<LoopCommand>)>> =
+  Mutex::new(Vec::new());
+static NEXT_EVENT_ROUTE_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+static INSTALL_EVENT_HANDLERS: std::sync::Once = std::sync::Once::new();
+
+/// Keeps an application registered with `route_events`; dropping it unregisters the application.
+struct EventRoute {
+  id: u64,
+}
+
+impl Drop for EventRoute {
+  fn drop(&mut self) {
+    EVENT_ROUTES
+      .lock()
+      .unwrap()
+      .retain(|(id, _)| *id != self.id);
+  }
+}
+
+/// Forwards menu and global shortcut events to `proxy` until the returned guard is dropped.
+/// muda and global-hotkey accept a single event handler per process, so the handlers are
+/// installed once, on the first call, and send every event to all applications. Each application
+/// then ignores the menu items and shortcuts it does not own.
+fn route_events(proxy: tao::event_loop::EventLoopProxy<LoopCommand>) -> EventRoute {
+  INSTALL_EVENT_HANDLERS.call_once(|| {
+    muda::MenuEvent::set_event_handler(Some(|event: muda::MenuEvent| {
+      forward_event(LoopCommand::MenuItemSelected(event.id.0));
+    }));
+    global_hotkey::GlobalHotKeyEvent::set_event_handler(Some(
+      |event: global_hotkey::GlobalHotKeyEvent| {
+        if event.state == global_hotkey::HotKeyState::Pressed {
+          forward_event(LoopCommand::GlobalShortcutPressed(event.id));
+        }
+      },
+    ));
+  });
+  let id = NEXT_EVENT_ROUTE_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+  EVENT_ROUTES.lock().unwrap().push((id, proxy));
+  EventRoute { id }
+}
+
+/// Sends `command` to the event loop of every registered application.
+fn forward_event(command: LoopCommand) {
+  for (_, proxy) in EVENT_ROUTES.lock().unwrap().iter() {
+    let _ = proxy.send_event(command.clone());
+  }
+}
+
+/// Global shortcuts registered with `Application.registerGlobalShortcut`, keyed by hotkey id.
+struct GlobalShortcuts {
+  manager: global_hotkey::GlobalHotKeyManager,
+  handlers: HashMap<u32, ThreadsafeFunction<()>>,
+}
+
+/// Wake-up interval of `ControlFlow.WaitUntil` when no wait time is given.
+const DEFAULT_WAIT_TIME: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Loop mode of `Application.run`, set from `ApplicationOptions` or `Application.setControlFlow`.
+struct LoopTiming {
+  flow: Option<ControlFlow>,
+  wait_time: std::time::Duration,
+  exit_code: i32,
+  /// Next periodic wake-up of `WaitUntil`, kept across events so the timer is not pushed back by
+  /// every event.
+  next_tick: Option<std::time::Instant>,
+}
+
+impl LoopTiming {
+  fn new(flow: Option<ControlFlow>, wait_time: Option<u32>, exit_code: Option<i32>) -> Self {
+    Self {
+      flow,
+      wait_time: wait_time
+        .map(|ms| std::time::Duration::from_millis(ms.into()))
+        .unwrap_or(DEFAULT_WAIT_TIME),
+      exit_code: exit_code.unwrap_or_default(),
+      next_tick: None,
+    }
+  }
+
+  /// Returns the control flow for the next iteration, waking no later than `wake_at`.
+  fn control_flow(&mut self, wake_at: Option<std::time::Instant>) -> tao::event_loop::ControlFlow {
+    match self.flow {
+      None => wake_at.map_or(
+        tao::event_loop::ControlFlow::Wait,
+        tao::event_loop::ControlFlow::WaitUntil,
+      ),
+      Some(ControlFlow::Poll) => tao::event_loop::ControlFlow::Poll,
+      Some(ControlFlow::WaitUntil) => {
+        let now = std::time::Instant::now();
+        let tick = match self.next_tick {
+          Some(tick) if tick > now => tick,
+          _ => now + self.wait_time,
+        };
+        self.next_tick = Some(tick);
+        tao::event_loop::ControlFlow::WaitUntil(wake_at.map_or(tick, |at| at.min(tick)))
+      }
+      Some(ControlFlow::Exit) => tao::event_loop::ControlFlow::Exit,
+      Some(ControlFlow::ExitWithCode) => tao::event_loop::ControlFlow::ExitWithCode(self.exit_code),
+    }
+  }
+}
+
+/// Interval between opacity animation frames.
+const ANIMATION_FRAME: std::time::Duration = std::time::Duration::from_millis(16);
+
+/// Opacity fade driven by the event loop.
+pub(crate) struct OpacityAnimation {
+  window: Arc<Mutex<tao::window::Window>>,
+  from: f64,
+  to: f64,
+  start: std::time::Instant,
+  duration: std::time::Duration,
+  last_frame: Option<std::time::Instant>,
+}
 
 #[napi]
 pub struct Application {
   #[allow(clippy::arc_with_non_send_sync)]
-  event_loop: Arc<Mutex<Option<tao::event_loop::EventLoop<()>>>>,
-  event_loop_proxy: tao::event_loop::EventLoopProxy<()>,
+  event_loop: Arc<Mutex<Option<tao::event_loop::EventLoop<LoopCommand>>>>,
+  event_loop_proxy: tao::event_loop::EventLoopProxy<LoopCommand>,
   handler: Arc<Mutex<Option<ThreadsafeFunction<ApplicationEvent>>>>,
   #[allow(clippy::arc_with_non_send_sync)]
   windows_to_create: Arc<Mutex<Vec<PendingWindow>>>,
+  windows: Arc<Mutex<HashMap<tao::window::WindowId, Arc<Mutex<WindowState>>>>>,
   exit_requested: Arc<Mutex<bool>>,
+  about_panel: Arc<Mutex<AboutPanelInfo>>,
+  profiles: Arc<Mutex<HashMap<String, Profile>>>,
+  #[allow(clippy::arc_with_non_send_sync)]
+  data_directories: DataDirectories,
+  modifiers: Arc<Mutex<tao::keyboard::ModifiersState>>,
+  #[allow(clippy::arc_with_non_send_sync)]
+  named_windows: Arc<Mutex<HashMap<String, BrowserWindow>>>,
+  /// Windows that have been built, checked for newly queued webviews.
+  #[allow(clippy::arc_with_non_send_sync)]
+  built_windows: Arc<Mutex<Vec<BrowserWindow>>>,
+  #[allow(clippy::arc_with_non_send_sync)]
+  state_store: Arc<Mutex<Option<std::rc::Rc<StateStore>>>>,
+  menu_handler: Arc<Mutex<Option<ThreadsafeFunction<crate::menu::MenuEvent>>>>,
+  #[allow(clippy::arc_with_non_send_sync)]
+  global_shortcuts: Arc<Mutex<Option<GlobalShortcuts>>>,
+  loop_timing: Arc<Mutex<LoopTiming>>,
+  /// `ApplicationOptions.appId`.
+  app_id: Option<String>,
+  /// Window of the tab last selected in each native tab group, by tabbing identifier.
+  selected_tabs: Arc<Mutex<HashMap<String, tao::window::WindowId>>>,
+  /// Keeps the power event listener forwarding to `handler` registered while the application
+  /// exists.
+  _power_watch: Arc<crate::power::PowerWatch>,
+  /// Keeps menu and global shortcut events forwarded to the event loop while the application
+  /// exists.
+  _event_route: Arc<EventRoute>,
 }
 
 #[napi]
 impl Application {
   #[napi(constructor)]
-  pub fn new(_options: Option<ApplicationOptions>) -> Self {
-    let event_loop = tao::event_loop::EventLoop::new();
+  pub fn new(options: Option<ApplicationOptions>) -> Self {
+    let mut event_loop_builder = tao::event_loop::EventLoopBuilder::with_user_event();
+    #[cfg(target_os = "windows")]
+    {
+      use tao::platform::windows::EventLoopBuilderExtWindows;
+      event_loop_builder.with_msg_hook(crate::menu::translate_accelerator);
+    }
+    let event_loop = event_loop_builder.build();
     let event_loop_proxy = event_loop.create_proxy();
+    let handler: Arc<Mutex<Option<ThreadsafeFunction<ApplicationEvent>>>> =
+      Arc::new(Mutex::new(None));
+    let power_handler = handler.clone();
+    let power_watch = crate::power::watch_power_events(Box::new(move |event| {
+      if let Some(handler) = power_handler.lock().unwrap().as_ref() {
+        let event = match event {
+          crate::power::PowerEvent::Suspend => WebviewApplicationEvent::SystemSuspend,
+          crate::power::PowerEvent::Resume => WebviewApplicationEvent::SystemResume,
+        };
+        let _ = handler.call(
+          Ok(ApplicationEvent::new(event)),
+          ThreadsafeFunctionCallMode::NonBlocking,
+        );
+      }
+    }));
+    let event_route = route_events(event_loop_proxy.clone());
     Self {
       #[allow(clippy::arc_with_non_send_sync)]
       event_loop: Arc::new(Mutex::new(Some(event_loop))),
       event_loop_proxy,
-      handler: Arc::new(Mutex::new(None)),
+      handler,
       #[allow(clippy::arc_with_non_send_sync)]
       windows_to_create: Arc::new(Mutex::new(Vec::new())),
+      #[allow(clippy::arc_with_non_send_sync)]
+      windows: Arc::new(Mutex::new(HashMap::new())),
       exit_requested: Arc::new(Mutex::new(false)),
+      about_panel: Arc::new(Mutex::new(AboutPanelInfo::default())),
+      #[allow(clippy::arc_with_non_send_sync)]
+      profiles: Arc::new(Mutex::new(HashMap::new())),
+      #[allow(clippy::arc_with_non_send_sync)]
+      data_directories: Arc::new(Mutex::new(HashMap::new())),
+      modifiers: Arc::new(Mutex::new(tao::keyboard::ModifiersState::empty())),
+      #[allow(clippy::arc_with_non_send_sync)]
+      named_windows: Arc::new(Mutex::new(HashMap::new())),
+      #[allow(clippy::arc_with_non_send_sync)]
+      built_windows: Arc::new(Mutex::new(Vec::new())),
+      #[allow(clippy::arc_with_non_send_sync)]
+      state_store: Arc::new(Mutex::new(None)),
+      menu_handler: Arc::new(Mutex::new(None)),
+      #[allow(clippy::arc_with_non_send_sync)]
+      global_shortcuts: Arc::new(Mutex::new(None)),
+      app_id: options.as_ref().and_then(|options| options.app_id.clone()),
+      loop_timing: Arc::new(Mutex::new(match options {
+        Some(options) => {
+          LoopTiming::new(options.control_flow, options.wait_time, options.exit_code)
+        }
+        None => LoopTiming::new(None, None, None),
+      })),
+      selected_tabs: Arc::new(Mutex::new(HashMap::new())),
+      _power_watch: Arc::new(power_watch),
+      _event_route: Arc::new(event_route),
     }
   }
 
+  /// Changes the loop mode of `run` while it is running, e.g. to `WaitUntil` with a long wait
+  /// time to save power while idle. `waitTimeMs` defaults to 100.
+  #[napi]
+  pub fn set_control_flow(&self, flow: ControlFlow, wait_time_ms: Option<u32>) {
+    let mut timing = self.loop_timing.lock().unwrap();
+    let exit_code = timing.exit_code;
+    *timing = LoopTiming::new(Some(flow), wait_time_ms, Some(exit_code));
+    drop(timing);
+    // Wakes the loop so a waiting loop picks up the new mode.
+    let _ = self.event_loop_proxy.send_event(LoopCommand::Wake);
+  }
+
   #[napi]
   pub fn on_event(&self, handler: Option<ThreadsafeFunction<ApplicationEvent>>) {
     *self.handler.lock().unwrap() = handler;
   }
 
+  /// Sets the handler called with the id of a menu item when it is selected in any menu of
+  /// this application.
+  #[napi]
+  pub fn on_menu_event(&self, handler: Option<ThreadsafeFunction<crate::menu::MenuEvent>>) {
+    *self.menu_handler.lock().unwrap() = handler;
+  }
+
+  /// Registers a system-wide shortcut such as `Ctrl+Shift+K` or `CmdOrCtrl+Alt+Space` that calls
+  /// `handler` even while another application has focus. Fails if the accelerator cannot be
+  /// parsed, is already registered by this application or is taken by another application.
+  #[napi]
+  pub fn register_global_shortcut(
+    &self,
+    accelerator: String,
+    handler: ThreadsafeFunction<()>,
+  ) -> Result<()> {
+    let hotkey = parse_hotkey(&accelerator)?;
+    let mut shortcuts = self.global_shortcuts.lock().unwrap();
+    if shortcuts.is_none() {
+      let manager = global_hotkey::GlobalHotKeyManager::new().map_err(|e| {
+        napi::Error::new(
+          napi::Status::GenericFailure,
+          format!("Global shortcuts are unavailable: {}", e),
+        )
+      })?;
+      *shortcuts = Some(GlobalShortcuts {
+        manager,
+        handlers: HashMap::new(),
+      });
+    }
+    let shortcuts = shortcuts.as_mut().unwrap();
+    if shortcuts.handlers.contains_key(&hotkey.id()) {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        format!("{} is already registered", accelerator),
+      ));
+    }
+    shortcuts.manager.register(hotkey).map_err(|e| {
+      napi::Error::new(
+        napi::Status::GenericFailure,
+        format!("Failed to register {}: {}", accelerator, e),
+      )
+    })?;
+    shortcuts.handlers.insert(hotkey.id(), handler);
+    Ok(())
+  }
+
+  /// Unregisters a shortcut registered with `registerGlobalShortcut`. Does nothing if it is not
+  /// registered.
+  #[napi]
+  pub fn unregister_global_shortcut(&self, accelerator: String) -> Result<()> {
+    let hotkey = parse_hotkey(&accelerator)?;
+    if let Some(shortcuts) = self.global_shortcuts.lock().unwrap().as_mut() {
+      if shortcuts.handlers.remove(&hotkey.id()).is_some() {
+        let _ = shortcuts.manager.unregister(hotkey);
+      }
+    }
+    Ok(())
+  }
+
+  fn emit_global_shortcut(&self, id: u32) {
+    if let Some(shortcuts) = self.global_shortcuts.lock().unwrap().as_ref() {
+      if let Some(handler) = shortcuts.handlers.get(&id) {
+        let _ = handler.call(Ok(()), ThreadsafeFunctionCallMode::NonBlocking);
+      }
+    }
+  }
+
+  fn emit_menu_event(&self, id: String) {
+    if !self.owns_menu_item(&id) {
+      return;
+    }
+    if let Some(handler) = self.menu_handler.lock().unwrap().as_ref() {
+      let _ = handler.call(
+        Ok(crate::menu::MenuEvent { id }),
+        ThreadsafeFunctionCallMode::NonBlocking,
+      );
+    }
+  }
+
+  /// Whether the menu item `id` belongs to a menu bar or context menu of one of the windows.
+  fn owns_menu_item(&self, id: &str) -> bool {
+    self.windows.lock().unwrap().values().any(|state| {
+      let state = state.lock().unwrap();
+      state
+        .menu
+        .iter()
+        .chain(state.context_menu.iter())
+        .any(|menu| crate::menu::contains_item(&menu.items(), id))
+    })
+  }
+
   #[napi]
   pub fn bind(&self, handler: Option<ThreadsafeFunction<ApplicationEvent>>) {
     self.on_event(handler);
@@ -221,10 +974,6 @@ impl Application {
 
   #[napi]
   pub fn create_browser_window(&self, options: Option<BrowserWindowOptions>) -> BrowserWindow {
-    #[allow(clippy::arc_with_non_send_sync)]
-    let inner = Arc::new(Mutex::new(None));
-    #[allow(clippy::arc_with_non_send_sync)]
-    let webviews_to_create = Arc::new(Mutex::new(Vec::new()));
     let options = options.unwrap_or(BrowserWindowOptions {
       resizable: Some(true),
       title: Some("Webview".to_string()),
@@ -244,154 +993,608 @@ impl Application {
       focused: None,
       transparent: None,
       fullscreen: None,
+      tabbing_identifier: None,
+      min_width: None,
+      min_height: None,
+      max_width: None,
+      max_height: None,
+      custom_decorations: None,
+      state_key: None,
     });
+    self.queue_browser_window(options, WindowState::default())
+  }
+
+  /// Creates a browser window from a `WindowAttributes` snapshot.
+  ///
+  /// The icon must be square RGBA pixel data. It is applied on Windows and Linux only.
+  /// The theme is honored on all platforms. The `menubar` flag is currently ignored.
+  #[napi]
+  pub fn create_browser_window_from_attributes(
+    &self,
+    attrs: crate::tao::structs::WindowAttributes,
+  ) -> Result<BrowserWindow> {
+    if attrs.width == 0 || attrs.height == 0 {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "Window width and height must be greater than zero".to_string(),
+      ));
+    }
+
+    let icon = match attrs.icon {
+      Some(rgba) => Some(square_rgba_icon(&rgba)?),
+      None => None,
+    };
+
+    let options = BrowserWindowOptions {
+      resizable: Some(attrs.resizable),
+      title: Some(attrs.title),
+      width: Some(attrs.width as f64),
+      height: Some(attrs.height as f64),
+      x: attrs.x,
+      y: attrs.y,
+      content_protection: None,
+      always_on_top: Some(attrs.always_on_top),
+      always_on_bottom: None,
+      visible: Some(attrs.visible),
+      decorations: Some(attrs.decorated),
+      visible_on_all_workspaces: None,
+      maximized: Some(attrs.maximized),
+      maximizable: None,
+      minimizable: None,
+      focused: Some(attrs.focused),
+      transparent: Some(attrs.transparent),
+      fullscreen: None,
+      tabbing_identifier: None,
+      min_width: None,
+      min_height: None,
+      max_width: None,
+      max_height: None,
+      custom_decorations: None,
+      state_key: None,
+    };
+    let state = WindowState {
+      theme: attrs.theme.map(|theme| match theme {
+        crate::tao::enums::TaoTheme::Light => Theme::Light,
+        crate::tao::enums::TaoTheme::Dark => Theme::Dark,
+      }),
+      icon,
+      ..Default::default()
+    };
+    Ok(self.queue_browser_window(options, state))
+  }
+
+  /// Sets the key-value store that windows created with `stateKey` load their geometry from and
+  /// save it to. `get(key)` returns the stored string or nothing; `set(key, value)` stores it.
+  /// Both are called synchronously. Values are JSON objects of the form
+  /// `{"x": 10, "y": 20, "width": 800, "height": 600, "maximized": false}`, with the position and
+  /// size of the unmaximized window in logical pixels. Geometry is saved 500 ms after the window
+  /// stops moving or resizing, and when it is asked to close.
+  #[napi]
+  pub fn set_state_store(
+    &self,
+    env: Env,
+    get: Function<String, Option<String>>,
+    set: Function<FnArgs<(String, String)>, ()>,
+  ) -> Result<()> {
+    let store = StateStore {
+      get: SyncJsHandler::new(&env, get)?,
+      set: SyncJsHandler::new(&env, set)?,
+    };
+    *self.state_store.lock().unwrap() = Some(std::rc::Rc::new(store));
+    Ok(())
+  }
+
+  /// Overrides the geometry in `options` with the geometry saved under its state key, if any,
+  /// moved onto a connected monitor when it is out of reach.
+  fn load_geometry(
+    &self,
+    options: &mut BrowserWindowOptions,
+    target: &tao::event_loop::EventLoopWindowTarget<LoopCommand>,
+  ) {
+    let Some(key) = options.state_key.clone() else {
+      return;
+    };
+    let Some(store) = self.state_store.lock().unwrap().clone() else {
+      return;
+    };
+    let Some(saved) = store
+      .get
+      .call(key)
+      .flatten()
+      .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+    else {
+      return;
+    };
+    let number = |name: &str| saved.get(name).and_then(|value| value.as_f64());
+    if let (Some(width), Some(height)) = (number("width"), number("height")) {
+      if width > 0.0 && height > 0.0 {
+        options.width = Some(width);
+        options.height = Some(height);
+      }
+    }
+    if let Some(maximized) = saved.get("maximized").and_then(|value| value.as_bool()) {
+      options.maximized = Some(maximized);
+    }
+    let (Some(x), Some(y)) = (number("x"), number("y")) else {
+      return;
+    };
+    // The monitor the window was saved on may have been unplugged or rearranged since.
+    let monitors: Vec<_> = target
+      .available_monitors()
+      .map(|monitor| MonitorBounds::of(&monitor))
+      .collect();
+    let primary = target
+      .primary_monitor()
+      .map(|monitor| MonitorBounds::of(&monitor));
+    let geometry = WindowGeometry {
+      x,
+      y,
+      width: options.width.unwrap_or(800.0),
+      height: options.height.unwrap_or(600.0),
+      maximized: false,
+      fullscreen: false,
+    };
+    let scale_factor = scale_factor_at(&geometry, &monitors, primary);
+    let (position, size) =
+      fit_to_monitors(physical_bounds(&geometry, scale_factor), &monitors, primary);
+    // Back to logical pixels of the monitor the window ends up on, which tao converts them with.
+    let scale_factor = monitors
+      .iter()
+      .find(|monitor| monitor.contains(position))
+      .map_or(scale_factor, |monitor| monitor.scale_factor);
+    let position = position.to_logical::<f64>(scale_factor);
+    let size = size.to_logical::<f64>(scale_factor);
+    options.x = Some(position.x);
+    options.y = Some(position.y);
+    options.width = Some(size.width);
+    options.height = Some(size.height);
+  }
+
+  /// Saves the geometry of windows whose debounce delay has passed, or of `force` right away.
+  /// Returns when the next pending save is due.
+  fn save_geometry(&self, force: Option<tao::window::WindowId>) -> Option<std::time::Instant> {
+    let now = std::time::Instant::now();
+    let store = self.state_store.lock().unwrap().clone();
+    let windows: Vec<_> = self
+      .windows
+      .lock()
+      .unwrap()
+      .iter()
+      .map(|(id, state)| (*id, state.clone()))
+      .collect();
+    let mut next_save: Option<std::time::Instant> = None;
+    for (window_id, state) in windows {
+      let mut state = state.lock().unwrap();
+      let Some(persisted) = state.persisted.as_mut() else {
+        continue;
+      };
+      let due = match persisted.save_at {
+        _ if force == Some(window_id) => true,
+        Some(at) if at <= now => true,
+        Some(at) => {
+          next_save = Some(next_save.map_or(at, |next| next.min(at)));
+          false
+        }
+        None => false,
+      };
+      if !due {
+        continue;
+      }
+      persisted.save_at = None;
+      let window = persisted.window.lock().unwrap();
+      if window.is_minimized() {
+        continue;
+      }
+      let maximized = window.is_maximized();
+      if !maximized {
+        persisted.normal_bounds = Some(logical_bounds(&window));
+      }
+      drop(window);
+      let (Some(store), Some((position, size))) = (store.as_ref(), persisted.normal_bounds) else {
+        continue;
+      };
+      let value = serde_json::json!({
+        "x": position.x,
+        "y": position.y,
+        "width": size.width,
+        "height": size.height,
+        "maximized": maximized,
+      });
+      let key = persisted.key.clone();
+      drop(state);
+      store.set.call(FnArgs::from((key, value.to_string())));
+    }
+    next_save
+  }
+
+  fn queue_browser_window(
+    &self,
+    options: BrowserWindowOptions,
+    state: WindowState,
+  ) -> BrowserWindow {
+    #[allow(clippy::arc_with_non_send_sync)]
+    let inner = Arc::new(Mutex::new(None));
+    #[allow(clippy::arc_with_non_send_sync)]
+    let webviews_to_create = Arc::new(Mutex::new(Vec::new()));
+    #[allow(clippy::arc_with_non_send_sync)]
+    let state = Arc::new(Mutex::new(state));
 
     self.windows_to_create.lock().unwrap().push((
       options,
       inner.clone(),
       webviews_to_create.clone(),
+      state.clone(),
     ));
+    let _ = self.event_loop_proxy.send_event(LoopCommand::CreatePending);
 
     BrowserWindow {
       inner,
       webviews_to_create,
+      state,
+      data_directories: self.data_directories.clone(),
+      event_loop_proxy: self.event_loop_proxy.clone(),
     }
   }
 
+  /// Returns the modifier keys held down as of the last keyboard event received by any window
+  /// of this application.
   #[napi]
-  pub fn exit(&self) {
-    *self.exit_requested.lock().unwrap() = true;
-    let _ = self.event_loop_proxy.send_event(());
+  pub fn current_modifiers(&self) -> Modifiers {
+    let modifiers = *self.modifiers.lock().unwrap();
+    Modifiers {
+      shift: modifiers.shift_key(),
+      ctrl: modifiers.control_key(),
+      alt: modifiers.alt_key(),
+      meta: modifiers.super_key(),
+    }
   }
 
-  fn process_pending_items(&self, event_loop_target: &tao::event_loop::EventLoopWindowTarget<()>) {
-    let mut pending = self.windows_to_create.lock().unwrap();
-    for (opts, win_handle, webviews_to_create) in pending.drain(..) {
-      let mut builder = tao::window::WindowBuilder::new()
-        .with_title(opts.title.clone().unwrap_or_default())
-        .with_inner_size(tao::dpi::LogicalSize::new(
-          opts.width.unwrap_or(800.0),
-          opts.height.unwrap_or(600.0),
-        ))
-        .with_resizable(opts.resizable.unwrap_or(true))
-        .with_decorations(opts.decorations.unwrap_or(true))
-        .with_always_on_top(opts.always_on_top.unwrap_or(false))
-        .with_maximized(opts.maximized.unwrap_or(false))
-        .with_focused(opts.focused.unwrap_or(true))
-        .with_transparent(opts.transparent.unwrap_or(false))
-        .with_visible(opts.visible.unwrap_or(true));
+  /// Returns the persistent profile with the given name, creating its directory if needed.
+  ///
+  /// Webviews created with the same profile share cookies and storage. Profiles are stored in
+  /// `<data dir>/<appId>/profiles/<name>`, where the data directory is `$XDG_DATA_HOME` (or
+  /// `~/.local/share`) on Linux, `~/Library/Application Support` on macOS and `%LOCALAPPDATA%`
+  /// on Windows. Fails unless the application was created with `ApplicationOptions.appId`.
+  #[napi]
+  pub fn profile(&self, name: String) -> Result<Profile> {
+    let app_id = self.app_id.clone().ok_or_else(|| {
+      napi::Error::new(
+        napi::Status::InvalidArg,
+        "Profiles need an application created with `appId` in its options".to_string(),
+      )
+    })?;
+    check_path_component("application id", &app_id)?;
+    check_path_component("profile name", &name)?;
 
-      if opts.transparent.unwrap_or(false) {
-        #[cfg(target_os = "windows")]
-        {
-          builder = builder.with_undecorated_shadow(false);
-        }
-        #[cfg(target_os = "macos")]
-        {
-          builder = builder
-            .with_titlebar_transparent(true)
-            .with_fullsize_content_view(true);
-        }
-        #[cfg(any(
-          target_os = "linux",
-          target_os = "dragonfly",
-          target_os = "freebsd",
-          target_os = "netbsd",
-          target_os = "openbsd"
-        ))]
-        {
-          builder = builder.with_rgba_visual(true);
-        }
-      }
+    let mut profiles = self.profiles.lock().unwrap();
+    if let Some(profile) = profiles.get(&name) {
+      return Ok(profile.clone());
+    }
 
-      if let Some(x) = opts.x {
-        if let Some(y) = opts.y {
-          builder = builder.with_position(tao::dpi::LogicalPosition::new(x, y));
-        }
-      }
+    let path = dirs::data_local_dir()
+      .ok_or_else(|| {
+        napi::Error::new(
+          napi::Status::GenericFailure,
+          "Could not determine the application data directory".to_string(),
+        )
+      })?
+      .join(app_id)
+      .join("profiles")
+      .join(&name);
+    std::fs::create_dir_all(&path).map_err(|e| {
+      napi::Error::new(
+        napi::Status::GenericFailure,
+        format!("Failed to create profile directory: {}", e),
+      )
+    })?;
 
-      if let Ok(window) = builder.build(event_loop_target) {
-        let mut handle = win_handle.lock().unwrap();
-        *handle = Some(crate::tao::structs::Window {
-          #[allow(clippy::arc_with_non_send_sync)]
-          inner: Some(Arc::new(Mutex::new(window))),
-        });
+    let profile = Profile {
+      name: name.clone(),
+      context: crate::wry::structs::WebContext::new(Some(path.to_string_lossy().to_string()))?,
+      path,
+    };
+    profiles.insert(name, profile.clone());
+    Ok(profile)
+  }
+
+  /// Sets the information shown in the standard about panel. Ignored on platforms other than macOS.
+  #[napi]
+  pub fn set_about_panel(&self, info: AboutPanel) {
+    *self.about_panel.lock().unwrap() = AboutPanelInfo {
+      application_name: info.application_name,
+      version: info.version,
+      credits: info.credits,
+      copyright: info.copyright,
+      icon: info.icon.map(|icon| icon.to_vec()),
+    };
+  }
+
+  /// Opens the standard about panel. Does nothing on platforms other than macOS.
+  #[napi]
+  pub fn show_about_panel(&self) {
+    #[cfg(target_os = "macos")]
+    show_macos_about_panel(&self.about_panel.lock().unwrap());
+  }
+
+  /// Creates a window addressed by `name`, e.g. `main` or `settings`, for apps with one window
+  /// per purpose. Fails if a window with that name is still open.
+  #[napi]
+  pub fn create_named_window(
+    &self,
+    name: String,
+    options: Option<BrowserWindowOptions>,
+  ) -> Result<BrowserWindow> {
+    if self.get_window(name.clone()).is_some() {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        format!("A window named {:?} already exists", name),
+      ));
+    }
+    let window = self.create_browser_window(options);
+    self
+      .named_windows
+      .lock()
+      .unwrap()
+      .insert(name, window.clone());
+    Ok(window)
+  }
+
+  /// The window created with `createNamedWindow` under `name`, unless it has been closed.
+  #[napi]
+  pub fn get_window(&self, name: String) -> Option<BrowserWindow> {
+    let mut named_windows = self.named_windows.lock().unwrap();
+    named_windows.retain(|_, window| !window.state.lock().unwrap().destroyed);
+    named_windows.get(&name).cloned()
+  }
+
+  /// Whether a window created with `createNamedWindow` under `name` is open.
+  #[napi]
+  pub fn has_window(&self, name: String) -> bool {
+    self.get_window(name).is_some()
+  }
+
+  /// Destroys the window named `name` and frees the name. Returns whether such a window was
+  /// open.
+  #[napi]
+  pub fn close_named_window(&self, name: String) -> bool {
+    let window = self.get_window(name.clone());
+    self.named_windows.lock().unwrap().remove(&name);
+    match window {
+      Some(window) => {
+        window.destroy();
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// The `BrowserWindow.id` of every window that has been created and not yet closed or
+  /// destroyed. Windows that are still queued are not included.
+  #[napi]
+  pub fn get_open_window_ids(&self) -> Vec<String> {
+    self
+      .windows
+      .lock()
+      .unwrap()
+      .iter()
+      .filter(|(_, state)| !state.lock().unwrap().destroyed)
+      .map(|(id, _)| window_id_string(*id))
+      .collect()
+  }
+
+  /// The number of windows listed by `getOpenWindowIds`.
+  #[napi]
+  pub fn window_count(&self) -> u32 {
+    self.get_open_window_ids().len() as u32
+  }
+
+  /// Lists the connected monitors, with sizes and positions in physical pixels. Unlike
+  /// `BrowserWindow.getAvailableMonitors` this needs no window, so it can be used to place the
+  /// first one. Fails while `run` is driving the event loop.
+  #[napi]
+  pub fn available_monitors(&self) -> Result<Vec<Monitor>> {
+    self.with_event_loop(|event_loop| {
+      event_loop
+        .available_monitors()
+        .map(|monitor| monitor_from_tao(&monitor))
+        .collect()
+    })
+  }
+
+  /// The primary monitor, if the platform can tell which one it is. Fails while `run` is driving
+  /// the event loop.
+  #[napi]
+  pub fn primary_monitor(&self) -> Result<Option<Monitor>> {
+    self.with_event_loop(|event_loop| {
+      event_loop
+        .primary_monitor()
+        .map(|monitor| monitor_from_tao(&monitor))
+    })
+  }
+
+  /// Runs `f` with the event loop while it is not being run.
+  fn with_event_loop<T>(
+    &self,
+    f: impl FnOnce(&tao::event_loop::EventLoop<LoopCommand>) -> T,
+  ) -> Result<T> {
+    let event_loop = self.event_loop.try_lock().ok();
+    match event_loop
+      .as_ref()
+      .and_then(|event_loop| event_loop.as_ref())
+    {
+      Some(event_loop) => Ok(f(event_loop)),
+      None => Err(napi::Error::new(
+        napi::Status::GenericFailure,
+        "The event loop is running".to_string(),
+      )),
+    }
+  }
+
+  #[napi]
+  pub fn exit(&self) {
+    *self.exit_requested.lock().unwrap() = true;
+    let _ = self.event_loop_proxy.send_event(LoopCommand::Exit);
+  }
+
+  fn process_pending_items(
+    &self,
+    event_loop_target: &tao::event_loop::EventLoopWindowTarget<LoopCommand>,
+  ) {
+    let mut pending = self.windows_to_create.lock().unwrap();
+    for (mut opts, win_handle, webviews_to_create, state) in pending.drain(..) {
+      if state.lock().unwrap().destroyed {
+        continue;
+      }
+      self.load_geometry(&mut opts, event_loop_target);
+      let mut builder = tao::window::WindowBuilder::new()
+        .with_title(opts.title.clone().unwrap_or_default())
+        .with_inner_size(tao::dpi::LogicalSize::new(
+          opts.width.unwrap_or(800.0),
+          opts.height.unwrap_or(600.0),
+        ))
+        .with_resizable(opts.resizable.unwrap_or(true))
+        .with_decorations(opts.decorations.unwrap_or(true))
+        .with_always_on_top(opts.always_on_top.unwrap_or(false))
+        .with_visible_on_all_workspaces(opts.visible_on_all_workspaces.unwrap_or(false))
+        .with_maximized(opts.maximized.unwrap_or(false))
+        .with_focused(opts.focused.unwrap_or(true))
+        .with_transparent(opts.transparent.unwrap_or(false))
+        .with_visible(opts.visible.unwrap_or(true));
+
+      if opts.transparent.unwrap_or(false) {
+        #[cfg(target_os = "windows")]
+        {
+          builder = builder.with_undecorated_shadow(false);
+        }
+        #[cfg(target_os = "macos")]
+        {
+          builder = builder
+            .with_titlebar_transparent(true)
+            .with_fullsize_content_view(true);
+        }
+        #[cfg(any(
+          target_os = "linux",
+          target_os = "dragonfly",
+          target_os = "freebsd",
+          target_os = "netbsd",
+          target_os = "openbsd"
+        ))]
+        {
+          builder = builder.with_rgba_visual(true);
+        }
+      }
+
+      if let Some(x) = opts.x {
+        if let Some(y) = opts.y {
+          builder = builder.with_position(tao::dpi::LogicalPosition::new(x, y));
+        }
+      }
+      if let Some(size) = size_limit(opts.min_width, opts.min_height, 0.0) {
+        builder = builder.with_min_inner_size(size);
+      }
+      if let Some(size) = size_limit(opts.max_width, opts.max_height, MAX_WINDOW_DIMENSION) {
+        builder = builder.with_max_inner_size(size);
+      }
+
+      #[cfg(target_os = "macos")]
+      if let Some(identifier) = &opts.tabbing_identifier {
+        builder = builder.with_tabbing_identifier(identifier);
+      }
+
+      if let Some(decorations) = opts.custom_decorations {
+        builder = builder.with_decorations(false);
+        #[cfg_attr(
+          not(any(target_os = "windows", target_os = "macos")),
+          allow(unused_variables)
+        )]
+        let shadow = decorations.shadow.unwrap_or(true);
+        #[cfg(target_os = "windows")]
+        {
+          builder = builder.with_undecorated_shadow(shadow);
+        }
+        #[cfg(target_os = "macos")]
+        {
+          builder = builder.with_has_shadow(shadow);
+        }
+      }
+
+      {
+        let mut state = state.lock().unwrap();
+        state.tabbing_identifier = opts.tabbing_identifier.clone();
+        state.custom_decorations = opts.custom_decorations;
+        state.transparent = opts.transparent.unwrap_or(false);
+        if let Some(theme) = state.theme {
+          builder = builder.with_theme(match theme {
+            Theme::Light => Some(tao::window::Theme::Light),
+            Theme::Dark => Some(tao::window::Theme::Dark),
+            Theme::System => None,
+          });
+        }
+        if let Some(icon) = state.icon.clone() {
+          builder = builder.with_window_icon(Some(icon));
+        }
+      }
 
-        // Create pending webviews for this window
-        let mut pending_webviews = webviews_to_create.lock().unwrap();
-        for (webview_opts, webview_handle, ipc_listeners, pending_actions) in
-          pending_webviews.drain(..)
+      if let Ok(window) = builder.build(event_loop_target) {
         {
-          if let Ok(mut builder) = crate::wry::structs::WebViewBuilder::new() {
-            if let Some(url) = webview_opts.url {
-              let _ = builder.with_url(url);
-            }
-            if let Some(html) = webview_opts.html {
-              let _ = builder.with_html(html);
-            }
-            if let Some(width) = webview_opts.width {
-              let _ = builder.with_width(width as u32);
-            }
-            if let Some(height) = webview_opts.height {
-              let _ = builder.with_height(height as u32);
-            }
-            if let Some(x) = webview_opts.x {
-              let _ = builder.with_x(x as i32);
-            }
-            if let Some(y) = webview_opts.y {
-              let _ = builder.with_y(y as i32);
-            }
-            if let Some(user_agent) = webview_opts.user_agent {
-              let _ = builder.with_user_agent(user_agent);
-            }
-            if let Some(transparent) = webview_opts.transparent {
-              let _ = builder.with_transparent(transparent);
-            }
-            if let Some(devtools) = webview_opts.enable_devtools {
-              let _ = builder.with_devtools(devtools);
-            }
-            if let Some(incognito) = webview_opts.incognito {
-              let _ = builder.with_incognito(incognito);
-            }
-            if let Some(hotkeys_zoom) = webview_opts.hotkeys_zoom {
-              let _ = builder.with_hotkeys_zoom(hotkeys_zoom);
-            }
-            if let Some(clipboard) = webview_opts.clipboard {
-              let _ = builder.with_clipboard(clipboard);
-            }
-            if let Some(autoplay) = webview_opts.autoplay {
-              let _ = builder.with_autoplay(autoplay);
-            }
-            if let Some(back_forward_navigation_gestures) =
-              webview_opts.back_forward_navigation_gestures
-            {
-              let _ =
-                builder.with_back_forward_navigation_gestures(back_forward_navigation_gestures);
-            }
-            // Apply preload script as initialization script
-            if let Some(preload) = webview_opts.preload {
-              let init_script = crate::wry::structs::InitializationScript {
-                js: preload,
-                once: false,
-              };
-              let _ = builder.with_initialization_script(init_script);
-            }
-            // Build the webview - pass the ipc_listeners Arc directly to setup_ipc_handler
-            if let Ok(webview) = builder.build_on_window(
-              handle.as_ref().unwrap(),
-              "webview".to_string(),
-              Some(ipc_listeners.clone()),
-            ) {
-              let mut wv_handle = webview_handle.lock().unwrap();
-              *wv_handle = Some(webview);
-
-              // Apply any pending actions that were called before the webview was initialized
-              apply_pending_actions(wv_handle.as_ref().unwrap(), &pending_actions);
-            }
-          }
+          let mut state = state.lock().unwrap();
+          state.theme = Some(theme_from_tao(window.theme()));
+          state.scale_factor = window.scale_factor();
+        }
+        if let Some(kind) = opts.fullscreen {
+          window.set_fullscreen(Some(tao_fullscreen(&window, kind)));
+        }
+        #[cfg(target_os = "macos")]
+        if let Some(overlay) = state.lock().unwrap().titlebar_overlay {
+          apply_titlebar_overlay(&window, overlay);
         }
+        #[cfg(target_os = "windows")]
+        if let Some(decorations) = opts.custom_decorations {
+          set_rounded_corners(&window, decorations.rounded_corners.unwrap_or(true));
+        }
+        self
+          .windows
+          .lock()
+          .unwrap()
+          .insert(window.id(), state.clone());
+
+        #[allow(clippy::arc_with_non_send_sync)]
+        let window = Arc::new(Mutex::new(window));
+        if let Some(key) = opts.state_key.clone() {
+          let normal_bounds = {
+            let window = window.lock().unwrap();
+            (!window.is_maximized()).then(|| logical_bounds(&window))
+          };
+          state.lock().unwrap().persisted = Some(PersistedGeometry {
+            key,
+            window: window.clone(),
+            normal_bounds,
+            save_at: None,
+          });
+        }
+        *win_handle.lock().unwrap() = Some(crate::tao::structs::Window {
+          inner: Some(window),
+          cursor_icon: Mutex::new(crate::tao::enums::CursorIcon::Default),
+        });
+        self.built_windows.lock().unwrap().push(BrowserWindow {
+          inner: win_handle,
+          webviews_to_create,
+          state,
+          data_directories: self.data_directories.clone(),
+          event_loop_proxy: self.event_loop_proxy.clone(),
+        });
       }
     }
+    drop(pending);
+
+    let windows = {
+      let mut built_windows = self.built_windows.lock().unwrap();
+      built_windows.retain(|window| !window.state.lock().unwrap().destroyed);
+      built_windows.clone()
+    };
+    for window in windows {
+      window.build_pending_webviews(&self.handler);
+    }
   }
 
   #[napi]
@@ -412,22 +1615,43 @@ impl Application {
         }
 
         app_ref.process_pending_items(event_loop_target);
+        let next_frame = app_ref.step_animations();
+        let next_save = app_ref.save_geometry(None);
+        let wake_at = next_frame.into_iter().chain(next_save).min();
+        *control_flow = app_ref.loop_timing.lock().unwrap().control_flow(wake_at);
+
+        match event {
+          tao::event::Event::UserEvent(LoopCommand::MenuItemSelected(id)) => {
+            app_ref.emit_menu_event(id);
+            return;
+          }
+          tao::event::Event::UserEvent(LoopCommand::GlobalShortcutPressed(id)) => {
+            app_ref.emit_global_shortcut(id);
+            return;
+          }
+          _ => {}
+        }
 
         if let tao::event::Event::WindowEvent {
-          event: tao::event::WindowEvent::CloseRequested,
-          ..
+          window_id, event, ..
         } = event
         {
-          let mut h = handler_clone.lock().unwrap();
-          if let Some(handler) = h.as_mut() {
-            let _ = handler.call(
-              Ok(ApplicationEvent {
-                event: WebviewApplicationEvent::WindowCloseRequested,
-              }),
-              ThreadsafeFunctionCallMode::NonBlocking,
-            );
+          if let tao::event::WindowEvent::CloseRequested = event {
+            app_ref.save_geometry(Some(window_id));
+            let mut h = handler_clone.lock().unwrap();
+            if let Some(handler) = h.as_mut() {
+              let _ = handler.call(
+                Ok(ApplicationEvent::window(
+                  WebviewApplicationEvent::WindowCloseRequested,
+                  window_id,
+                )),
+                ThreadsafeFunctionCallMode::NonBlocking,
+              );
+            }
+            *control_flow = tao::event_loop::ControlFlow::Exit;
+          } else {
+            app_ref.handle_window_event(window_id, &event);
           }
-          *control_flow = tao::event_loop::ControlFlow::Exit;
         }
       });
     }
@@ -439,7 +1663,168 @@ impl Application {
       event_loop_proxy: self.event_loop_proxy.clone(),
       handler: self.handler.clone(),
       windows_to_create: self.windows_to_create.clone(),
+      windows: self.windows.clone(),
       exit_requested: self.exit_requested.clone(),
+      about_panel: self.about_panel.clone(),
+      profiles: self.profiles.clone(),
+      data_directories: self.data_directories.clone(),
+      modifiers: self.modifiers.clone(),
+      named_windows: self.named_windows.clone(),
+      built_windows: self.built_windows.clone(),
+      state_store: self.state_store.clone(),
+      menu_handler: self.menu_handler.clone(),
+      global_shortcuts: self.global_shortcuts.clone(),
+      loop_timing: self.loop_timing.clone(),
+      app_id: self.app_id.clone(),
+      selected_tabs: self.selected_tabs.clone(),
+      _power_watch: self._power_watch.clone(),
+      _event_route: self._event_route.clone(),
+    }
+  }
+
+  fn emit(&self, event: ApplicationEvent) {
+    if let Some(handler) = self.handler.lock().unwrap().as_ref() {
+      let _ = handler.call(Ok(event), ThreadsafeFunctionCallMode::NonBlocking);
+    }
+  }
+
+  /// Advances running opacity animations and returns when the next frame is due, if any are
+  /// still running.
+  fn step_animations(&self) -> Option<std::time::Instant> {
+    let now = std::time::Instant::now();
+    let mut next_frame: Option<std::time::Instant> = None;
+    let mut schedule = |at: std::time::Instant| {
+      next_frame = Some(next_frame.map_or(at, |next| next.min(at)));
+    };
+    let windows: Vec<_> = self
+      .windows
+      .lock()
+      .unwrap()
+      .iter()
+      .map(|(id, state)| (*id, state.clone()))
+      .collect();
+    for (window_id, state) in windows {
+      let mut state = state.lock().unwrap();
+      let Some(animation) = state.opacity_animation.as_mut() else {
+        continue;
+      };
+      if let Some(last) = animation
+        .last_frame
+        .filter(|last| now < *last + ANIMATION_FRAME)
+      {
+        schedule(last + ANIMATION_FRAME);
+        continue;
+      }
+      animation.last_frame = Some(now);
+      let elapsed = now.duration_since(animation.start);
+      let progress = if animation.duration.is_zero() {
+        1.0
+      } else {
+        (elapsed.as_secs_f64() / animation.duration.as_secs_f64()).min(1.0)
+      };
+      let opacity = animation.from + (animation.to - animation.from) * progress;
+      apply_window_opacity(&animation.window.lock().unwrap(), opacity);
+      if progress < 1.0 {
+        schedule(now + ANIMATION_FRAME);
+        continue;
+      }
+      state.opacity_animation = None;
+      drop(state);
+      self.emit(ApplicationEvent::window(
+        WebviewApplicationEvent::OpacityAnimationFinished,
+        window_id,
+      ));
+    }
+    next_frame
+  }
+
+  /// Updates per-window state and forwards window events other than close requests.
+  fn handle_window_event(&self, window_id: tao::window::WindowId, event: &tao::event::WindowEvent) {
+    if let tao::event::WindowEvent::ModifiersChanged(modifiers) = event {
+      *self.modifiers.lock().unwrap() = *modifiers;
+    }
+
+    let Some(state) = self.windows.lock().unwrap().get(&window_id).cloned() else {
+      return;
+    };
+
+    if let tao::event::WindowEvent::Focused(focused) = event {
+      let kind = if *focused {
+        WebviewApplicationEvent::WindowFocused
+      } else {
+        WebviewApplicationEvent::WindowBlurred
+      };
+      self.emit(ApplicationEvent::window(kind, window_id));
+    }
+
+    let scale_factor = state.lock().unwrap().scale_factor;
+    match event {
+      tao::event::WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+        state.lock().unwrap().scale_factor = *scale_factor;
+      }
+      tao::event::WindowEvent::ThemeChanged(theme) => {
+        let theme = theme_from_tao(*theme);
+        state.lock().unwrap().theme = Some(theme);
+        self.emit(ApplicationEvent {
+          theme: Some(theme),
+          ..ApplicationEvent::window(WebviewApplicationEvent::ThemeChanged, window_id)
+        });
+      }
+      // Selecting a native tab makes its window the key window, as does focusing the tab group.
+      tao::event::WindowEvent::Focused(true)
+        if cfg!(target_os = "macos") && state.lock().unwrap().tabbing_identifier.is_some() =>
+      {
+        let identifier = state.lock().unwrap().tabbing_identifier.clone();
+        let previous = identifier.and_then(|identifier| {
+          self
+            .selected_tabs
+            .lock()
+            .unwrap()
+            .insert(identifier, window_id)
+        });
+        if previous.is_some_and(|previous| previous != window_id) && key_window_is_tabbed() {
+          self.emit(ApplicationEvent::window(
+            WebviewApplicationEvent::TabSelected,
+            window_id,
+          ));
+        }
+      }
+      tao::event::WindowEvent::Resized(size) => {
+        {
+          let mut state = state.lock().unwrap();
+          for webview in state.webviews.iter() {
+            webview.apply_relative_bounds();
+          }
+          if let Some(persisted) = state.persisted.as_mut() {
+            persisted.save_at = Some(std::time::Instant::now() + GEOMETRY_SAVE_DELAY);
+          }
+        }
+        let size = size.to_logical::<f64>(scale_factor);
+        self.emit(ApplicationEvent {
+          size: Some(Dimensions {
+            width: size.width,
+            height: size.height,
+          }),
+          ..ApplicationEvent::window(WebviewApplicationEvent::WindowResized, window_id)
+        });
+      }
+      tao::event::WindowEvent::Moved(position) => {
+        if let Some(persisted) = state.lock().unwrap().persisted.as_mut() {
+          persisted.save_at = Some(std::time::Instant::now() + GEOMETRY_SAVE_DELAY);
+        }
+        let position = position.to_logical::<f64>(scale_factor);
+        self.emit(ApplicationEvent {
+          position: Some(Position {
+            x: position.x,
+            y: position.y,
+          }),
+          ..ApplicationEvent::window(WebviewApplicationEvent::WindowMoved, window_id)
+        });
+      }
+      tao::event::WindowEvent::Destroyed => {
+        self.windows.lock().unwrap().remove(&window_id);
+      }
+      _ => {}
     }
   }
 
@@ -464,24 +1849,40 @@ impl Application {
         *control_flow = tao::event_loop::ControlFlow::Poll;
 
         app_ref.process_pending_items(event_loop_target);
+        app_ref.step_animations();
+        app_ref.save_geometry(None);
 
         match event {
           tao::event::Event::WindowEvent {
+            window_id,
             event: tao::event::WindowEvent::CloseRequested,
             ..
           } => {
+            app_ref.save_geometry(Some(window_id));
             let mut h = handler_clone.lock().unwrap();
             if let Some(handler) = h.as_mut() {
               let _ = handler.call(
-                Ok(ApplicationEvent {
-                  event: WebviewApplicationEvent::WindowCloseRequested,
-                }),
+                Ok(ApplicationEvent::window(
+                  WebviewApplicationEvent::WindowCloseRequested,
+                  window_id,
+                )),
                 ThreadsafeFunctionCallMode::NonBlocking,
               );
             }
             keep_running = false;
             *control_flow = tao::event_loop::ControlFlow::Exit;
           }
+          tao::event::Event::WindowEvent {
+            window_id, event, ..
+          } => {
+            app_ref.handle_window_event(window_id, &event);
+          }
+          tao::event::Event::UserEvent(LoopCommand::MenuItemSelected(id)) => {
+            app_ref.emit_menu_event(id);
+          }
+          tao::event::Event::UserEvent(LoopCommand::GlobalShortcutPressed(id)) => {
+            app_ref.emit_global_shortcut(id);
+          }
           tao::event::Event::RedrawEventsCleared => {
             *control_flow = tao::event_loop::ControlFlow::Exit;
           }
@@ -494,24 +1895,135 @@ impl Application {
 }
 
 #[napi]
+#[derive(Clone)]
 pub struct BrowserWindow {
   pub(crate) inner: Arc<Mutex<Option<crate::tao::structs::Window>>>,
   pub(crate) webviews_to_create: Arc<Mutex<Vec<PendingWebview>>>,
+  pub(crate) state: Arc<Mutex<WindowState>>,
+  pub(crate) data_directories: DataDirectories,
+  pub(crate) event_loop_proxy: tao::event_loop::EventLoopProxy<LoopCommand>,
 }
 
 #[napi]
 impl BrowserWindow {
+  /// Identifier of the window, as reported in `ApplicationEvent.windowId`. `uninitialized`
+  /// until the window is built.
   #[napi(getter)]
   pub fn id(&self) -> String {
-    if let Some(win) = self.inner.lock().unwrap().as_ref() {
-      format!("{:?}", win.id())
-    } else {
-      "uninitialized".to_string()
+    self
+      .inner
+      .lock()
+      .unwrap()
+      .as_ref()
+      .and_then(built_window_id)
+      .unwrap_or_else(|| "uninitialized".to_string())
+  }
+
+  /// The raw tao `WindowId` of this window as a `u64`, matching the low-level `Window.id`, for
+  /// correlating with events from other tao integrations. Fails before the window is created.
+  #[napi]
+  pub fn native_window_id(&self) -> Result<u64> {
+    match self.inner.lock().unwrap().as_ref() {
+      Some(win) => win.id(),
+      None => Err(napi::Error::new(
+        napi::Status::GenericFailure,
+        "Window not initialized".to_string(),
+      )),
     }
   }
 
+  /// Returns the platform handle of the window, for compositing content drawn by another
+  /// library next to the webviews. The handle belongs to the event loop thread: only use it there
+  /// and only while the window exists. Fails before the window is created.
+  #[napi]
+  pub fn raw_window_handle(&self) -> Result<WindowHandle> {
+    use tao::rwh_06::{HasDisplayHandle, HasWindowHandle, RawDisplayHandle, RawWindowHandle};
+
+    let handle_error = |e: tao::rwh_06::HandleError| {
+      napi::Error::new(
+        napi::Status::GenericFailure,
+        format!("Failed to get the window handle: {}", e),
+      )
+    };
+    let (raw_window, raw_display) = self.with_tao_window(|window| {
+      Ok((
+        window.window_handle().map_err(handle_error)?.as_raw(),
+        window.display_handle().map_err(handle_error)?.as_raw(),
+      ))
+    })?;
+    let display = match raw_display {
+      RawDisplayHandle::Xlib(display) => display.display.map(|ptr| ptr.as_ptr() as u64),
+      RawDisplayHandle::Xcb(display) => display.connection.map(|ptr| ptr.as_ptr() as u64),
+      RawDisplayHandle::Wayland(display) => Some(display.display.as_ptr() as u64),
+      _ => None,
+    };
+    let (platform, handle) = match raw_window {
+      RawWindowHandle::Win32(handle) => ("win32", handle.hwnd.get() as u64),
+      RawWindowHandle::AppKit(handle) => ("appkit", handle.ns_view.as_ptr() as u64),
+      // `c_ulong` is only 32 bits wide on some targets.
+      #[allow(clippy::unnecessary_cast)]
+      RawWindowHandle::Xlib(handle) => ("xlib", handle.window as u64),
+      RawWindowHandle::Xcb(handle) => ("xcb", handle.window.get() as u64),
+      RawWindowHandle::Wayland(handle) => ("wayland", handle.surface.as_ptr() as u64),
+      _ => return Err(crate::wry::enums::Error::Unsupported.to_js_error()),
+    };
+    Ok(WindowHandle {
+      platform: platform.to_string(),
+      handle: BigInt::from(handle),
+      display: display.map(BigInt::from),
+    })
+  }
+
+  /// Returns the position, size, maximized and fullscreen state of the window, for restoring it
+  /// later with `applyGeometry`. Fails before the window is created.
+  #[napi]
+  pub fn get_geometry(&self) -> Result<WindowGeometry> {
+    self.with_tao_window(|window| {
+      let (position, size) = logical_bounds(window);
+      Ok(WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        maximized: window.is_maximized(),
+        fullscreen: window.fullscreen().is_some(),
+      })
+    })
+  }
+
+  /// Restores a geometry returned by `getGeometry`. A window that would not be reachable on any
+  /// connected monitor, e.g. one saved on a monitor that was unplugged since, is moved and if
+  /// needed shrunk onto the primary monitor. Fails before the window is created.
+  #[napi]
+  pub fn apply_geometry(&self, geometry: WindowGeometry) -> Result<()> {
+    self.with_tao_window(|window| {
+      if geometry.width <= 0.0 || geometry.height <= 0.0 {
+        return Err(napi::Error::new(
+          napi::Status::InvalidArg,
+          "Window geometry must have a positive size".to_string(),
+        ));
+      }
+      // Bounds only apply to the normal state, so leave fullscreen and maximized first.
+      window.set_fullscreen(None);
+      window.set_maximized(false);
+      let (position, size) = clamp_to_monitors(window, &geometry);
+      window.set_inner_size(size);
+      window.set_outer_position(position);
+      if geometry.fullscreen {
+        window.set_fullscreen(Some(tao::window::Fullscreen::Borderless(None)));
+      } else if geometry.maximized {
+        window.set_maximized(true);
+      }
+      Ok(())
+    })
+  }
+
   #[napi]
-  pub fn create_webview(&self, options: Option<WebviewOptions>) -> Result<Webview> {
+  pub fn create_webview(
+    &self,
+    options: Option<WebviewOptions>,
+    profile: Option<&Profile>,
+  ) -> Result<Webview> {
     #[allow(clippy::arc_with_non_send_sync)]
     let inner = Arc::new(Mutex::new(None));
     let ipc_listeners = Arc::new(Mutex::new(Vec::new()));
@@ -534,20 +2046,140 @@ impl BrowserWindow {
       clipboard: None,
       autoplay: None,
       back_forward_navigation_gestures: None,
+      blocked_url_patterns: None,
+      paint_placeholder_color: None,
+      background_color: None,
+      label: None,
+      accept_language: None,
+      default_headers: None,
+      cache_mode: None,
+      disable_gpu: None,
+      custom_protocols: None,
+      scroll_restoration: None,
+      min_tls_version: None,
+      proxy: None,
+      data_directory: None,
+      asset_root: None,
     });
+    if let Some(mode) = options.cache_mode {
+      check_cache_mode(mode)?;
+    }
+    if let Some(version) = options.min_tls_version {
+      check_min_tls_version(version)?;
+    }
+    if let Some(proxy) = &options.proxy {
+      proxy_config(proxy)?;
+    }
+    if options
+      .background_color
+      .as_ref()
+      .is_some_and(|color| !matches!(color.len(), 3 | 4))
+    {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "backgroundColor must be 3 or 4 RGBA bytes".to_string(),
+      ));
+    }
+    for header in options.default_headers.iter().flatten() {
+      crate::wry::structs::header_pair(&header.key, header.value.as_deref().unwrap_or_default())?;
+    }
+    let data_profile = match &options.data_directory {
+      Some(_) if profile.is_some() => {
+        return Err(napi::Error::new(
+          napi::Status::InvalidArg,
+          "dataDirectory cannot be combined with a profile".to_string(),
+        ))
+      }
+      Some(dir) => Some(data_directory_profile(&self.data_directories, dir)?),
+      None => None,
+    };
 
-    self.webviews_to_create.lock().unwrap().push((
-      options,
-      inner.clone(),
-      ipc_listeners.clone(),
-      pending_actions.clone(),
-    ));
+    let mut state = self.state.lock().unwrap();
+    let label = match &options.label {
+      Some(label) => label.clone(),
+      None => (state.webviews.len()..)
+        .map(|n| format!("webview-{}", n))
+        .find(|label| state.webviews.iter().all(|webview| &webview.label != label))
+        .unwrap_or_default(),
+    };
+    if state.webviews.iter().any(|webview| webview.label == label) {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        format!(
+          "A webview labeled {:?} already exists in this window",
+          label
+        ),
+      ));
+    }
 
-    Ok(Webview {
+    let webview = Webview {
       inner,
+      label,
+      window: self.inner.clone(),
       ipc_listeners,
+      source_ipc_listeners: Arc::new(Mutex::new(Vec::new())),
       pending_actions,
-    })
+      relative_bounds: Arc::new(Mutex::new(None)),
+      fullscreen_handler: Arc::new(Mutex::new(None)),
+      download_progress_handler: Arc::new(Mutex::new(None)),
+      title_changed_handler: Arc::new(Mutex::new(None)),
+      favicon_changed_handler: Arc::new(Mutex::new(None)),
+      page_load_handler: Arc::new(Mutex::new(None)),
+      #[allow(clippy::arc_with_non_send_sync)]
+      navigation_hook: Arc::new(Mutex::new(None)),
+      #[allow(clippy::arc_with_non_send_sync)]
+      drag_drop_hook: Arc::new(Mutex::new(None)),
+      #[allow(clippy::arc_with_non_send_sync)]
+      permission_hook: Arc::new(Mutex::new(None)),
+      custom_protocol_handler: Arc::new(Mutex::new(None)),
+      history: Arc::new(Mutex::new(HistoryState::default())),
+      load_guard: Arc::new(Mutex::new(LoadGuard::default())),
+      pings: Arc::new(Mutex::new(PingState::default())),
+      #[allow(clippy::arc_with_non_send_sync)]
+      build_context: Arc::new(Mutex::new(None)),
+      zoom: Arc::new(Mutex::new(1.0)),
+      page: Arc::new(Mutex::new(PageState::default())),
+      gestures: Arc::new(Mutex::new(GestureFlags::default())),
+    };
+    self.webviews_to_create.lock().unwrap().push((
+      options,
+      webview.clone(),
+      profile.cloned().or(data_profile),
+    ));
+    state.webviews.push(webview.clone());
+    let _ = self.event_loop_proxy.send_event(LoopCommand::CreatePending);
+
+    Ok(webview)
+  }
+
+  /// Sends a message to every webview in this window, like `Webview.send`.
+  #[napi]
+  pub fn post_message_all(&self, json: String) -> Vec<MessageDelivery> {
+    let webviews = self.state.lock().unwrap().webviews.clone();
+    webviews
+      .iter()
+      .map(|webview| {
+        let result = match webview.inner.lock().unwrap().as_ref() {
+          Some(inner) => inner.send(json.clone()).map_err(|e| e.reason.clone()),
+          None => Err("Webview not initialized".to_string()),
+        };
+        MessageDelivery {
+          label: webview.label.clone(),
+          delivered: result.is_ok(),
+          error: result.err(),
+        }
+      })
+      .collect()
+  }
+
+  #[napi]
+  pub fn webview_by_label(&self, label: String) -> Option<Webview> {
+    let state = self.state.lock().unwrap();
+    state
+      .webviews
+      .iter()
+      .find(|webview| webview.label == label)
+      .cloned()
   }
 
   #[napi(getter)]
@@ -641,6 +2273,9 @@ impl BrowserWindow {
 
   #[napi(getter)]
   pub fn theme(&self) -> Theme {
+    if let Some(theme) = self.state.lock().unwrap().theme {
+      return theme;
+    }
     if let Some(win) = self.inner.lock().unwrap().as_ref() {
       match win.theme() {
         Ok(Some(crate::tao::enums::TaoTheme::Dark)) => Theme::Dark,
@@ -653,6 +2288,10 @@ impl BrowserWindow {
 
   #[napi(setter)]
   pub fn set_theme(&self, theme: Theme) {
+    self.state.lock().unwrap().theme = match theme {
+      Theme::System => None,
+      _ => Some(theme),
+    };
     if let Some(win) = self.inner.lock().unwrap().as_ref() {
       let t = match theme {
         Theme::Dark => crate::tao::enums::TaoTheme::Dark,
@@ -669,10 +2308,71 @@ impl BrowserWindow {
         Either::A(b) => b,
         Either::B(_) => return, // Skipping path-based for now
       };
+      // Kept so that the icon can be restored after a loading state.
+      self.state.lock().unwrap().icon =
+        tao::window::Icon::from_rgba(buf.to_vec(), width, height).ok();
       let _ = win.set_window_icon(width, height, buf);
     }
   }
 
+  /// Shows `menu` as the window's menu bar, with accelerators active while the window has focus.
+  /// On macOS the menu becomes the application menu shared by all windows. Selections are
+  /// reported to `Application.onMenuEvent`. Fails before the window is created.
+  #[napi]
+  pub fn set_menu(&self, menu: &crate::menu::Menu) -> Result<()> {
+    self.with_tao_window(|window| crate::menu::attach_menu(window, &menu.inner))?;
+    self.state.lock().unwrap().menu = Some(menu.inner.clone());
+    Ok(())
+  }
+
+  /// Shows `menu` as a context menu at `x`, `y` in logical pixels relative to the window, or at
+  /// the cursor when no position is given. Selections are reported to `Application.onMenuEvent`.
+  #[napi]
+  pub fn popup_menu(&self, menu: &crate::menu::Menu, x: Option<f64>, y: Option<f64>) {
+    let position = match (x, y) {
+      (Some(x), Some(y)) => Some(muda::dpi::Position::Logical(
+        muda::dpi::LogicalPosition::new(x, y),
+      )),
+      _ => None,
+    };
+    let handle = self.inner.lock().unwrap();
+    if let Some(inner) = handle.as_ref().and_then(|win| win.inner.as_ref()) {
+      self.state.lock().unwrap().context_menu = Some(menu.inner.clone());
+      crate::menu::show_context_menu(&inner.lock().unwrap(), &menu.inner, position);
+    }
+  }
+
+  /// Shows a placeholder title, and optionally an icon, while a page loads, so the window does not
+  /// keep showing the previous page's title. The icon must be square RGBA pixel data and is
+  /// applied on Windows and Linux only. The next title reported by one of the window's webviews
+  /// (see `Webview.onTitleChanged`) replaces the placeholder title, and the window icon set
+  /// before is restored.
+  #[napi]
+  pub fn set_loading_state(&self, title: String, icon: Option<Buffer>) -> Result<()> {
+    let icon = match icon {
+      Some(rgba) => Some(square_rgba_icon(&rgba)?),
+      None => None,
+    };
+    let handle = self.inner.lock().unwrap();
+    let inner = handle
+      .as_ref()
+      .and_then(|win| win.inner.as_ref())
+      .ok_or_else(|| {
+        napi::Error::new(
+          napi::Status::GenericFailure,
+          "Window not initialized".to_string(),
+        )
+      })?;
+    let window = inner.lock().unwrap();
+    window.set_title(&title);
+    let replaced_icon = icon.is_some();
+    if replaced_icon {
+      window.set_window_icon(icon);
+    }
+    self.state.lock().unwrap().loading_state = Some(LoadingState { replaced_icon });
+    Ok(())
+  }
+
   #[napi]
   pub fn remove_window_icon(&self) {}
 
@@ -686,6 +2386,140 @@ impl BrowserWindow {
   #[napi]
   pub fn set_progress_bar(&self, _state: ProgressBarState) {}
 
+  /// Sets the window backdrop effect. The window must be created with `transparent: true`.
+  ///
+  /// Requires Windows 11 22H2 or macOS 10.14. Returns an error on other platforms and when the
+  /// system rejects the effect.
+  #[napi]
+  pub fn set_backdrop(&self, effect: BackdropEffect) -> Result<()> {
+    let handle = self.inner.lock().unwrap();
+    let Some(inner) = handle.as_ref().and_then(|win| win.inner.as_ref()) else {
+      return Err(napi::Error::new(
+        napi::Status::GenericFailure,
+        "Window not initialized".to_string(),
+      ));
+    };
+    let window = inner.lock().unwrap();
+    apply_backdrop(&window, effect)
+  }
+
+  /// Sets the opacity of the whole window, including its frame, from 0 (invisible) to 1 (opaque).
+  /// Cancels a running `animateOpacity` animation. On Linux the effect requires a compositing
+  /// window manager.
+  #[napi]
+  pub fn set_window_opacity(&self, opacity: f64) -> Result<()> {
+    if !opacity.is_finite() {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "Opacity must be a finite number".to_string(),
+      ));
+    }
+    let handle = self.inner.lock().unwrap();
+    let Some(inner) = handle.as_ref().and_then(|win| win.inner.as_ref()) else {
+      return Err(napi::Error::new(
+        napi::Status::GenericFailure,
+        "Window not initialized".to_string(),
+      ));
+    };
+    self.state.lock().unwrap().opacity_animation = None;
+    apply_window_opacity(&inner.lock().unwrap(), opacity.clamp(0.0, 1.0));
+    Ok(())
+  }
+
+  /// Turns the blurred backdrop behind a transparent window on or off. Shorthand for
+  /// `setBackdrop(BackdropEffect.Blur)` and `setBackdrop(BackdropEffect.None)`, with the same
+  /// platform requirements.
+  #[napi]
+  pub fn set_blur(&self, enabled: bool) -> Result<()> {
+    self.set_backdrop(if enabled {
+      BackdropEffect::Blur
+    } else {
+      BackdropEffect::None
+    })
+  }
+
+  /// Fades the window from opacity `from` to `to` (0-1) over `durationMs`, driven by the event
+  /// loop so the JS thread is not blocked. Cancels any animation already running on this window.
+  /// An `OpacityAnimationFinished` application event is emitted when the target is reached; a
+  /// canceled animation emits nothing. On Linux the effect requires a compositing window manager.
+  #[napi]
+  pub fn animate_opacity(&self, from: f64, to: f64, duration_ms: u32) -> Result<()> {
+    if !from.is_finite() || !to.is_finite() {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "Opacity must be a finite number".to_string(),
+      ));
+    }
+    let handle = self.inner.lock().unwrap();
+    let Some(inner) = handle.as_ref().and_then(|win| win.inner.as_ref()) else {
+      return Err(napi::Error::new(
+        napi::Status::GenericFailure,
+        "Window not initialized".to_string(),
+      ));
+    };
+    let from = from.clamp(0.0, 1.0);
+    apply_window_opacity(&inner.lock().unwrap(), from);
+    self.state.lock().unwrap().opacity_animation = Some(OpacityAnimation {
+      window: inner.clone(),
+      from,
+      to: to.clamp(0.0, 1.0),
+      start: std::time::Instant::now(),
+      duration: std::time::Duration::from_millis(duration_ms as u64),
+      last_frame: None,
+    });
+    Ok(())
+  }
+
+  /// Reserves an overlay title bar of `height` with the window buttons inset by `button_inset`
+  /// from the left edge, so web content can draw its own title bar underneath.
+  ///
+  /// On macOS the content view is extended under a transparent title bar and the traffic lights
+  /// are moved, vertically centered in `height`. Linux has no native overlay; combine with
+  /// `decorations: false` there. Unsupported on Windows, where the caption buttons cannot be
+  /// drawn over web content. The values are exposed to every page loaded in the window as the
+  /// `--titlebar-overlay-height` and `--titlebar-button-inset` CSS variables.
+  #[napi]
+  pub fn set_titlebar_overlay(&self, height: f64, button_inset: f64) -> Result<()> {
+    if cfg!(target_os = "windows") {
+      return Err(crate::wry::enums::Error::Unsupported.to_js_error());
+    }
+    let overlay = TitlebarOverlay {
+      height,
+      button_inset,
+    };
+    let webviews = {
+      let mut state = self.state.lock().unwrap();
+      state.titlebar_overlay = Some(overlay);
+      state.webviews.clone()
+    };
+
+    #[cfg(target_os = "macos")]
+    if let Some(inner) = self
+      .inner
+      .lock()
+      .unwrap()
+      .as_ref()
+      .and_then(|win| win.inner.as_ref())
+    {
+      apply_titlebar_overlay(&inner.lock().unwrap(), overlay);
+    }
+
+    // Webviews that are not built yet receive the variables from an initialization script, built
+    // ones again after every page load.
+    for webview in webviews {
+      if let Some(inner) = webview.inner.lock().unwrap().as_ref() {
+        inner.evaluate_script(titlebar_overlay_script(overlay))?;
+      }
+    }
+    Ok(())
+  }
+
+  /// The overlay title bar set with `setTitlebarOverlay`, if any.
+  #[napi(getter)]
+  pub fn titlebar_overlay(&self) -> Option<TitlebarOverlay> {
+    self.state.lock().unwrap().titlebar_overlay
+  }
+
   #[napi]
   pub fn set_maximized(&self, value: bool) {
     if let Some(win) = self.inner.lock().unwrap().as_ref() {
@@ -707,6 +2541,301 @@ impl BrowserWindow {
     }
   }
 
+  /// Moves the window with the mouse, e.g. from a custom HTML titlebar of an undecorated window.
+  /// Only works while a mouse button is held, so call it from a `mousedown` handler.
+  #[napi]
+  pub fn start_drag(&self) -> Result<()> {
+    self.with_tao_window(|window| window.drag_window().map_err(platform_error))
+  }
+
+  /// Resizes the window with the mouse from the edge or corner in `direction`. Like `startDrag`,
+  /// only works while a mouse button is held.
+  #[napi]
+  pub fn start_resize_drag(&self, direction: crate::tao::enums::ResizeDirection) -> Result<()> {
+    self.with_tao_window(|window| {
+      window
+        .drag_resize_window(crate::tao::structs::tao_resize_direction(direction))
+        .map_err(platform_error)
+    })
+  }
+
+  /// Shows `count` as an unread badge: the dock badge on macOS, a taskbar overlay icon on Windows
+  /// and the launcher count on Linux desktops implementing the Unity launcher API, which need the
+  /// application's `desktopFile`, e.g. `application://my-app.desktop`. `0` or no count clears
+  /// the badge.
+  #[napi]
+  #[cfg_attr(
+    not(any(
+      target_os = "linux",
+      target_os = "dragonfly",
+      target_os = "freebsd",
+      target_os = "netbsd",
+      target_os = "openbsd"
+    )),
+    allow(unused_variables)
+  )]
+  pub fn set_badge_count(&self, count: Option<u32>, desktop_file: Option<String>) -> Result<()> {
+    let count = count.filter(|count| *count > 0);
+    #[cfg(target_os = "windows")]
+    let icon = count.map(badge_icon).transpose()?;
+    self.with_tao_window(|window| {
+      #[cfg(target_os = "macos")]
+      {
+        use tao::platform::macos::WindowExtMacOS;
+        window.set_badge_label(count.map(|count| count.to_string()));
+      }
+      #[cfg(target_os = "windows")]
+      {
+        use tao::platform::windows::WindowExtWindows;
+        window.set_overlay_icon(icon.as_ref());
+      }
+      #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+      ))]
+      {
+        use tao::platform::unix::WindowExtUnix;
+        window.set_badge_count(count.map(i64::from), desktop_file);
+      }
+      Ok(())
+    })
+  }
+
+  /// Runs an operation on the tao window, failing before the window is created or when the
+  /// operation fails.
+  fn with_tao_window<T>(
+    &self,
+    operation: impl FnOnce(&tao::window::Window) -> Result<T>,
+  ) -> Result<T> {
+    let handle = self.inner.lock().unwrap();
+    let Some(inner) = handle.as_ref().and_then(|win| win.inner.as_ref()) else {
+      return Err(napi::Error::new(
+        napi::Status::GenericFailure,
+        "Window not initialized".to_string(),
+      ));
+    };
+    let window = inner.lock().unwrap();
+    operation(&window)
+  }
+
+  /// Size of the window's client area in physical pixels.
+  #[napi]
+  pub fn inner_size(&self) -> Dimensions {
+    let handle = self.inner.lock().unwrap();
+    match handle.as_ref().and_then(|win| win.inner.as_ref()) {
+      Some(inner) => {
+        let size = inner.lock().unwrap().inner_size();
+        Dimensions {
+          width: size.width as f64,
+          height: size.height as f64,
+        }
+      }
+      None => Dimensions {
+        width: 0.0,
+        height: 0.0,
+      },
+    }
+  }
+
+  /// Size of the whole window, including its frame, in physical pixels.
+  #[napi]
+  pub fn outer_size(&self) -> Dimensions {
+    let handle = self.inner.lock().unwrap();
+    match handle.as_ref().and_then(|win| win.inner.as_ref()) {
+      Some(inner) => {
+        let size = inner.lock().unwrap().outer_size();
+        Dimensions {
+          width: size.width as f64,
+          height: size.height as f64,
+        }
+      }
+      None => Dimensions {
+        width: 0.0,
+        height: 0.0,
+      },
+    }
+  }
+
+  /// Ratio of physical to logical pixels on the window's current monitor. Returns 1 before the
+  /// window is created.
+  #[napi]
+  pub fn scale_factor(&self) -> f64 {
+    let handle = self.inner.lock().unwrap();
+    handle
+      .as_ref()
+      .and_then(|win| win.inner.as_ref())
+      .map_or(1.0, |inner| inner.lock().unwrap().scale_factor())
+  }
+
+  /// Resizes the window's client area, in physical pixels.
+  #[napi]
+  pub fn set_inner_size(&self, width: f64, height: f64) {
+    let handle = self.inner.lock().unwrap();
+    if let Some(inner) = handle.as_ref().and_then(|win| win.inner.as_ref()) {
+      inner
+        .lock()
+        .unwrap()
+        .set_inner_size(tao::dpi::PhysicalSize::new(width as u32, height as u32));
+    }
+  }
+
+  /// Sets the smallest client area the user can resize the window to, in logical pixels.
+  #[napi]
+  pub fn set_min_inner_size(&self, width: f64, height: f64) {
+    let handle = self.inner.lock().unwrap();
+    if let Some(inner) = handle.as_ref().and_then(|win| win.inner.as_ref()) {
+      inner
+        .lock()
+        .unwrap()
+        .set_min_inner_size(Some(tao::dpi::LogicalSize::new(width, height)));
+    }
+  }
+
+  /// Sets the largest client area the user can resize the window to, in logical pixels.
+  #[napi]
+  pub fn set_max_inner_size(&self, width: f64, height: f64) {
+    let handle = self.inner.lock().unwrap();
+    if let Some(inner) = handle.as_ref().and_then(|win| win.inner.as_ref()) {
+      inner
+        .lock()
+        .unwrap()
+        .set_max_inner_size(Some(tao::dpi::LogicalSize::new(width, height)));
+    }
+  }
+
+  /// Position of the window's top-left corner, including decorations, in logical pixels.
+  #[napi]
+  pub fn outer_position(&self) -> Position {
+    let handle = self.inner.lock().unwrap();
+    let position = handle
+      .as_ref()
+      .and_then(|win| win.inner.as_ref())
+      .and_then(|inner| {
+        let window = inner.lock().unwrap();
+        let position = window.outer_position().ok()?;
+        Some(position.to_logical::<f64>(window.scale_factor()))
+      });
+    match position {
+      Some(position) => Position {
+        x: position.x,
+        y: position.y,
+      },
+      None => Position { x: 0.0, y: 0.0 },
+    }
+  }
+
+  /// Moves the window's top-left corner, including decorations, in logical pixels.
+  #[napi]
+  pub fn set_outer_position(&self, x: f64, y: f64) {
+    let handle = self.inner.lock().unwrap();
+    if let Some(inner) = handle.as_ref().and_then(|win| win.inner.as_ref()) {
+      inner
+        .lock()
+        .unwrap()
+        .set_outer_position(tao::dpi::LogicalPosition::new(x, y));
+    }
+  }
+
+  /// Whether the window was created with `transparent: true` and can actually be transparent.
+  ///
+  /// On Linux this also requires a running compositor and an RGBA visual on the window's screen;
+  /// without them transparent areas render black. Windows and macOS always support transparency,
+  /// so the requested flag is returned there. Returns `false` until the window is created.
+  #[napi]
+  pub fn is_transparent_effective(&self) -> bool {
+    if !self.state.lock().unwrap().transparent {
+      return false;
+    }
+    let handle = self.inner.lock().unwrap();
+    match handle.as_ref().and_then(|win| win.inner.as_ref()) {
+      Some(inner) => supports_alpha(&inner.lock().unwrap()),
+      None => false,
+    }
+  }
+
+  /// Restricts the visible and clickable area of the window to the union of `regions`, given in
+  /// logical pixels relative to the window. Pass an empty list to restore the full window.
+  ///
+  /// Uses `SetWindowRgn` on Windows and GTK shape regions on Linux. Many Wayland compositors
+  /// ignore the visible shape and only honor the input region. Not supported on macOS.
+  #[napi]
+  pub fn set_shape(&self, regions: Vec<crate::tao::structs::Rectangle>) -> Result<()> {
+    let handle = self.inner.lock().unwrap();
+    let Some(inner) = handle.as_ref().and_then(|win| win.inner.as_ref()) else {
+      return Err(napi::Error::new(
+        napi::Status::GenericFailure,
+        "Window not initialized".to_string(),
+      ));
+    };
+    let window = inner.lock().unwrap();
+    apply_window_shape(&window, &regions)
+  }
+
+  /// Captures the whole window as PNG bytes.
+  ///
+  /// Uses `PrintWindow` on Windows, `CGWindowListCreateImage` on macOS (which needs the screen
+  /// recording permission) and GDK on Linux, where only the client area is captured and Wayland
+  /// sessions are unsupported.
+  #[napi]
+  pub fn capture_window(&self) -> Result<AsyncTask<crate::capture::EncodePng>> {
+    let handle = self.inner.lock().unwrap();
+    let Some(inner) = handle.as_ref().and_then(|win| win.inner.as_ref()) else {
+      return Err(napi::Error::new(
+        napi::Status::GenericFailure,
+        "Window not initialized".to_string(),
+      ));
+    };
+    let image = crate::capture::capture_window(&inner.lock().unwrap())?;
+    Ok(AsyncTask::new(crate::capture::EncodePng::new(image)))
+  }
+
+  /// Brings the window in front of the other windows without focusing it or making it
+  /// topmost. Windows and X11 may keep it behind another application's active window, and
+  /// Wayland compositors ignore restacking requests.
+  #[napi]
+  pub fn raise(&self) {
+    self.restack(true);
+  }
+
+  /// Sends the window behind the other windows without minimizing it. On Windows and X11 this
+  /// also places it behind other applications' windows; on macOS it stays within its window
+  /// level.
+  #[napi]
+  pub fn lower(&self) {
+    self.restack(false);
+  }
+
+  fn restack(&self, to_front: bool) {
+    let handle = self.inner.lock().unwrap();
+    if let Some(inner) = handle.as_ref().and_then(|win| win.inner.as_ref()) {
+      restack_window(&inner.lock().unwrap(), to_front);
+    }
+  }
+
+  /// Selects the next native tab. Only supported on macOS.
+  #[napi]
+  pub fn select_next_tab(&self) {
+    #[cfg(target_os = "macos")]
+    self.with_ns_window(|window| window.selectNextTab(None));
+  }
+
+  /// Selects the previous native tab. Only supported on macOS.
+  #[napi]
+  pub fn select_previous_tab(&self) {
+    #[cfg(target_os = "macos")]
+    self.with_ns_window(|window| window.selectPreviousTab(None));
+  }
+
+  /// Merges all windows into tabs of this window. Only supported on macOS.
+  #[napi]
+  pub fn merge_all_windows(&self) {
+    #[cfg(target_os = "macos")]
+    self.with_ns_window(|window| window.mergeAllWindows(None));
+  }
+
   #[napi]
   pub fn get_available_monitors(&self) -> Vec<Monitor> {
     let mut monitors = Vec::new();
@@ -753,6 +2882,43 @@ impl BrowserWindow {
   #[napi]
   pub fn set_always_on_bottom(&self, _enabled: bool) {}
 
+  /// Shows the window on every workspace or virtual desktop, or only on the current one. Supported
+  /// on macOS and Linux; ignored on Windows.
+  #[napi]
+  pub fn set_visible_on_all_workspaces(&self, enabled: bool) {
+    if let Some(win) = self.inner.lock().unwrap().as_ref() {
+      let _ = win.set_visible_on_all_workspaces(enabled);
+    }
+  }
+
+  /// Keeps the window above other apps' fullscreen spaces, e.g. for a recording indicator, and
+  /// shows it on every space. Only supported on macOS, where it raises the window to the screen
+  /// saver level; disabling it returns the window to the normal level. Some macOS versions only
+  /// honor this for apps with accessibility or screen recording permissions.
+  #[napi]
+  #[cfg_attr(not(target_os = "macos"), allow(unused_variables))]
+  pub fn set_above_fullscreen(&self, enabled: bool) {
+    #[cfg(target_os = "macos")]
+    self.with_ns_window(|window| {
+      use objc2_app_kit::NSWindowCollectionBehavior;
+
+      // kCGNormalWindowLevel and kCGScreenSaverWindowLevel.
+      const NORMAL_WINDOW_LEVEL: isize = 0;
+      const SCREEN_SAVER_WINDOW_LEVEL: isize = 1000;
+
+      let flags = NSWindowCollectionBehavior::CanJoinAllSpaces
+        | NSWindowCollectionBehavior::FullScreenAuxiliary;
+      let behavior = window.collectionBehavior();
+      if enabled {
+        window.setCollectionBehavior(behavior | flags);
+        window.setLevel(SCREEN_SAVER_WINDOW_LEVEL);
+      } else {
+        window.setCollectionBehavior(behavior & !flags);
+        window.setLevel(NORMAL_WINDOW_LEVEL);
+      }
+    });
+  }
+
   #[napi]
   pub fn set_decorations(&self, enabled: bool) {
     if let Some(win) = self.inner.lock().unwrap().as_ref() {
@@ -762,216 +2928,3914 @@ impl BrowserWindow {
 
   #[napi(getter)]
   pub fn fullscreen(&self) -> Option<FullscreenType> {
-    None
+    let handle = self.inner.lock().unwrap();
+    let inner = handle.as_ref()?.inner.as_ref()?;
+    let fullscreen = inner.lock().unwrap().fullscreen()?;
+    match fullscreen {
+      tao::window::Fullscreen::Exclusive(_) => Some(FullscreenType::Exclusive),
+      _ => Some(FullscreenType::Borderless),
+    }
+  }
+
+  /// Enters fullscreen with the given type, or leaves fullscreen when `None` is passed.
+  /// Exclusive fullscreen uses the current monitor's first video mode; use
+  /// `setExclusiveFullscreen` to pick one.
+  #[napi]
+  pub fn set_fullscreen(&self, fullscreen: Option<FullscreenType>) {
+    let handle = self.inner.lock().unwrap();
+    let Some(inner) = handle.as_ref().and_then(|win| win.inner.as_ref()) else {
+      return;
+    };
+    let window = inner.lock().unwrap();
+    window.set_fullscreen(fullscreen.map(|kind| tao_fullscreen(&window, kind)));
+  }
+
+  /// Enters exclusive fullscreen with the video mode matching `width`x`height` at
+  /// `refresh_rate` Hz on the monitor named `monitor_name`, or on the current monitor when no
+  /// name is given. Fails with the list of available modes when none matches.
+  #[napi]
+  pub fn set_exclusive_fullscreen(
+    &self,
+    monitor_name: Option<String>,
+    width: u32,
+    height: u32,
+    refresh_rate: u16,
+  ) -> Result<()> {
+    let handle = self.inner.lock().unwrap();
+    let Some(inner) = handle.as_ref().and_then(|win| win.inner.as_ref()) else {
+      return Err(napi::Error::new(
+        napi::Status::GenericFailure,
+        "Window not initialized".to_string(),
+      ));
+    };
+    let window = inner.lock().unwrap();
+    let monitor = match &monitor_name {
+      Some(name) => window
+        .available_monitors()
+        .find(|monitor| monitor.name().as_ref() == Some(name)),
+      None => window.current_monitor(),
+    };
+    let Some(monitor) = monitor else {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        format!("Monitor {:?} not found", monitor_name.unwrap_or_default()),
+      ));
+    };
+
+    let mut available = Vec::new();
+    for mode in monitor.video_modes() {
+      let size = mode.size();
+      if size.width == width && size.height == height && mode.refresh_rate() == refresh_rate {
+        window.set_fullscreen(Some(tao::window::Fullscreen::Exclusive(mode)));
+        return Ok(());
+      }
+      available.push(format!(
+        "{}x{}@{}Hz",
+        size.width,
+        size.height,
+        mode.refresh_rate()
+      ));
+    }
+    available.dedup();
+    Err(napi::Error::new(
+      napi::Status::InvalidArg,
+      format!(
+        "No video mode {}x{}@{}Hz on this monitor. Available modes: {}",
+        width,
+        height,
+        refresh_rate,
+        available.join(", ")
+      ),
+    ))
   }
 
   #[napi]
   pub fn show(&self) {
     self.set_visible(true);
   }
+
+  /// Closes the window for good, unlike hiding it. Its webviews are dropped first, then the
+  /// native window is released and the application stops tracking it. Webviews that were not
+  /// created yet are discarded, and later calls on this window and its webviews do nothing.
+  #[napi]
+  pub fn destroy(&self) {
+    self.webviews_to_create.lock().unwrap().clear();
+    let webviews = {
+      let mut state = self.state.lock().unwrap();
+      state.destroyed = true;
+      std::mem::take(&mut state.webviews)
+    };
+    for webview in webviews {
+      webview.inner.lock().unwrap().take();
+    }
+    self.inner.lock().unwrap().take();
+  }
 }
 
-#[napi]
-pub struct Webview {
-  #[allow(clippy::arc_with_non_send_sync)]
-  inner: Arc<Mutex<Option<crate::wry::structs::WebView>>>,
-  ipc_listeners: Arc<Mutex<Vec<crate::wry::structs::IpcHandler>>>,
-  #[allow(clippy::arc_with_non_send_sync)]
-  pending_actions: Arc<Mutex<Vec<PendingWebviewAction>>>,
+fn parse_hotkey(accelerator: &str) -> Result<global_hotkey::hotkey::HotKey> {
+  accelerator.parse().map_err(|e| {
+    napi::Error::new(
+      napi::Status::InvalidArg,
+      format!("Invalid accelerator {:?}: {}", accelerator, e),
+    )
+  })
 }
 
-/// Applies all pending actions to the webview after it's been initialized.
-fn apply_pending_actions(
-  webview: &crate::wry::structs::WebView,
-  pending_actions: &Arc<Mutex<Vec<PendingWebviewAction>>>,
-) {
-  let mut actions = pending_actions.lock().unwrap();
-  let actions_vec = std::mem::take(&mut *actions);
-  drop(actions);
-  for action in actions_vec {
-    match action {
-      PendingWebviewAction::LoadUrl(url) => {
-        let _ = webview.load_url(url);
-      }
-      PendingWebviewAction::LoadHtml(html) => {
-        let _ = webview.load_html(html);
-      }
-      PendingWebviewAction::EvaluateScript(js) => {
-        let _ = webview.evaluate_script(js);
-      }
-      PendingWebviewAction::OpenDevtools => {
-        let _ = webview.open_devtools();
-      }
-      PendingWebviewAction::CloseDevtools => {
-        let _ = webview.close_devtools();
-      }
-      PendingWebviewAction::Reload => {
-        let _ = webview.reload();
-      }
-      PendingWebviewAction::Print => {
-        let _ = webview.print();
-      }
+/// Formats a window id the way `BrowserWindow.id`, `ApplicationEvent.windowId` and
+/// `IpcMessage.windowId` report it: the decimal `Window.id` of the low-level API.
+fn window_id_string(id: tao::window::WindowId) -> String {
+  crate::tao::structs::window_id_u64(id).to_string()
+}
+
+/// The id string of `window`, or `None` before it is built.
+fn built_window_id(window: &crate::tao::structs::Window) -> Option<String> {
+  let inner = window.inner.as_ref()?;
+  let id = inner.lock().unwrap().id();
+  Some(window_id_string(id))
+}
+
+/// Converts an error of a tao window operation the platform rejected.
+fn platform_error(e: tao::error::ExternalError) -> napi::Error {
+  napi::Error::new(napi::Status::GenericFailure, e.to_string())
+}
+
+/// Outer position and inner size of `window` in logical pixels.
+fn logical_bounds(
+  window: &tao::window::Window,
+) -> (tao::dpi::LogicalPosition<f64>, tao::dpi::LogicalSize<f64>) {
+  let scale_factor = window.scale_factor();
+  (
+    window
+      .outer_position()
+      .unwrap_or_default()
+      .to_logical(scale_factor),
+    window.inner_size().to_logical(scale_factor),
+  )
+}
+
+/// Part of a restored window, in logical pixels, that must overlap a monitor in both directions
+/// for the window to count as reachable.
+const MIN_VISIBLE_EXTENT: f64 = 48.0;
+
+/// Physical position and size of a window or monitor. Monitors with different scale factors
+/// only share a coordinate space in physical pixels.
+type PhysicalBounds = (tao::dpi::PhysicalPosition<f64>, tao::dpi::PhysicalSize<f64>);
+
+/// A connected monitor.
+#[derive(Clone, Copy)]
+struct MonitorBounds {
+  bounds: PhysicalBounds,
+  scale_factor: f64,
+}
+
+impl MonitorBounds {
+  fn of(monitor: &tao::monitor::MonitorHandle) -> Self {
+    let position = monitor.position();
+    let size = monitor.size();
+    Self {
+      bounds: (
+        tao::dpi::PhysicalPosition::new(position.x as f64, position.y as f64),
+        tao::dpi::PhysicalSize::new(size.width as f64, size.height as f64),
+      ),
+      scale_factor: monitor.scale_factor(),
     }
   }
+
+  fn contains(&self, point: tao::dpi::PhysicalPosition<f64>) -> bool {
+    let (position, size) = self.bounds;
+    (position.x..position.x + size.width).contains(&point.x)
+      && (position.y..position.y + size.height).contains(&point.y)
+  }
 }
 
-#[napi]
-impl Webview {
-  #[napi(getter)]
-  pub fn id(&self) -> String {
-    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
-      webview.id().unwrap_or_default()
-    } else {
-      "uninitialized".to_string()
+/// Converts the logical bounds of `geometry` to physical pixels.
+fn physical_bounds(geometry: &WindowGeometry, scale_factor: f64) -> PhysicalBounds {
+  (
+    tao::dpi::LogicalPosition::new(geometry.x, geometry.y).to_physical(scale_factor),
+    tao::dpi::LogicalSize::new(geometry.width, geometry.height).to_physical(scale_factor),
+  )
+}
+
+/// Scale factor of the monitor a window at the logical position of `geometry` is created on:
+/// like tao, the first monitor that contains the position scaled by its own factor, or `primary`.
+fn scale_factor_at(
+  geometry: &WindowGeometry,
+  monitors: &[MonitorBounds],
+  primary: Option<MonitorBounds>,
+) -> f64 {
+  monitors
+    .iter()
+    .find(|monitor| monitor.contains(physical_bounds(geometry, monitor.scale_factor).0))
+    .or(primary.as_ref())
+    .map_or(1.0, |monitor| monitor.scale_factor)
+}
+
+/// Returns the physical bounds of `geometry`, moved and shrunk onto the primary monitor unless
+/// enough of the window overlaps one of the connected monitors.
+fn clamp_to_monitors(window: &tao::window::Window, geometry: &WindowGeometry) -> PhysicalBounds {
+  let monitors: Vec<_> = window
+    .available_monitors()
+    .map(|monitor| MonitorBounds::of(&monitor))
+    .collect();
+  let primary = window
+    .primary_monitor()
+    .map(|monitor| MonitorBounds::of(&monitor));
+  fit_to_monitors(
+    physical_bounds(geometry, window.scale_factor()),
+    &monitors,
+    primary,
+  )
+}
+
+/// Returns `requested`, moved and shrunk onto `primary`, or the first of `monitors` without one,
+/// unless enough of the window overlaps one of `monitors`.
+fn fit_to_monitors(
+  requested: PhysicalBounds,
+  monitors: &[MonitorBounds],
+  primary: Option<MonitorBounds>,
+) -> PhysicalBounds {
+  let (origin, extent) = requested;
+  let reachable = monitors.iter().any(|monitor| {
+    let (position, size) = monitor.bounds;
+    let min_visible = MIN_VISIBLE_EXTENT * monitor.scale_factor;
+    let overlap_x =
+      (origin.x + extent.width).min(position.x + size.width) - origin.x.max(position.x);
+    let overlap_y =
+      (origin.y + extent.height).min(position.y + size.height) - origin.y.max(position.y);
+    overlap_x >= min_visible && overlap_y >= min_visible
+  });
+  let target = primary.or_else(|| monitors.first().copied());
+  let Some(MonitorBounds {
+    bounds: (position, size),
+    ..
+  }) = target.filter(|_| !reachable)
+  else {
+    return requested;
+  };
+  let width = extent.width.min(size.width);
+  let height = extent.height.min(size.height);
+  (
+    tao::dpi::PhysicalPosition::new(
+      origin.x.clamp(position.x, position.x + size.width - width),
+      origin
+        .y
+        .clamp(position.y, position.y + size.height - height),
+    ),
+    tao::dpi::PhysicalSize::new(width, height),
+  )
+}
+
+/// Side of the overlay icon drawn for `BrowserWindow.setBadgeCount` on Windows. The taskbar scales
+/// it down to its small icon size.
+#[cfg(target_os = "windows")]
+const BADGE_ICON_SIZE: usize = 32;
+
+/// Draws `count`, or `99+` above 99, in white on a red disc.
+#[cfg(target_os = "windows")]
+fn badge_icon(count: u32) -> Result<tao::window::Icon> {
+  // 3x5 pixel glyphs, one row per byte with the leftmost pixel in the highest of three bits.
+  const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+  ];
+  const PLUS: [u8; 5] = [0b000, 0b010, 0b111, 0b010, 0b000];
+  const BACKGROUND: [u8; 3] = [0xd9, 0x30, 0x25];
+
+  let text = if count > 99 {
+    "99+".to_string()
+  } else {
+    count.to_string()
+  };
+  let glyphs: Vec<[u8; 5]> = text
+    .chars()
+    .map(|c| match c.to_digit(10) {
+      Some(digit) => DIGITS[digit as usize],
+      None => PLUS,
+    })
+    .collect();
+  // Each glyph takes four columns including the gap after it; the text stays inside the disc.
+  let columns = 4 * glyphs.len() - 1;
+  let max_width = BADGE_ICON_SIZE * 11 / 16;
+  let scale = (1..=4)
+    .rev()
+    .find(|scale| scale * columns <= max_width)
+    .unwrap_or(1);
+  let (text_width, text_height) = (scale * columns, scale * 5);
+  let left = (BADGE_ICON_SIZE - text_width) / 2;
+  let top = (BADGE_ICON_SIZE - text_height) / 2;
+
+  let center = (BADGE_ICON_SIZE as f64 - 1.0) / 2.0;
+  let radius = BADGE_ICON_SIZE as f64 / 2.0;
+  let mut rgba = vec![0u8; BADGE_ICON_SIZE * BADGE_ICON_SIZE * 4];
+  for y in 0..BADGE_ICON_SIZE {
+    for x in 0..BADGE_ICON_SIZE {
+      let distance = ((x as f64 - center).powi(2) + (y as f64 - center).powi(2)).sqrt();
+      // Fades the outermost pixel to smooth the edge of the disc.
+      let coverage = (radius - distance).clamp(0.0, 1.0);
+      if coverage == 0.0 {
+        continue;
+      }
+      let in_text =
+        (left..left + text_width).contains(&x) && (top..top + text_height).contains(&y) && {
+          let (column, row) = ((x - left) / scale, (y - top) / scale);
+          column % 4 != 3 && glyphs[column / 4][row] & (0b100 >> (column % 4)) != 0
+        };
+      let [r, g, b] = if in_text { [255; 3] } else { BACKGROUND };
+      let offset = (y * BADGE_ICON_SIZE + x) * 4;
+      rgba[offset..offset + 4].copy_from_slice(&[r, g, b, (coverage * 255.0) as u8]);
     }
   }
+  tao::window::Icon::from_rgba(rgba, BADGE_ICON_SIZE as u32, BADGE_ICON_SIZE as u32).map_err(|e| {
+    napi::Error::new(
+      napi::Status::GenericFailure,
+      format!("Failed to create the badge icon: {}", e),
+    )
+  })
+}
 
-  #[napi(getter)]
-  pub fn label(&self) -> String {
-    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
-      webview.label().unwrap_or_default()
-    } else {
-      "uninitialized".to_string()
-    }
+/// Creates an icon from square RGBA pixel data.
+fn square_rgba_icon(rgba: &[u8]) -> Result<tao::window::Icon> {
+  let size = ((rgba.len() / 4) as f64).sqrt() as u32;
+  if size == 0 || (size * size * 4) as usize != rgba.len() {
+    return Err(napi::Error::new(
+      napi::Status::InvalidArg,
+      "Window icon must be square RGBA pixel data".to_string(),
+    ));
   }
+  tao::window::Icon::from_rgba(rgba.to_vec(), size, size)
+    .map_err(|e| napi::Error::new(napi::Status::InvalidArg, format!("Invalid icon: {}", e)))
+}
 
-  #[napi]
-  pub fn on_ipc_message(&self, handler: Option<crate::wry::structs::IpcHandler>) {
-    if let Some(h) = handler {
-      self.ipc_listeners.lock().unwrap().push(h);
+impl BrowserWindow {
+  /// Builds the webviews queued for this window, once the window itself has been built.
+  fn build_pending_webviews(
+    &self,
+    app_handler: &Arc<Mutex<Option<ThreadsafeFunction<ApplicationEvent>>>>,
+  ) {
+    let handle = self.inner.lock().unwrap();
+    let Some(window) = handle.as_ref() else {
+      return;
+    };
+    let pending: Vec<PendingWebview> = self.webviews_to_create.lock().unwrap().drain(..).collect();
+    if pending.is_empty() {
+      return;
+    }
+    for (webview_opts, webview, profile) in pending {
+      build_webview(
+        window,
+        webview_opts,
+        &webview,
+        profile,
+        &self.state,
+        app_handler,
+      );
+    }
+    drop(handle);
+
+    for webview in self.state.lock().unwrap().webviews.iter() {
+      webview.apply_relative_bounds();
     }
   }
+}
 
-  #[napi]
-  pub fn on(&self, handler: crate::wry::structs::IpcHandler) {
-    self.ipc_listeners.lock().unwrap().push(handler);
+/// Fails unless `name` is usable as a single directory name on every platform: `:` would also
+/// allow Windows drive prefixes such as `C:` and alternate data streams.
+fn check_path_component(kind: &str, name: &str) -> Result<()> {
+  let invalid =
+    name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\', ':', '\0']);
+  if invalid {
+    return Err(napi::Error::new(
+      napi::Status::InvalidArg,
+      format!("Invalid {}: {:?}", kind, name),
+    ));
   }
+  Ok(())
+}
 
-  #[napi]
-  pub fn send(&self, message: String) -> Result<()> {
-    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
-      webview.send(message)
-    } else {
-      Ok(())
+/// Removes `.` and `..` components of an absolute path without touching the filesystem.
+fn normalize_path(path: &std::path::Path) -> std::path::PathBuf {
+  use std::path::Component;
+
+  let mut normalized = std::path::PathBuf::new();
+  for component in path.components() {
+    match component {
+      Component::CurDir => {}
+      Component::ParentDir => {
+        normalized.pop();
+      }
+      component => normalized.push(component),
     }
   }
+  normalized
+}
 
+/// Web contexts created for `WebviewOptions.dataDirectory`, keyed by directory.
+type DataDirectories = Arc<Mutex<HashMap<std::path::PathBuf, Profile>>>;
+
+/// Returns the profile backed by `dir`, creating the directory and its web context on first use
+/// so that every webview of the application given the same directory shares one context.
+fn data_directory_profile(directories: &DataDirectories, dir: &str) -> Result<Profile> {
+  std::fs::create_dir_all(dir).map_err(|e| {
+    napi::Error::new(
+      napi::Status::GenericFailure,
+      format!("Failed to create data directory: {}", e),
+    )
+  })?;
+  // Not `canonicalize`, which turns Windows paths into verbatim `\\?\` paths.
+  let path = std::path::absolute(dir)
+    .map(|path| normalize_path(&path))
+    .map_err(|e| {
+      napi::Error::new(
+        napi::Status::GenericFailure,
+        format!("Failed to resolve data directory: {}", e),
+      )
+    })?;
+
+  let mut directories = directories.lock().unwrap();
+  if let Some(profile) = directories.get(&path) {
+    return Ok(profile.clone());
+  }
+  let profile = Profile {
+    name: path
+      .file_name()
+      .map(|name| name.to_string_lossy().to_string())
+      .unwrap_or_default(),
+    context: crate::wry::structs::WebContext::new(Some(path.to_string_lossy().to_string()))?,
+    path: path.clone(),
+  };
+  directories.insert(path, profile.clone());
+  Ok(profile)
+}
+
+/// Range accepted by `Webview.zoom`.
+const MIN_ZOOM: f64 = 0.25;
+const MAX_ZOOM: f64 = 5.0;
+
+/// A named, persistent browsing profile shared by the webviews created with it.
+#[napi]
+#[derive(Clone)]
+pub struct Profile {
+  name: String,
+  path: std::path::PathBuf,
+  context: crate::wry::structs::WebContext,
+}
+
+#[napi]
+impl Profile {
+  #[napi(getter)]
+  pub fn name(&self) -> String {
+    self.name.clone()
+  }
+
+  #[napi(getter)]
+  pub fn path(&self) -> String {
+    self.path.to_string_lossy().to_string()
+  }
+}
+
+#[napi]
+#[derive(Clone)]
+pub struct Webview {
+  #[allow(clippy::arc_with_non_send_sync)]
+  inner: Arc<Mutex<Option<crate::wry::structs::WebView>>>,
+  label: String,
+  window: Arc<Mutex<Option<crate::tao::structs::Window>>>,
+  ipc_listeners: Arc<Mutex<Vec<crate::wry::structs::IpcHandler>>>,
+  source_ipc_listeners: Arc<Mutex<Vec<ThreadsafeFunction<IpcMessage>>>>,
+  #[allow(clippy::arc_with_non_send_sync)]
+  pending_actions: Arc<Mutex<Vec<PendingWebviewAction>>>,
+  relative_bounds: Arc<Mutex<Option<RelativeBounds>>>,
+  fullscreen_handler: Arc<Mutex<Option<ThreadsafeFunction<bool>>>>,
+  download_progress_handler: Arc<Mutex<Option<ThreadsafeFunction<DownloadProgress>>>>,
+  title_changed_handler: Arc<Mutex<Option<ThreadsafeFunction<String>>>>,
+  favicon_changed_handler: Arc<Mutex<Option<ThreadsafeFunction<String>>>>,
+  page_load_handler: Arc<Mutex<Option<ThreadsafeFunction<PageLoadEventData>>>>,
+  #[allow(clippy::arc_with_non_send_sync)]
+  navigation_hook: SyncJsHook<String, bool>,
+  #[allow(clippy::arc_with_non_send_sync)]
+  drag_drop_hook: SyncJsHook<DragDropEventData, bool>,
+  #[allow(clippy::arc_with_non_send_sync)]
+  permission_hook: SyncJsHook<PermissionRequest, crate::wry::enums::PermissionDecision>,
+  custom_protocol_handler: Arc<Mutex<Option<CustomProtocolHandler>>>,
+  history: Arc<Mutex<HistoryState>>,
+  load_guard: Arc<Mutex<LoadGuard>>,
+  pings: Arc<Mutex<PingState>>,
+  #[allow(clippy::arc_with_non_send_sync)]
+  build_context: Arc<Mutex<Option<WebviewBuildContext>>>,
+  zoom: Arc<Mutex<f64>>,
+  page: Arc<Mutex<PageState>>,
+  gestures: Arc<Mutex<GestureFlags>>,
+}
+
+type CustomProtocolHandler = ThreadsafeFunction<IpcMessage, CustomProtocolResponse>;
+
+/// JS handler whose result the engine needs before its callback returns. Engine callbacks run on
+/// the event loop thread, which is the JS thread, so the handler is called synchronously instead
+/// of through a threadsafe function that could only run after the answer is due.
+struct SyncJsHandler<Args: JsValuesTupleIntoVec, Return> {
+  env: napi::sys::napi_env,
+  handler: FunctionRef<Args, Return>,
+}
+
+/// Replaceable slot for a `SyncJsHandler`, cloned out before calling so the handler may replace
+/// itself.
+type SyncJsHook<Args, Return> = Arc<Mutex<Option<std::rc::Rc<SyncJsHandler<Args, Return>>>>>;
+
+impl<Args: JsValuesTupleIntoVec, Return: FromNapiValue> SyncJsHandler<Args, Return> {
+  fn new(env: &Env, handler: Function<Args, Return>) -> Result<Self> {
+    Ok(Self {
+      env: env.raw(),
+      handler: handler.create_ref()?,
+    })
+  }
+
+  /// Calls the handler, returning `None` if it throws.
+  fn call(&self, args: Args) -> Option<Return> {
+    let env = Env::from_raw(self.env);
+    self
+      .handler
+      .borrow_back(&env)
+      .and_then(|handler| handler.call(args))
+      .ok()
+  }
+}
+
+/// Document state tracked from page load and title events, and from the focus bridge script.
+#[derive(Default)]
+struct PageState {
+  url: Option<String>,
+  title: Option<String>,
+  loading: bool,
+  focused: bool,
+}
+
+/// Trackpad gestures enabled with `Webview.setPinchZoomEnabled` and
+/// `Webview.setSwipeNavigationEnabled`.
+#[derive(Clone, Copy, PartialEq)]
+struct GestureFlags {
+  pinch_zoom: bool,
+  swipe_navigation: bool,
+}
+
+impl Default for GestureFlags {
+  fn default() -> Self {
+    Self {
+      pinch_zoom: true,
+      swipe_navigation: true,
+    }
+  }
+}
+
+/// Session history position as last reported by the page, `None` while unknown.
+#[derive(Default)]
+struct HistoryState {
+  can_go_back: Option<bool>,
+  can_go_forward: Option<bool>,
+}
+
+/// Responsiveness probes waiting for the page to answer, keyed by probe id.
+#[derive(Default)]
+struct PingState {
+  next_id: u64,
+  waiters: HashMap<u64, std::sync::mpsc::Sender<()>>,
+}
+
+/// Waits on the libuv thread pool for the page to answer a responsiveness probe.
+pub struct ResponsivenessProbe {
+  id: u64,
+  receiver: std::sync::mpsc::Receiver<()>,
+  timeout: std::time::Duration,
+  pings: Arc<Mutex<PingState>>,
+}
+
+#[napi]
+impl Task for ResponsivenessProbe {
+  type Output = bool;
+  type JsValue = bool;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let answered = self.receiver.recv_timeout(self.timeout).is_ok();
+    self.pings.lock().unwrap().waiters.remove(&self.id);
+    Ok(answered)
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+/// Waits on the libuv thread pool for the JSON-serialized result of a script evaluated in the page.
+pub struct ScriptValue {
+  receiver: std::sync::mpsc::Receiver<String>,
+  timeout: std::time::Duration,
+  /// Resolve to the string the script returned instead of its JSON.
+  decode_string: bool,
+  /// Emit a process warning for decoded strings longer than this many bytes.
+  warn_bytes: Option<usize>,
+}
+
+#[napi]
+impl Task for ScriptValue {
+  type Output = String;
+  type JsValue = String;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let json = self
+      .receiver
+      .recv_timeout(self.timeout)
+      .map_err(|e| match e {
+        std::sync::mpsc::RecvTimeoutError::Timeout => napi::Error::new(
+          napi::Status::GenericFailure,
+          format!("The script did not return within {:?}", self.timeout),
+        ),
+        std::sync::mpsc::RecvTimeoutError::Disconnected => napi::Error::new(
+          napi::Status::GenericFailure,
+          "The webview was closed before the script returned".to_string(),
+        ),
+      })?;
+    if self.decode_string {
+      // `null` means there was no value.
+      let value = serde_json::from_str::<Option<String>>(&json)
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+      return Ok(value);
+    }
+    // Some engines report `undefined` as an empty string.
+    if json.is_empty() {
+      Ok("null".to_string())
+    } else {
+      Ok(json)
+    }
+  }
+
+  fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    if let Some(warn_bytes) = self
+      .warn_bytes
+      .filter(|warn_bytes| output.len() > *warn_bytes)
+    {
+      // The result is still returned, so a warning that cannot be emitted is not an error.
+      let _ = emit_process_warning(
+        &env,
+        format!(
+          "The page HTML is {} bytes, more than {} bytes; copying it out of the webview is slow",
+          output.len(),
+          warn_bytes
+        ),
+      );
+    }
+    Ok(output)
+  }
+}
+
+/// Reports `message` with `process.emitWarning`, printed by Node unless warnings are disabled.
+fn emit_process_warning(env: &Env, message: String) -> Result<()> {
+  let process: Object = env.get_global()?.get_named_property("process")?;
+  let emit_warning: Function<String, ()> = process.get_named_property("emitWarning")?;
+  emit_warning.apply(process, message)
+}
+
+/// Size above which `Webview.getHtml` emits a process warning along with the page.
+const LARGE_HTML_BYTES: usize = 16 * 1024 * 1024;
+
+/// What a webview was last built with, kept so that `Webview.restart` can build it again.
+#[derive(Clone)]
+struct WebviewBuildContext {
+  options: WebviewOptions,
+  profile: Option<Profile>,
+  state: std::sync::Weak<Mutex<WindowState>>,
+  app_handler: Arc<Mutex<Option<ThreadsafeFunction<ApplicationEvent>>>>,
+}
+
+/// Load timeout configuration. `generation` changes on every page load event so that a pending
+/// timer can tell whether the load it was armed for is still in progress.
+#[derive(Default)]
+struct LoadGuard {
+  timeout_ms: u32,
+  generation: u64,
+}
+
+/// Webview bounds expressed as fractions of the owning window's inner size.
+#[derive(Clone, Copy)]
+struct RelativeBounds {
+  x: f64,
+  y: f64,
+  width: f64,
+  height: f64,
+}
+
+/// Builds `webview` into `window` and applies the actions queued before it existed.
+fn build_webview(
+  window: &crate::tao::structs::Window,
+  webview_opts: WebviewOptions,
+  webview: &Webview,
+  profile: Option<Profile>,
+  state: &Arc<Mutex<WindowState>>,
+  app_handler: &Arc<Mutex<Option<ThreadsafeFunction<ApplicationEvent>>>>,
+) {
+  *webview.build_context.lock().unwrap() = Some(WebviewBuildContext {
+    options: webview_opts.clone(),
+    profile: profile.clone(),
+    state: Arc::downgrade(state),
+    app_handler: app_handler.clone(),
+  });
+  if let Ok(mut builder) = crate::wry::structs::WebViewBuilder::new() {
+    if let Some(profile) = profile {
+      let _ = builder.with_web_context(&profile.context);
+    }
+    if let Some(url) = webview_opts.url {
+      let _ = builder.with_url(url);
+    }
+    if let Some(html) = webview_opts.html {
+      let _ = builder.with_html(html);
+    }
+    if let Some(width) = webview_opts.width {
+      let _ = builder.with_width(width as u32);
+    }
+    if let Some(height) = webview_opts.height {
+      let _ = builder.with_height(height as u32);
+    }
+    if let Some(x) = webview_opts.x {
+      let _ = builder.with_x(x as i32);
+    }
+    if let Some(y) = webview_opts.y {
+      let _ = builder.with_y(y as i32);
+    }
+    if let Some(user_agent) = webview_opts.user_agent {
+      let _ = builder.with_user_agent(user_agent);
+    }
+    if let Some(transparent) = webview_opts.transparent {
+      let _ = builder.with_transparent(transparent);
+    }
+    if let Some(devtools) = webview_opts.enable_devtools {
+      let _ = builder.with_devtools(devtools);
+    }
+    if let Some(incognito) = webview_opts.incognito {
+      let _ = builder.with_incognito(incognito);
+    }
+    if let Some(hotkeys_zoom) = webview_opts.hotkeys_zoom {
+      let _ = builder.with_hotkeys_zoom(hotkeys_zoom);
+    }
+    if let Some(clipboard) = webview_opts.clipboard {
+      let _ = builder.with_clipboard(clipboard);
+    }
+    if let Some(accept_language) = webview_opts.accept_language {
+      let _ = builder.with_accept_language(accept_language);
+    }
+    for header in webview_opts.default_headers.unwrap_or_default() {
+      let _ = builder.with_header(header.key, header.value.unwrap_or_default());
+    }
+    if let Some(config) = webview_opts
+      .proxy
+      .as_ref()
+      .and_then(|proxy| proxy_config(proxy).ok().flatten())
+    {
+      builder.set_proxy_config(config);
+    }
+    if webview_opts.cache_mode == Some(CacheMode::NoCache) {
+      builder.set_cache_disabled(true);
+    }
+    if let Some(disable_gpu) = webview_opts.disable_gpu {
+      builder.set_gpu_disabled(disable_gpu);
+    }
+    if webview_opts.min_tls_version == Some(TlsVersion::Tls13) {
+      builder.set_tls13_required(true);
+    }
+    if let Some(restoration) = webview_opts.scroll_restoration {
+      let value = match restoration {
+        ScrollRestoration::Auto => "auto",
+        ScrollRestoration::Manual => "manual",
+      };
+      let _ = builder.with_initialization_script(crate::wry::structs::InitializationScript {
+        js: format!(
+          "try {{ history.scrollRestoration = '{}'; }} catch (e) {{}}",
+          value
+        ),
+        once: false,
+      });
+    }
+    for scheme in webview_opts.custom_protocols.unwrap_or_default() {
+      let protocol_handler = webview.custom_protocol_handler.clone();
+      builder.add_custom_protocol(
+        scheme,
+        Box::new(move |request, responder| {
+          respond_custom_protocol(&protocol_handler, request, responder)
+        }),
+      );
+    }
+    if let Some(root) = webview_opts.asset_root {
+      let root = std::path::PathBuf::from(root);
+      builder.add_custom_protocol(
+        ASSET_SCHEME.to_string(),
+        Box::new(move |request, responder| respond_asset(&root, request, responder)),
+      );
+    }
+    if let Some(autoplay) = webview_opts.autoplay {
+      let _ = builder.with_autoplay(autoplay);
+    }
+    if let Some(back_forward_navigation_gestures) = webview_opts.back_forward_navigation_gestures {
+      let _ = builder.with_back_forward_navigation_gestures(back_forward_navigation_gestures);
+    }
+    // Apply preload script as initialization script
+    if let Some(preload) = webview_opts.preload {
+      let init_script = crate::wry::structs::InitializationScript {
+        js: preload,
+        once: false,
+      };
+      let _ = builder.with_initialization_script(init_script);
+    }
+    if let Some(patterns) = webview_opts.blocked_url_patterns.filter(|p| !p.is_empty()) {
+      let _ = builder.with_initialization_script(crate::wry::structs::InitializationScript {
+        js: request_blocker_script(&patterns),
+        once: false,
+      });
+      builder.add_navigation_filter(Box::new(move |url| {
+        !patterns.iter().any(|pattern| glob_match(pattern, url))
+      }));
+    }
+    if let Some(color) = webview_opts
+      .paint_placeholder_color
+      .filter(|c| c.len() >= 3)
+    {
+      let rgba = [
+        color[0],
+        color[1],
+        color[2],
+        color.get(3).copied().unwrap_or(255),
+      ];
+      let _ = builder.with_background_color(Buffer::from(rgba.to_vec()));
+      let _ = builder.with_initialization_script(crate::wry::structs::InitializationScript {
+        js: paint_placeholder_script(rgba),
+        once: false,
+      });
+    }
+    if let Some(color) = webview_opts.background_color.filter(|c| c.len() >= 3) {
+      let rgba = vec![
+        color[0],
+        color[1],
+        color[2],
+        color.get(3).copied().unwrap_or(255),
+      ];
+      let _ = builder.with_background_color(Buffer::from(rgba));
+    }
+    let load_guard = webview.load_guard.clone();
+    let app_handler = app_handler.clone();
+    let load_window_id = built_window_id(window).unwrap_or_default();
+    builder.add_page_load_handler(Box::new(move |event, url| {
+      let (timeout, generation) = {
+        let mut guard = load_guard.lock().unwrap();
+        guard.generation += 1;
+        (guard.timeout_ms, guard.generation)
+      };
+      if timeout == 0 || !matches!(event, wry::PageLoadEvent::Started) {
+        return;
+      }
+      let load_guard = load_guard.clone();
+      let app_handler = app_handler.clone();
+      let window_id = load_window_id.clone();
+      let url = url.to_string();
+      std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(timeout as u64));
+        if load_guard.lock().unwrap().generation != generation {
+          return;
+        }
+        if let Some(handler) = app_handler.lock().unwrap().as_ref() {
+          let _ = handler.call(
+            Ok(ApplicationEvent {
+              url: Some(url),
+              window_id: Some(window_id),
+              ..ApplicationEvent::new(WebviewApplicationEvent::LoadTimeout)
+            }),
+            ThreadsafeFunctionCallMode::NonBlocking,
+          );
+        }
+      });
+    }));
+    // WKWebView does not report download progress, so it is sampled from the destination file.
+    // Download handlers change how engines treat downloads, so they are only installed for
+    // webviews with a progress handler.
+    #[cfg(target_os = "macos")]
+    if webview.download_progress_handler.lock().unwrap().is_some() {
+      let downloads = Arc::new(Mutex::new(HashMap::new()));
+      let started_downloads = downloads.clone();
+      let progress_handler = webview.download_progress_handler.clone();
+      let completed_progress_handler = webview.download_progress_handler.clone();
+      builder.set_download_handlers(
+        Box::new(move |url, path| {
+          if !path.as_os_str().is_empty() {
+            started_downloads
+              .lock()
+              .unwrap()
+              .insert(url.clone(), path.clone());
+            watch_download(url, started_downloads.clone(), progress_handler.clone());
+          }
+          true
+        }),
+        Box::new(move |url, path, success| {
+          let started_path = downloads.lock().unwrap().remove(&url);
+          let Some(path) = path.or(started_path).filter(|_| success) else {
+            return;
+          };
+          if let Some(handler) = completed_progress_handler.lock().unwrap().as_ref() {
+            let size = std::fs::metadata(&path).map(|m| m.len() as f64).ok();
+            let _ = handler.call(
+              Ok(DownloadProgress {
+                url,
+                received_bytes: size.unwrap_or(0.0),
+                total_bytes: size,
+              }),
+              ThreadsafeFunctionCallMode::NonBlocking,
+            );
+          }
+        }),
+      );
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+      let progress_handler = webview.download_progress_handler.clone();
+      builder.set_download_progress_handler(Box::new(move |url, received, total| {
+        if let Some(handler) = progress_handler.lock().unwrap().as_ref() {
+          let _ = handler.call(
+            Ok(DownloadProgress {
+              url: url.to_string(),
+              received_bytes: received as f64,
+              total_bytes: total.map(|total| total as f64),
+            }),
+            ThreadsafeFunctionCallMode::NonBlocking,
+          );
+        }
+      }));
+    }
+    let built_overlay = state.lock().unwrap().titlebar_overlay;
+    if let Some(overlay) = built_overlay {
+      let _ = builder.with_initialization_script(crate::wry::structs::InitializationScript {
+        js: titlebar_overlay_script(overlay),
+        once: false,
+      });
+    }
+    let overlay_state = Arc::downgrade(state);
+    let overlay_target = webview.inner.clone();
+    builder.add_page_load_handler(Box::new(move |event, _| {
+      let Some(state) = overlay_state.upgrade() else {
+        return;
+      };
+      let Ok(overlay) = state.try_lock().map(|state| state.titlebar_overlay) else {
+        return;
+      };
+      if !matches!(event, wry::PageLoadEvent::Finished) || overlay == built_overlay {
+        return;
+      }
+      // The webview is already locked when the event is raised synchronously by a call on it.
+      if let (Some(overlay), Ok(inner)) = (overlay, overlay_target.try_lock()) {
+        if let Some(webview) = inner.as_ref() {
+          let _ = webview.evaluate_script(titlebar_overlay_script(overlay));
+        }
+      }
+    }));
+    let _ = builder.with_initialization_script(crate::wry::structs::InitializationScript {
+      js: FULLSCREEN_BRIDGE_SCRIPT.to_string(),
+      once: false,
+    });
+    let _ = builder.with_initialization_script(crate::wry::structs::InitializationScript {
+      js: HISTORY_BRIDGE_SCRIPT.to_string(),
+      once: false,
+    });
+    let _ = builder.with_initialization_script(crate::wry::structs::InitializationScript {
+      js: FAVICON_BRIDGE_SCRIPT.to_string(),
+      once: false,
+    });
+    let _ = builder.with_initialization_script(crate::wry::structs::InitializationScript {
+      js: FOCUS_BRIDGE_SCRIPT.to_string(),
+      once: false,
+    });
+    let _ = builder.with_initialization_script(crate::wry::structs::InitializationScript {
+      js: REQUEST_BRIDGE_SCRIPT.to_string(),
+      once: false,
+    });
+    // Initialization scripts are fixed once the webview is built, so gesture flags changed later
+    // are re-applied after every page load.
+    let built_gestures = *webview.gestures.lock().unwrap();
+    if built_gestures != GestureFlags::default() {
+      let _ = builder.with_initialization_script(crate::wry::structs::InitializationScript {
+        js: gesture_guard_script(built_gestures),
+        once: false,
+      });
+    }
+    let gestures = webview.gestures.clone();
+    let gestures_target = webview.inner.clone();
+    builder.add_page_load_handler(Box::new(move |event, _| {
+      let flags = *gestures.lock().unwrap();
+      if !matches!(event, wry::PageLoadEvent::Finished) || flags == built_gestures {
+        return;
+      }
+      // The webview is already locked when the event is raised synchronously by a call on it.
+      if let Ok(inner) = gestures_target.try_lock() {
+        if let Some(webview) = inner.as_ref() {
+          let _ = webview.evaluate_script(gesture_guard_script(flags));
+        }
+      }
+    }));
+    let custom_decorations = state.lock().unwrap().custom_decorations;
+    if let Some(decorations) = custom_decorations {
+      let _ = builder.with_initialization_script(crate::wry::structs::InitializationScript {
+        js: custom_decorations_script(decorations.resize_border.unwrap_or(8.0).max(0.0)),
+        once: false,
+      });
+    }
+    let navigation_hook = webview.navigation_hook.clone();
+    builder.add_navigation_filter(Box::new(move |url| {
+      let hook = navigation_hook.lock().unwrap().clone();
+      hook.is_none_or(|hook| hook.call(url.to_string()).unwrap_or(true))
+    }));
+    let drag_drop_hook = webview.drag_drop_hook.clone();
+    builder.set_drag_drop_handler(Box::new(move |event| {
+      let hook = drag_drop_hook.lock().unwrap().clone();
+      hook.is_some_and(|hook| hook.call(drag_drop_event_data(event)).unwrap_or(false))
+    }));
+    let permission_hook = webview.permission_hook.clone();
+    builder.set_permission_handler(Box::new(move |origin, kind| {
+      let hook = permission_hook.lock().unwrap().clone()?;
+      match hook.call(PermissionRequest {
+        origin: origin.to_string(),
+        kind,
+      })? {
+        crate::wry::enums::PermissionDecision::Grant => Some(true),
+        crate::wry::enums::PermissionDecision::Deny => Some(false),
+        crate::wry::enums::PermissionDecision::Default => None,
+      }
+    }));
+    // Engines reset the zoom level on navigation, so it is re-applied after every page load.
+    let zoom = webview.zoom.clone();
+    let zoom_target = webview.inner.clone();
+    builder.add_page_load_handler(Box::new(move |event, _| {
+      let scale_factor = *zoom.lock().unwrap();
+      if !matches!(event, wry::PageLoadEvent::Finished) || scale_factor == 1.0 {
+        return;
+      }
+      // The webview is already locked when the event is raised synchronously by a call on it.
+      if let Ok(inner) = zoom_target.try_lock() {
+        if let Some(webview) = inner.as_ref().and_then(|webview| webview.inner.as_ref()) {
+          let _ = webview.lock().unwrap().zoom(scale_factor);
+        }
+      }
+    }));
+    let page = webview.page.clone();
+    builder.add_page_load_handler(Box::new(move |event, url| {
+      let mut page = page.lock().unwrap();
+      page.url = Some(url.to_string());
+      page.loading = matches!(event, wry::PageLoadEvent::Started);
+      // The new document reports its own focus once its bridge script runs.
+      if page.loading {
+        page.focused = false;
+      }
+    }));
+    let page_load_handler = webview.page_load_handler.clone();
+    builder.add_page_load_handler(Box::new(move |event, url| {
+      if let Some(handler) = page_load_handler.lock().unwrap().as_ref() {
+        let event = match event {
+          wry::PageLoadEvent::Started => crate::wry::enums::PageLoadEvent::Started,
+          wry::PageLoadEvent::Finished => crate::wry::enums::PageLoadEvent::Completed,
+        };
+        let _ = handler.call(
+          Ok(PageLoadEventData {
+            event,
+            url: url.to_string(),
+          }),
+          ThreadsafeFunctionCallMode::NonBlocking,
+        );
+      }
+    }));
+    let title_changed_handler = webview.title_changed_handler.clone();
+    // `WindowState` owns the webview and with it this handler, so it is only referenced weakly.
+    let title_state = Arc::downgrade(state);
+    let title_window = window.inner.clone();
+    let title_page = webview.page.clone();
+    builder.set_title_changed_handler(Box::new(move |title| {
+      title_page.lock().unwrap().title = Some(title.clone());
+      if let Some(state) = title_state.upgrade() {
+        let mut state = state.lock().unwrap();
+        if let Some(loading) = state.loading_state.take() {
+          if let Some(window) = title_window.as_ref() {
+            let window = window.lock().unwrap();
+            window.set_title(&title);
+            if loading.replaced_icon {
+              window.set_window_icon(state.icon.clone());
+            }
+          }
+        }
+      }
+      if let Some(handler) = title_changed_handler.lock().unwrap().as_ref() {
+        let _ = handler.call(Ok(title), ThreadsafeFunctionCallMode::NonBlocking);
+      }
+    }));
+    let fullscreen_handler = webview.fullscreen_handler.clone();
+    let favicon_changed_handler = webview.favicon_changed_handler.clone();
+    let focus_page = webview.page.clone();
+    let history = webview.history.clone();
+    let pings = webview.pings.clone();
+    let chrome_window = window
+      .inner
+      .clone()
+      .filter(|_| custom_decorations.is_some());
+    let source_listeners = webview.source_ipc_listeners.clone();
+    let reply_target = webview.inner.clone();
+    // Matches `BrowserWindow.id` and `ApplicationEvent.windowId`.
+    let window_id = built_window_id(window).unwrap_or_default();
+    let label = webview.label.clone();
+    builder.set_internal_ipc_handler(Box::new(move |request| {
+      let msg = request.body();
+      if let Some(state) = msg.strip_prefix(FULLSCREEN_MESSAGE_PREFIX) {
+        if let Some(handler) = fullscreen_handler.lock().unwrap().as_ref() {
+          let _ = handler.call(Ok(state == "1"), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+        return true;
+      }
+      if let Some(state) = msg.strip_prefix(HISTORY_MESSAGE_PREFIX) {
+        let flag = |index: usize| match state.get(index..index + 1) {
+          Some("1") => Some(true),
+          Some("0") => Some(false),
+          _ => None,
+        };
+        let mut history = history.lock().unwrap();
+        history.can_go_back = flag(0);
+        history.can_go_forward = flag(1);
+        return true;
+      }
+      if let Some(href) = msg.strip_prefix(FAVICON_MESSAGE_PREFIX) {
+        if let Some(handler) = favicon_changed_handler.lock().unwrap().as_ref() {
+          let _ = handler.call(
+            Ok(href.to_string()),
+            ThreadsafeFunctionCallMode::NonBlocking,
+          );
+        }
+        return true;
+      }
+      if let Some(state) = msg.strip_prefix(FOCUS_MESSAGE_PREFIX) {
+        focus_page.lock().unwrap().focused = state == "1";
+        return true;
+      }
+      if let Some(action) = msg.strip_prefix(CHROME_MESSAGE_PREFIX) {
+        if let Some(window) = &chrome_window {
+          handle_chrome_action(&window.lock().unwrap(), action);
+        }
+        return true;
+      }
+      if let Some(id) = msg.strip_prefix(PONG_MESSAGE_PREFIX) {
+        if let Some(waiter) = id
+          .parse()
+          .ok()
+          .and_then(|id| pings.lock().unwrap().waiters.remove(&id))
+        {
+          let _ = waiter.send(());
+        }
+        return true;
+      }
+      let (request_id, body) = match msg
+        .strip_prefix(REQUEST_MESSAGE_PREFIX)
+        .and_then(|rest| rest.split_once(':'))
+      {
+        Some((id, body)) => (Some(id.to_string()), body),
+        None => (None, msg.as_str()),
+      };
+      let source_listeners = source_listeners.lock().unwrap();
+      if let (Some(id), true) = (&request_id, source_listeners.is_empty()) {
+        reject_request(
+          &reply_target,
+          id,
+          "No handler is registered for the request",
+        );
+      }
+      for listener in source_listeners.iter() {
+        let message = IpcMessage {
+          body: body.as_bytes().to_vec().into(),
+          method: request.method().to_string(),
+          headers: request
+            .headers()
+            .iter()
+            .map(|(key, value)| HeaderData {
+              key: key.to_string(),
+              value: value.to_str().ok().map(|value| value.to_string()),
+            })
+            .collect(),
+          uri: request.uri().to_string(),
+          window_id: Some(window_id.clone()),
+          label: Some(label.clone()),
+          request_id: request_id.clone(),
+        };
+        let Some(id) = request_id.clone() else {
+          let _ = listener.call(Ok(message), ThreadsafeFunctionCallMode::NonBlocking);
+          continue;
+        };
+        let reply_target = reply_target.clone();
+        let _ = listener.call_with_return_value(
+          Ok(message),
+          ThreadsafeFunctionCallMode::NonBlocking,
+          move |result: Result<Unknown>, _env| {
+            if let Err(error) = result {
+              reject_request(&reply_target, &id, &error.reason);
+            }
+            Ok(())
+          },
+        );
+      }
+      // Requests only make sense to listeners that can reply to them.
+      request_id.is_some()
+    }));
+    // Build the webview - pass the ipc_listeners Arc directly to setup_ipc_handler
+    if let Ok(built) = builder.build_on_window(
+      window,
+      webview.label.clone(),
+      Some(webview.ipc_listeners.clone()),
+    ) {
+      let mut wv_handle = webview.inner.lock().unwrap();
+      *wv_handle = Some(built);
+
+      // Apply any pending actions that were called before the webview was initialized
+      apply_pending_actions(wv_handle.as_ref().unwrap(), &webview.pending_actions);
+    }
+  }
+}
+
+/// Applies all pending actions to the webview after it's been initialized.
+fn apply_pending_actions(
+  webview: &crate::wry::structs::WebView,
+  pending_actions: &Arc<Mutex<Vec<PendingWebviewAction>>>,
+) {
+  let mut actions = pending_actions.lock().unwrap();
+  let actions_vec = std::mem::take(&mut *actions);
+  drop(actions);
+  for action in actions_vec {
+    match action {
+      PendingWebviewAction::LoadUrl(url) => {
+        let _ = webview.load_url(url);
+      }
+      PendingWebviewAction::LoadHtml(html) => {
+        let _ = webview.load_html(html);
+      }
+      PendingWebviewAction::EvaluateScript(js) => {
+        let _ = webview.evaluate_script(js);
+      }
+      PendingWebviewAction::OpenDevtools => {
+        let _ = webview.open_devtools();
+      }
+      PendingWebviewAction::CloseDevtools => {
+        let _ = webview.close_devtools();
+      }
+      PendingWebviewAction::Reload => {
+        let _ = webview.reload();
+      }
+      PendingWebviewAction::Print => {
+        let _ = webview.print();
+      }
+      PendingWebviewAction::SetBounds(bounds) => {
+        let _ = webview.set_bounds(bounds);
+      }
+    }
+  }
+}
+
+#[napi]
+impl Webview {
+  #[napi(getter)]
+  pub fn id(&self) -> String {
+    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
+      webview.id().unwrap_or_default()
+    } else {
+      "uninitialized".to_string()
+    }
+  }
+
+  #[napi(getter)]
+  pub fn label(&self) -> String {
+    self.label.clone()
+  }
+
+  #[napi]
+  pub fn on_ipc_message(&self, handler: Option<crate::wry::structs::IpcHandler>) {
+    if let Some(h) = handler {
+      self.ipc_listeners.lock().unwrap().push(h);
+    }
+  }
+
+  #[napi]
+  pub fn on(&self, handler: crate::wry::structs::IpcHandler) {
+    self.ipc_listeners.lock().unwrap().push(handler);
+  }
+
+  /// Adds a listener for IPC messages that receives the full request together with the
+  /// `windowId` and `label` of the sending webview, for routing messages from several webviews.
+  #[napi]
+  pub fn on_ipc_message_with_source(&self, handler: ThreadsafeFunction<IpcMessage>) {
+    self.source_ipc_listeners.lock().unwrap().push(handler);
+  }
+
+  /// Answers a message the page sent with `window.__webview_invoke__(message)`, resolving the
+  /// returned promise with `body` decoded as UTF-8. `requestId` is the `IpcMessage.requestId`
+  /// delivered to `onIpcMessageWithSource` handlers; such requests are not passed to
+  /// `onIpcMessage` handlers, which cannot reply. The page's promise is rejected instead when no
+  /// `onIpcMessageWithSource` handler is registered or a handler throws.
+  #[napi]
+  pub fn reply(&self, request_id: String, body: Buffer) -> Result<()> {
+    let inner = self.inner.lock().unwrap();
+    let webview = inner
+      .as_ref()
+      .ok_or_else(|| crate::wry::enums::Error::Uninitialized.to_js_error())?;
+    webview.evaluate_script(reply_script(
+      &request_id,
+      Some(&String::from_utf8_lossy(&body)),
+      None,
+    ))
+  }
+
+  #[napi]
+  pub fn send(&self, message: String) -> Result<()> {
+    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
+      webview.send(message)
+    } else {
+      Ok(())
+    }
+  }
+
+  #[napi]
+  pub fn load_url(&self, url: String) -> Result<()> {
+    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
+      webview.load_url(url)
+    } else {
+      // Queue the action to be applied when the webview is initialized
+      self
+        .pending_actions
+        .lock()
+        .unwrap()
+        .push(PendingWebviewAction::LoadUrl(url));
+      Ok(())
+    }
+  }
+
+  /// Checks whether the page still runs scripts by asking it to answer over IPC. Resolves to
+  /// `false` if no answer arrives within `timeoutMs` (default 1000), e.g. because the page is
+  /// stuck in a long-running script. Combine with `restart` to recover a hung webview.
+  #[napi]
+  pub fn is_responsive(&self, timeout_ms: Option<u32>) -> Result<AsyncTask<ResponsivenessProbe>> {
+    let inner = self.inner.lock().unwrap();
+    let webview = inner
+      .as_ref()
+      .ok_or_else(|| crate::wry::enums::Error::Uninitialized.to_js_error())?;
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let id = {
+      let mut pings = self.pings.lock().unwrap();
+      pings.next_id += 1;
+      let id = pings.next_id;
+      pings.waiters.insert(id, sender);
+      id
+    };
+    webview.evaluate_script(format!(
+      "window.ipc.postMessage('{}{}')",
+      PONG_MESSAGE_PREFIX, id
+    ))?;
+    Ok(AsyncTask::new(ResponsivenessProbe {
+      id,
+      receiver,
+      timeout: std::time::Duration::from_millis(timeout_ms.unwrap_or(1000) as u64),
+      pings: self.pings.clone(),
+    }))
+  }
+
+  /// Resolves to the HTML of the whole document, `document.documentElement.outerHTML`. Pages
+  /// larger than 16 MiB are returned too, with a warning from `process.emitWarning`. Rejects if
+  /// the HTML is not returned within `timeoutMs`, 5000 by default. Fails if the webview has not
+  /// been created yet.
+  #[napi]
+  pub fn get_html(&self, timeout_ms: Option<u32>) -> Result<AsyncTask<ScriptValue>> {
+    self.evaluate_string(
+      "document.documentElement ? document.documentElement.outerHTML : ''",
+      Some(LARGE_HTML_BYTES),
+      timeout_ms,
+    )
+  }
+
+  /// Evaluates `js` in the page and resolves to its result serialized as JSON, e.g. `"\"Home\""`
+  /// for `document.title`; parse it with `JSON.parse`. Rejects if there is no result within
+  /// `timeoutMs`, 5000 by default. The engine answers on the event loop thread, so a blocking
+  /// variant would deadlock; await the promise instead. Fails if the webview has not been created
+  /// yet.
+  #[napi]
+  pub fn evaluate_script_for_result(
+    &self,
+    js: String,
+    timeout_ms: Option<u32>,
+  ) -> Result<AsyncTask<ScriptValue>> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    self.evaluate_with_callback(&js, sender)?;
+    Ok(AsyncTask::new(ScriptValue {
+      receiver,
+      timeout: std::time::Duration::from_millis(timeout_ms.unwrap_or(5000).into()),
+      decode_string: false,
+      warn_bytes: None,
+    }))
+  }
+
+  /// Resolves to the text currently selected in the page, or an empty string if there is no
+  /// selection. Rejects if the text is not returned within `timeoutMs`, 5000 by default. Fails
+  /// if the webview has not been created yet.
+  #[napi]
+  pub fn get_selected_text(&self, timeout_ms: Option<u32>) -> Result<AsyncTask<ScriptValue>> {
+    self.evaluate_string("String(window.getSelection() || '')", None, timeout_ms)
+  }
+
+  /// Tears down the webview and builds it again with its original options, loading the page it
+  /// was showing. Use it to recover from a hung or crashed page. All in-page state, such as the
+  /// DOM, scripts and unsaved form input, is lost; handlers registered on this object are kept.
+  #[napi]
+  pub fn restart(&self) -> Result<()> {
+    let context = self
+      .build_context
+      .lock()
+      .unwrap()
+      .clone()
+      .ok_or_else(|| crate::wry::enums::Error::Uninitialized.to_js_error())?;
+    let state = context.state.upgrade().ok_or_else(|| {
+      napi::Error::new(
+        napi::Status::GenericFailure,
+        "Window has been destroyed".to_string(),
+      )
+    })?;
+    let mut options = context.options;
+    let old = self.inner.lock().unwrap().take();
+    if let Some(url) = old
+      .and_then(|webview| webview.url().ok())
+      .filter(|url| !url.is_empty() && !url.starts_with("about:"))
+    {
+      options.url = Some(url);
+      options.html = None;
+    }
+
+    {
+      let window = self.window.lock().unwrap();
+      let window = window
+        .as_ref()
+        .ok_or_else(|| crate::wry::enums::Error::Uninitialized.to_js_error())?;
+      build_webview(
+        window,
+        options,
+        self,
+        context.profile,
+        &state,
+        &context.app_handler,
+      );
+    }
+    if self.inner.lock().unwrap().is_none() {
+      return Err(napi::Error::new(
+        napi::Status::GenericFailure,
+        "Failed to rebuild the webview".to_string(),
+      ));
+    }
+    self.apply_relative_bounds();
+    Ok(())
+  }
+
+  /// Resets the webview to a blank page so it can be reused instead of creating a new one.
+  ///
+  /// Clears the session storage of the current page, then navigates to `about:blank`, which
+  /// drops the document, its scripts and any injected CSS or gesture settings. Actions queued
+  /// before the webview was created are discarded. Cookies, local storage, IndexedDB, the HTTP
+  /// cache, initialization scripts and registered handlers are kept; call
+  /// `clearAllBrowsingData` as well to remove stored data.
+  #[napi]
+  pub fn reset(&self) -> Result<()> {
+    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
+      webview.evaluate_script("try { sessionStorage.clear(); } catch (e) {}".to_string())?;
+      webview.load_url("about:blank".to_string())
+    } else {
+      let mut pending_actions = self.pending_actions.lock().unwrap();
+      pending_actions.clear();
+      pending_actions.push(PendingWebviewAction::LoadUrl("about:blank".to_string()));
+      Ok(())
+    }
+  }
+
+  #[napi]
+  pub fn load_html(&self, html: String) -> Result<()> {
+    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
+      webview.load_html(html)
+    } else {
+      // Queue the action to be applied when the webview is initialized
+      self
+        .pending_actions
+        .lock()
+        .unwrap()
+        .push(PendingWebviewAction::LoadHtml(html));
+      Ok(())
+    }
+  }
+
+  /// Loads `html` with relative URLs resolved against `base_url`, by inserting a `<base>` element
+  /// into its head. Pair with `WebviewOptions.assetRoot` to reference local files.
+  #[napi]
+  pub fn load_html_with_base_url(&self, html: String, base_url: String) -> Result<()> {
+    self.load_html(with_base_url(&html, &base_url))
+  }
+
+  #[napi]
+  pub fn evaluate_script(&self, js: String) -> Result<()> {
+    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
+      webview.evaluate_script(js)
+    } else {
+      // Queue the action to be applied when the webview is initialized
+      self
+        .pending_actions
+        .lock()
+        .unwrap()
+        .push(PendingWebviewAction::EvaluateScript(js));
+      Ok(())
+    }
+  }
+
+  #[napi]
+  pub fn open_devtools(&self) {
+    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
+      let _ = webview.open_devtools();
+    } else {
+      // Queue the action to be applied when the webview is initialized
+      self
+        .pending_actions
+        .lock()
+        .unwrap()
+        .push(PendingWebviewAction::OpenDevtools);
+    }
+  }
+
+  #[napi]
+  pub fn close_devtools(&self) {
+    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
+      let _ = webview.close_devtools();
+    } else {
+      // Queue the action to be applied when the webview is initialized
+      self
+        .pending_actions
+        .lock()
+        .unwrap()
+        .push(PendingWebviewAction::CloseDevtools);
+    }
+  }
+
+  /// Lists the cookies of the webview's profile, or only those sent to `url` when given. Fails if
+  /// the webview has not been created yet.
+  #[napi]
+  pub fn get_cookies(&self, url: Option<String>) -> Result<Vec<CookieData>> {
+    let cookies = self.with_wry_webview(|webview| match &url {
+      Some(url) => webview.cookies_for_url(url),
+      None => webview.cookies(),
+    })?;
+    cookies
+      .map(|cookies| cookies.iter().map(cookie_data).collect())
+      .map_err(|e| {
+        napi::Error::new(
+          napi::Status::GenericFailure,
+          format!("Failed to read cookies: {}", e),
+        )
+      })
+  }
+
+  /// Stores a cookie in the webview's profile, replacing one with the same name, domain and path.
+  /// Cookies with an `expires` time persist across launches when the profile does. Fails if the
+  /// webview has not been created yet.
+  #[napi]
+  pub fn set_cookie(&self, cookie: CookieData) -> Result<()> {
+    let cookie = wry_cookie(cookie)?;
+    self
+      .with_wry_webview(|webview| webview.set_cookie(&cookie))?
+      .map_err(|e| {
+        napi::Error::new(
+          napi::Status::GenericFailure,
+          format!("Failed to set cookie: {}", e),
+        )
+      })
+  }
+
+  /// Removes the cookie matching the name, domain and path of `cookie`. Fails if the webview has
+  /// not been created yet.
+  #[napi]
+  pub fn delete_cookie(&self, cookie: CookieData) -> Result<()> {
+    let cookie = wry_cookie(cookie)?;
+    self
+      .with_wry_webview(|webview| webview.delete_cookie(&cookie))?
+      .map_err(|e| {
+        napi::Error::new(
+          napi::Status::GenericFailure,
+          format!("Failed to delete cookie: {}", e),
+        )
+      })
+  }
+
+  /// Clears all browsing data of the webview's profile, including cookies, storage and the cache.
+  /// Fails if the webview has not been created yet.
+  #[napi]
+  pub fn clear_all_browsing_data(&self) -> Result<()> {
+    match self.inner.lock().unwrap().as_ref() {
+      Some(webview) => webview.clear_all_browsing_data(),
+      None => Err(crate::wry::enums::Error::Uninitialized.to_js_error()),
+    }
+  }
+
+  #[napi]
+  pub fn is_devtools_open(&self) -> bool {
+    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
+      webview.is_devtools_open().unwrap_or(false)
+    } else {
+      // Check if we have a pending OpenDevtools action
+      let pending = self.pending_actions.lock().unwrap();
+      pending
+        .iter()
+        .any(|action| matches!(action, PendingWebviewAction::OpenDevtools))
+    }
+  }
+
+  #[napi]
+  pub fn reload(&self) {
+    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
+      let _ = webview.reload();
+    } else {
+      // Queue the action to be applied when the webview is initialized
+      self
+        .pending_actions
+        .lock()
+        .unwrap()
+        .push(PendingWebviewAction::Reload);
+    }
+  }
+
+  /// Navigates back in the session history, like `history.back()`.
+  #[napi]
+  pub fn go_back(&self) -> Result<()> {
+    self.evaluate_script("history.back()".to_string())
+  }
+
+  /// Navigates forward in the session history, like `history.forward()`.
+  #[napi]
+  pub fn go_forward(&self) -> Result<()> {
+    self.evaluate_script("history.forward()".to_string())
+  }
+
+  /// URL of the current page, including changes made with `history.pushState`. `null` before the
+  /// webview is built; the URL of the last page load event if the engine cannot report it.
+  #[napi]
+  pub fn current_url(&self) -> Option<String> {
+    let live = self
+      .inner
+      .lock()
+      .unwrap()
+      .as_ref()
+      .and_then(|webview| webview.url().ok());
+    live.or_else(|| self.page.lock().unwrap().url.clone())
+  }
+
+  /// Title of the current document as last reported by the engine.
+  #[napi]
+  pub fn current_title(&self) -> Option<String> {
+    self.page.lock().unwrap().title.clone()
+  }
+
+  /// Moves keyboard focus into the webview, e.g. when it hosts a dialog that was just opened.
+  /// Falls back to `window.focus()` in the page if the engine refuses. Fails if the webview has
+  /// not been created yet or the fallback fails too.
+  #[napi]
+  pub fn focus(&self) -> Result<()> {
+    let Err(engine_error) = self.with_wry_webview(|webview| webview.focus())? else {
+      return Ok(());
+    };
+    self
+      .evaluate_script("window.focus()".to_string())
+      .map_err(|e| {
+        napi::Error::new(
+          napi::Status::GenericFailure,
+          format!(
+            "Failed to focus the webview: {}; {}",
+            engine_error, e.reason
+          ),
+        )
+      })
+  }
+
+  /// Whether the page has keyboard focus, as last reported by its `focus` and `blur` events.
+  #[napi]
+  pub fn is_focused(&self) -> bool {
+    self.page.lock().unwrap().focused
+  }
+
+  /// Whether a page load has started and not finished or failed yet.
+  #[napi]
+  pub fn is_loading(&self) -> bool {
+    // WebKit on macOS raises no page load event when a navigation fails, so it is asked directly.
+    #[cfg(target_os = "macos")]
+    if let Ok(loading) = self.with_wry_webview(|webview| {
+      use wry::WebViewExtMacOS;
+      let webview = webview.webview();
+      unsafe { objc2::msg_send![&*webview, isLoading] }
+    }) {
+      return loading;
+    }
+    self.page.lock().unwrap().loading
+  }
+
+  /// Whether there is a previous history entry, as reported by the page asynchronously after each
+  /// navigation. `null` until the page has reported it, and on engines without the Navigation
+  /// API, e.g. WebKitGTK and older WebKit on macOS, where the position is not observable.
+  #[napi]
+  pub fn can_go_back(&self) -> Option<bool> {
+    self.history.lock().unwrap().can_go_back
+  }
+
+  /// Whether there is a next history entry. `null` when unknown, like `canGoBack`.
+  #[napi]
+  pub fn can_go_forward(&self) -> Option<bool> {
+    self.history.lock().unwrap().can_go_forward
+  }
+
+  #[napi]
+  pub fn print(&self) {
+    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
+      let _ = webview.print();
+    } else {
+      // Queue the action to be applied when the webview is initialized
+      self
+        .pending_actions
+        .lock()
+        .unwrap()
+        .push(PendingWebviewAction::Print);
+    }
+  }
+
+  /// Moves and resizes the webview, in logical pixels relative to the window's client area.
+  /// Replaces any bounds set with `setBoundsRelative`, so the webview keeps this position and size
+  /// when the window is resized.
+  #[napi]
+  pub fn set_bounds(&self, x: f64, y: f64, width: f64, height: f64) -> Result<()> {
+    if ![x, y, width, height].iter().all(|value| value.is_finite()) || width < 0.0 || height < 0.0 {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "Bounds must be finite, with a non-negative width and height".to_string(),
+      ));
+    }
+    *self.relative_bounds.lock().unwrap() = None;
+    let bounds = crate::wry::structs::Rect {
+      x: x.round() as i32,
+      y: y.round() as i32,
+      width: width.round() as u32,
+      height: height.round() as u32,
+    };
+    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
+      webview.set_bounds(bounds)
+    } else {
+      self
+        .pending_actions
+        .lock()
+        .unwrap()
+        .push(PendingWebviewAction::SetBounds(bounds));
+      Ok(())
+    }
+  }
+
+  /// The position and size of the webview, in logical pixels relative to the window's client
+  /// area.
+  #[napi]
+  pub fn bounds(&self) -> Result<crate::wry::structs::Rect> {
+    let scale_factor = self
+      .window
+      .lock()
+      .unwrap()
+      .as_ref()
+      .and_then(|window| window.inner.as_ref())
+      .map(|window| window.lock().unwrap().scale_factor())
+      .ok_or_else(|| crate::wry::enums::Error::Uninitialized.to_js_error())?;
+    let inner = self.inner.lock().unwrap();
+    let webview = inner
+      .as_ref()
+      .and_then(|webview| webview.inner.as_ref())
+      .ok_or_else(|| crate::wry::enums::Error::Uninitialized.to_js_error())?;
+    let bounds = webview.lock().unwrap().bounds().map_err(|e| {
+      napi::Error::new(
+        napi::Status::GenericFailure,
+        format!("Failed to get the webview bounds: {}", e),
+      )
+    })?;
+    let position = bounds.position.to_logical::<f64>(scale_factor);
+    let size = bounds.size.to_logical::<f64>(scale_factor);
+    Ok(crate::wry::structs::Rect {
+      x: position.x.round() as i32,
+      y: position.y.round() as i32,
+      width: size.width.round() as u32,
+      height: size.height.round() as u32,
+    })
+  }
+
+  /// Captures the visible part of the webview as PNG bytes, e.g. for visual regression tests.
+  /// The engine renders the snapshot itself (`CapturePreview` on WebView2, `takeSnapshot` on
+  /// WKWebView and `webkit_web_view_get_snapshot` on WebKitGTK), so windows covering the webview
+  /// are not included and no screen recording permission is needed. The engine answers on the
+  /// event loop thread, so await the promise instead of blocking. Rejects if there is no snapshot
+  /// within `timeoutMs`, 5000 by default. Fails if the webview has not been created yet.
+  #[napi]
+  pub fn capture_page(
+    &self,
+    timeout_ms: Option<u32>,
+  ) -> Result<AsyncTask<crate::capture::PageSnapshot>> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    self.with_wry_webview(|webview| crate::capture::snapshot_webview(webview, sender))??;
+    Ok(AsyncTask::new(crate::capture::PageSnapshot::new(
+      receiver,
+      std::time::Duration::from_millis(timeout_ms.unwrap_or(5000).into()),
+    )))
+  }
+
+  /// Sets the webview bounds as fractions (0-1) of the window's inner size.
+  /// The bounds are recomputed whenever the window is resized.
+  #[napi]
+  pub fn set_bounds_relative(&self, x: f64, y: f64, width: f64, height: f64) {
+    *self.relative_bounds.lock().unwrap() = Some(RelativeBounds {
+      x: x.clamp(0.0, 1.0),
+      y: y.clamp(0.0, 1.0),
+      width: width.clamp(0.0, 1.0),
+      height: height.clamp(0.0, 1.0),
+    });
+    self.apply_relative_bounds();
+  }
+
+  /// Sets a handler called with `true` when web content enters fullscreen (HTML5 Fullscreen API)
+  /// and `false` when it leaves it.
+  #[napi]
+  pub fn on_fullscreen_request(&self, handler: Option<ThreadsafeFunction<bool>>) {
+    *self.fullscreen_handler.lock().unwrap() = handler;
+  }
+
+  /// Sets a handler notified about download progress, at most every 250 ms per download and
+  /// once more when a download has finished successfully.
+  ///
+  /// Progress comes from the engine's download events on Windows and Linux, where `totalBytes`
+  /// is set when the server sent the size. WKWebView does not report progress, so on macOS it is
+  /// approximated from the size of the destination file and `totalBytes` is only set in the
+  /// final notification; there, downloads are only tracked by webviews built while a handler is
+  /// set, so set it before the window is created, or call `restart` afterwards.
+  #[napi]
+  pub fn on_download_progress(
+    &self,
+    handler: Option<ThreadsafeFunction<DownloadProgress>>,
+  ) -> Result<()> {
+    *self.download_progress_handler.lock().unwrap() = handler;
+    Ok(())
+  }
+
+  /// Sets a handler deciding whether the webview may navigate to a URL. Returning `false` cancels
+  /// the navigation, e.g. to open external links in the system browser instead. The handler runs
+  /// synchronously while the engine waits for the decision; if it throws, the navigation is
+  /// allowed.
   #[napi]
-  pub fn load_url(&self, url: String) -> Result<()> {
+  pub fn on_navigation(&self, env: Env, handler: Option<Function<String, bool>>) -> Result<()> {
+    let hook = match handler {
+      Some(handler) => Some(std::rc::Rc::new(SyncJsHandler::new(&env, handler)?)),
+      None => None,
+    };
+    *self.navigation_hook.lock().unwrap() = hook;
+    Ok(())
+  }
+
+  /// Sets a handler for files dragged over the webview, receiving the real file paths that the
+  /// page itself cannot see. Returning `true` consumes the event so the page does not receive
+  /// it; like `onNavigation`, the handler runs synchronously.
+  #[napi]
+  pub fn on_drag_drop(
+    &self,
+    env: Env,
+    handler: Option<Function<DragDropEventData, bool>>,
+  ) -> Result<()> {
+    let hook = match handler {
+      Some(handler) => Some(std::rc::Rc::new(SyncJsHandler::new(&env, handler)?)),
+      None => None,
+    };
+    *self.drag_drop_hook.lock().unwrap() = hook;
+    Ok(())
+  }
+
+  /// Sets a handler deciding camera, microphone, geolocation and notification requests from web
+  /// content, e.g. to always allow the camera for the app's own origin. Like `onNavigation` the
+  /// handler runs synchronously; returning `Default`, throwing or having no handler leaves the
+  /// request to the engine. WebView2 asks for the camera and the microphone separately, so
+  /// `CameraAndMicrophone` is only reported on Linux. Not supported on macOS, where WKWebView
+  /// requests are not routed to the handler, so setting one fails; clearing it with `null` always
+  /// succeeds.
+  #[napi]
+  pub fn on_permission_request(
+    &self,
+    env: Env,
+    handler: Option<Function<PermissionRequest, crate::wry::enums::PermissionDecision>>,
+  ) -> Result<()> {
+    if handler.is_some() && cfg!(target_os = "macos") {
+      return Err(crate::wry::enums::Error::Unsupported.to_js_error());
+    }
+    let hook = match handler {
+      Some(handler) => Some(std::rc::Rc::new(SyncJsHandler::new(&env, handler)?)),
+      None => None,
+    };
+    *self.permission_hook.lock().unwrap() = hook;
+    Ok(())
+  }
+
+  /// Sets a handler called when a page starts loading and again when it has finished loading,
+  /// with the page URL.
+  #[napi]
+  pub fn on_page_load(&self, handler: Option<ThreadsafeFunction<PageLoadEventData>>) {
+    *self.page_load_handler.lock().unwrap() = handler;
+  }
+
+  /// Sets a handler called with the new `document.title` whenever it changes, e.g. to mirror it
+  /// with `BrowserWindow.setTitle`.
+  #[napi]
+  pub fn on_title_changed(&self, handler: Option<ThreadsafeFunction<String>>) {
+    *self.title_changed_handler.lock().unwrap() = handler;
+  }
+
+  /// Sets a handler called with the absolute URL of the page's icon whenever it changes: the
+  /// first `<link rel="icon">`, else `/favicon.ico` for http and https pages, else an empty
+  /// string. Tracked by a script injected into every page, so it is not reported for pages
+  /// whose content security policy blocks it.
+  #[napi]
+  pub fn on_favicon_changed(&self, handler: Option<ThreadsafeFunction<String>>) {
+    *self.favicon_changed_handler.lock().unwrap() = handler;
+  }
+
+  /// Sets the handler serving the schemes listed in `customProtocols`. It receives the request and
+  /// returns the response status, headers and body. Requests fail with status 404 while no
+  /// handler is set, and with 500 when the handler throws or returns invalid headers.
+  #[napi]
+  pub fn on_custom_protocol(
+    &self,
+    handler: Option<ThreadsafeFunction<IpcMessage, CustomProtocolResponse>>,
+  ) {
+    *self.custom_protocol_handler.lock().unwrap() = handler;
+  }
+
+  /// Emits a `LoadTimeout` application event when a page has not finished loading within
+  /// `ms` milliseconds of starting to load. `0` disables the guard.
+  #[napi]
+  pub fn set_load_timeout(&self, ms: u32) {
+    let mut guard = self.load_guard.lock().unwrap();
+    guard.timeout_ms = ms;
+    guard.generation += 1;
+  }
+
+  /// Scrolls the document to `x`, `y` with `window.scrollTo`. Fails if the webview has not been
+  /// created yet.
+  #[napi]
+  pub fn scroll_to(&self, x: f64, y: f64, behavior: Option<ScrollBehavior>) -> Result<()> {
+    self.scroll("scrollTo", x, y, behavior)
+  }
+
+  /// Scrolls the document by `dx`, `dy` with `window.scrollBy`. Fails if the webview has not
+  /// been created yet.
+  #[napi]
+  pub fn scroll_by(&self, dx: f64, dy: f64, behavior: Option<ScrollBehavior>) -> Result<()> {
+    self.scroll("scrollBy", dx, dy, behavior)
+  }
+
+  fn scroll(&self, method: &str, x: f64, y: f64, behavior: Option<ScrollBehavior>) -> Result<()> {
+    let behavior = match behavior.unwrap_or(ScrollBehavior::Auto) {
+      ScrollBehavior::Auto => "auto",
+      ScrollBehavior::Smooth => "smooth",
+      ScrollBehavior::Instant => "instant",
+    };
+    if !x.is_finite() || !y.is_finite() {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "Scroll offsets must be finite numbers".to_string(),
+      ));
+    }
+    let inner = self.inner.lock().unwrap();
+    let Some(webview) = inner.as_ref() else {
+      return Err(crate::wry::enums::Error::Uninitialized.to_js_error());
+    };
+    webview.evaluate_script(format!(
+      "window.{}({{ left: {}, top: {}, behavior: '{}' }})",
+      method, x, y, behavior
+    ))
+  }
+
+  /// Remembers the scroll position of the current page, keyed by its URL. Positions are kept in
+  /// session storage, so they last for the lifetime of the webview.
+  #[napi]
+  pub fn save_scroll_position(&self) -> Result<()> {
+    self.evaluate_script(
+      "try { sessionStorage.setItem('__webview_scroll__:' + location.href, \
+       JSON.stringify([window.scrollX, window.scrollY])); } catch (e) {}"
+        .to_string(),
+    )
+  }
+
+  /// Scrolls the current page back to the position saved for its URL, if any.
+  #[napi]
+  pub fn restore_scroll_position(&self) -> Result<()> {
+    self.evaluate_script(
+      "try { var p = JSON.parse(sessionStorage.getItem('__webview_scroll__:' + location.href)); \
+       if (p) window.scrollTo(p[0], p[1]); } catch (e) {}"
+        .to_string(),
+    )
+  }
+
+  /// Sets the zoom level, where `1.0` is 100%. The level must be between `0.25` and `5.0` and is
+  /// kept across navigations and reloads. Applied once the page has loaded if the webview has
+  /// not been created yet.
+  #[napi]
+  pub fn zoom(&self, scale_factor: f64) -> Result<()> {
+    if !(MIN_ZOOM..=MAX_ZOOM).contains(&scale_factor) {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        format!(
+          "Zoom level must be between {} and {}, got {}",
+          MIN_ZOOM, MAX_ZOOM, scale_factor
+        ),
+      ));
+    }
+    *self.zoom.lock().unwrap() = scale_factor;
+    let inner = self.inner.lock().unwrap();
+    if let Some(webview) = inner.as_ref().and_then(|webview| webview.inner.as_ref()) {
+      webview.lock().unwrap().zoom(scale_factor).map_err(|e| {
+        napi::Error::new(
+          napi::Status::GenericFailure,
+          format!("Failed to set the zoom level: {}", e),
+        )
+      })?;
+    }
+    Ok(())
+  }
+
+  /// Returns the zoom level last set with `zoom`, `1.0` by default.
+  #[napi]
+  pub fn get_zoom(&self) -> f64 {
+    *self.zoom.lock().unwrap()
+  }
+
+  /// Enables or disables pinch-to-zoom, also for pages loaded later.
+  /// Engines only expose this at build time, so it is enforced by a script that
+  /// cancels pinch gestures.
+  #[napi]
+  pub fn set_pinch_zoom_enabled(&self, enabled: bool) -> Result<()> {
+    self.update_gestures(|flags| flags.pinch_zoom = enabled)
+  }
+
+  /// Enables or disables trackpad swipe navigation, also for pages loaded later.
+  /// Engines only expose this at build time, so it is enforced by disabling
+  /// horizontal overscroll.
+  #[napi]
+  pub fn set_swipe_navigation_enabled(&self, enabled: bool) -> Result<()> {
+    self.update_gestures(|flags| flags.swipe_navigation = enabled)
+  }
+}
+
+impl Webview {
+  /// Evaluates `js` in the page and resolves to the string it returns, warning about strings
+  /// longer than `warn_bytes`.
+  fn evaluate_string(
+    &self,
+    js: &str,
+    warn_bytes: Option<usize>,
+    timeout_ms: Option<u32>,
+  ) -> Result<AsyncTask<ScriptValue>> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    self.evaluate_with_callback(js, sender)?;
+    Ok(AsyncTask::new(ScriptValue {
+      receiver,
+      timeout: std::time::Duration::from_millis(timeout_ms.unwrap_or(5000).into()),
+      decode_string: true,
+      warn_bytes,
+    }))
+  }
+
+  /// Evaluates `js` in the page and sends the JSON-serialized result to `sender`.
+  fn evaluate_with_callback(
+    &self,
+    js: &str,
+    sender: std::sync::mpsc::Sender<String>,
+  ) -> Result<()> {
+    self
+      .with_wry_webview(|webview| {
+        webview.evaluate_script_with_callback(js, move |result| {
+          let _ = sender.send(result);
+        })
+      })?
+      .map_err(|e| {
+        napi::Error::new(
+          napi::Status::GenericFailure,
+          format!("Failed to evaluate script: {}", e),
+        )
+      })
+  }
+
+  /// Changes the gesture flags and applies them to the current document, if any.
+  fn update_gestures(&self, update: impl FnOnce(&mut GestureFlags)) -> Result<()> {
+    let flags = {
+      let mut flags = self.gestures.lock().unwrap();
+      update(&mut flags);
+      *flags
+    };
+    match self.inner.lock().unwrap().as_ref() {
+      Some(webview) => webview.evaluate_script(gesture_guard_script(flags)),
+      None => Ok(()),
+    }
+  }
+
+  /// Runs `f` with the underlying wry webview, failing if it has not been created yet.
+  fn with_wry_webview<T>(&self, f: impl FnOnce(&wry::WebView) -> T) -> Result<T> {
+    let inner = self.inner.lock().unwrap();
+    let webview = inner
+      .as_ref()
+      .and_then(|webview| webview.inner.as_ref())
+      .ok_or_else(|| crate::wry::enums::Error::Uninitialized.to_js_error())?;
+    let webview = webview.lock().unwrap();
+    Ok(f(&webview))
+  }
+
+  /// Recomputes pixel bounds from the relative bounds, if any were set.
+  pub(crate) fn apply_relative_bounds(&self) {
+    let Some(relative) = *self.relative_bounds.lock().unwrap() else {
+      return;
+    };
+    let Some((window_width, window_height)) = self
+      .window
+      .lock()
+      .unwrap()
+      .as_ref()
+      .and_then(logical_inner_size)
+    else {
+      return;
+    };
     if let Some(webview) = self.inner.lock().unwrap().as_ref() {
-      webview.load_url(url)
+      let _ = webview.set_bounds(crate::wry::structs::Rect {
+        x: (relative.x * window_width).round() as i32,
+        y: (relative.y * window_height).round() as i32,
+        width: (relative.width * window_width).round() as u32,
+        height: (relative.height * window_height).round() as u32,
+      });
+    }
+  }
+}
+
+/// Reports the growing size of a download's destination file until the download is removed
+/// from `downloads` by the completion handler.
+#[cfg(target_os = "macos")]
+fn watch_download(
+  url: String,
+  downloads: Arc<Mutex<HashMap<String, std::path::PathBuf>>>,
+  handler: Arc<Mutex<Option<ThreadsafeFunction<DownloadProgress>>>>,
+) {
+  std::thread::spawn(move || {
+    let mut last_size = None;
+    loop {
+      std::thread::sleep(crate::wry::structs::DOWNLOAD_PROGRESS_INTERVAL);
+      let Some(path) = downloads.lock().unwrap().get(&url).cloned() else {
+        break;
+      };
+      let Ok(size) = std::fs::metadata(&path).map(|m| m.len()) else {
+        continue;
+      };
+      if last_size == Some(size) {
+        continue;
+      }
+      last_size = Some(size);
+      if let Some(handler) = handler.lock().unwrap().as_ref() {
+        let _ = handler.call(
+          Ok(DownloadProgress {
+            url: url.clone(),
+            received_bytes: size as f64,
+            total_bytes: None,
+          }),
+          ThreadsafeFunctionCallMode::NonBlocking,
+        );
+      }
+    }
+  });
+}
+
+/// Covers the first page with a solid color until it has loaded and painted its first frame.
+/// Initialization scripts run on every page, so later pages are told apart by their history
+/// length. The document may not have a root element yet when the script runs.
+fn paint_placeholder_script([r, g, b, a]: [u8; 4]) -> String {
+  format!(
+    r#"(function () {{
+  if (window.top !== window || history.length > 1) return;
+  var placeholder = document.createElement('div');
+  placeholder.style.cssText = 'position:fixed;inset:0;z-index:2147483647;pointer-events:none;background:rgba({r},{g},{b},{alpha})';
+  var attach = function () {{
+    if (!document.documentElement) return false;
+    document.documentElement.appendChild(placeholder);
+    return true;
+  }};
+  if (!attach()) {{
+    var observer = new MutationObserver(function () {{
+      if (attach()) observer.disconnect();
+    }});
+    observer.observe(document, {{ childList: true }});
+  }}
+  window.addEventListener('load', function () {{
+    requestAnimationFrame(function () {{ placeholder.remove(); }});
+  }});
+}})();"#,
+    alpha = a as f64 / 255.0
+  )
+}
+
+const FULLSCREEN_MESSAGE_PREFIX: &str = "__webview_fullscreen__:";
+
+/// Reports HTML5 fullscreen changes to the native side over IPC.
+const FULLSCREEN_BRIDGE_SCRIPT: &str = r#"(function () {
+  if (window.__webview_fullscreen_bridge__) return;
+  window.__webview_fullscreen_bridge__ = true;
+  var notify = function () {
+    var active = !!(document.fullscreenElement || document.webkitFullscreenElement);
+    window.ipc.postMessage('__webview_fullscreen__:' + (active ? '1' : '0'));
+  };
+  document.addEventListener('fullscreenchange', notify);
+  document.addEventListener('webkitfullscreenchange', notify);
+})();"#;
+
+const HISTORY_MESSAGE_PREFIX: &str = "__webview_history__:";
+
+/// Prefix of the IPC messages sent by `custom_decorations_script`.
+const CHROME_MESSAGE_PREFIX: &str = "__webview_chrome__:";
+
+/// Builds a script that turns presses near the window edges into native resizes and presses on
+/// drag regions into native moves, for windows with custom decorations.
+fn custom_decorations_script(resize_border: f64) -> String {
+  format!(
+    r#"(function () {{
+  if (window.top !== window || window.__webview_chrome__) return;
+  window.__webview_chrome__ = true;
+  var border = {};
+  var cursors = {{
+    North: 'n-resize', South: 's-resize', East: 'e-resize', West: 'w-resize',
+    NorthEast: 'ne-resize', NorthWest: 'nw-resize', SouthEast: 'se-resize', SouthWest: 'sw-resize'
+  }};
+  var send = function (action) {{
+    window.ipc.postMessage('__webview_chrome__:' + action);
+  }};
+  var edge = function (e) {{
+    var vertical = e.clientY < border ? 'North' : e.clientY >= window.innerHeight - border ? 'South' : '';
+    var horizontal = e.clientX < border ? 'West' : e.clientX >= window.innerWidth - border ? 'East' : '';
+    return vertical + horizontal;
+  }};
+  var cursor = '';
+  document.addEventListener('mousemove', function (e) {{
+    var next = edge(e);
+    if (next === cursor) return;
+    cursor = next;
+    document.documentElement.style.cursor = next ? cursors[next] : '';
+  }}, true);
+  document.addEventListener('mousedown', function (e) {{
+    if (e.button !== 0) return;
+    var direction = edge(e);
+    if (direction) {{
+      e.preventDefault();
+      send('resize:' + direction);
+    }} else if (e.target.hasAttribute && e.target.hasAttribute('data-webview-drag-region')) {{
+      e.preventDefault();
+      send(e.detail === 2 ? 'maximize' : 'drag');
+    }}
+  }}, true);
+}})();"#,
+    resize_border
+  )
+}
+
+/// Performs a move, resize or maximize requested by `custom_decorations_script`.
+fn handle_chrome_action(window: &tao::window::Window, action: &str) {
+  use tao::window::ResizeDirection;
+
+  let direction = match action {
+    "drag" => {
+      let _ = window.drag_window();
+      return;
+    }
+    "maximize" => {
+      window.set_maximized(!window.is_maximized());
+      return;
+    }
+    "resize:North" => ResizeDirection::North,
+    "resize:South" => ResizeDirection::South,
+    "resize:East" => ResizeDirection::East,
+    "resize:West" => ResizeDirection::West,
+    "resize:NorthEast" => ResizeDirection::NorthEast,
+    "resize:NorthWest" => ResizeDirection::NorthWest,
+    "resize:SouthEast" => ResizeDirection::SouthEast,
+    "resize:SouthWest" => ResizeDirection::SouthWest,
+    _ => return,
+  };
+  if window.is_resizable() && !window.is_maximized() {
+    let _ = window.drag_resize_window(direction);
+  }
+}
+
+/// Prefix of the IPC messages sent by `window.__webview_invoke__`, followed by the request id and
+/// a colon.
+const REQUEST_MESSAGE_PREFIX: &str = "__webview_request__:";
+
+/// Defines `window.__webview_invoke__(message)`, which posts `message` with a request id and
+/// returns a promise resolved with the string passed to `Webview.reply`, or rejected when no
+/// `onIpcMessageWithSource` handler is registered or the handler throws.
+const REQUEST_BRIDGE_SCRIPT: &str = r#"(function () {
+  if (window.__webview_invoke__) return;
+  var pending = {};
+  var nextId = 0;
+  window.__webview_invoke__ = function (message) {
+    var id = String(++nextId);
+    return new Promise(function (resolve, reject) {
+      pending[id] = { resolve: resolve, reject: reject };
+      window.ipc.postMessage('__webview_request__:' + id + ':' + message);
+    });
+  };
+  window.__webview_reply__ = function (id, body, error) {
+    var request = pending[id];
+    delete pending[id];
+    if (!request) return;
+    if (error != null) request.reject(new Error(error));
+    else request.resolve(body);
+  };
+})();"#;
+
+/// Builds a script settling the `window.__webview_invoke__` promise of `request_id`, resolving it
+/// with `body` or rejecting it with `error`.
+fn reply_script(request_id: &str, body: Option<&str>, error: Option<&str>) -> String {
+  let encode = |value: Option<&str>| serde_json::to_string(&value).unwrap_or_default();
+  format!(
+    "window.__webview_reply__ && window.__webview_reply__({}, {}, {})",
+    encode(Some(request_id)),
+    encode(body),
+    encode(error)
+  )
+}
+
+/// Rejects the `window.__webview_invoke__` promise of `request_id` with `error`.
+fn reject_request(
+  target: &Mutex<Option<crate::wry::structs::WebView>>,
+  request_id: &str,
+  error: &str,
+) {
+  // The webview is already locked when the message is raised synchronously by a call on it.
+  if let Ok(inner) = target.try_lock() {
+    if let Some(webview) = inner.as_ref() {
+      let _ = webview.evaluate_script(reply_script(request_id, None, Some(error)));
+    }
+  }
+}
+
+/// Prefix of the IPC messages answering `Webview.isResponsive`.
+const PONG_MESSAGE_PREFIX: &str = "__webview_pong__:";
+
+/// Reports whether the top-level document can navigate back and forward over IPC, as `1`, `0` or
+/// `?` when the engine has no Navigation API. `history.length` counts the entries after the
+/// current one too, so it cannot tell the position.
+const HISTORY_BRIDGE_SCRIPT: &str = r#"(function () {
+  if (window.top !== window || window.__webview_history_bridge__) return;
+  window.__webview_history_bridge__ = true;
+  var report = function () {
+    var nav = window.navigation;
+    var flag = function (name) {
+      return nav && name in nav ? (nav[name] ? '1' : '0') : '?';
+    };
+    window.ipc.postMessage('__webview_history__:' + flag('canGoBack') + flag('canGoForward'));
+  };
+  ['pushState', 'replaceState'].forEach(function (name) {
+    var original = history[name];
+    history[name] = function () {
+      var result = original.apply(this, arguments);
+      report();
+      return result;
+    };
+  });
+  window.addEventListener('popstate', report);
+  window.addEventListener('pageshow', report);
+  if (window.navigation) window.navigation.addEventListener('currententrychange', report);
+})();"#;
+
+const FOCUS_MESSAGE_PREFIX: &str = "__webview_focus__:";
+
+/// Reports whether the top-level document has keyboard focus over IPC.
+const FOCUS_BRIDGE_SCRIPT: &str = r#"(function () {
+  if (window.top !== window || window.__webview_focus_bridge__) return;
+  window.__webview_focus_bridge__ = true;
+  var report = function (focused) {
+    window.ipc.postMessage('__webview_focus__:' + (focused ? '1' : '0'));
+  };
+  window.addEventListener('focus', function () { report(true); });
+  window.addEventListener('blur', function () { report(false); });
+  window.addEventListener('pageshow', function () { report(document.hasFocus()); });
+})();"#;
+
+const FAVICON_MESSAGE_PREFIX: &str = "__webview_favicon__:";
+
+/// Reports the icon of the top-level document over IPC when the page starts and whenever its
+/// icon links change.
+const FAVICON_BRIDGE_SCRIPT: &str = r#"(function () {
+  if (window.top !== window || window.__webview_favicon_bridge__) return;
+  window.__webview_favicon_bridge__ = true;
+  var last = null;
+  var report = function () {
+    var link = document.querySelector('link[rel~="icon" i][href]');
+    var href = link ? link.href
+      : /^https?:$/.test(location.protocol) ? location.origin + '/favicon.ico' : '';
+    if (href === last) return;
+    last = href;
+    window.ipc.postMessage('__webview_favicon__:' + href);
+  };
+  var observe = function () {
+    report();
+    new MutationObserver(report).observe(document.head || document.documentElement, {
+      childList: true, subtree: true, attributes: true, attributeFilter: ['href', 'rel']
+    });
+  };
+  if (document.readyState === 'loading') document.addEventListener('DOMContentLoaded', observe);
+  else observe();
+})();"#;
+
+/// Matches `text` against a glob pattern where `*` matches any sequence and `?` any character.
+fn glob_match(pattern: &str, text: &str) -> bool {
+  let pattern: Vec<char> = pattern.chars().collect();
+  let text: Vec<char> = text.chars().collect();
+  let (mut p, mut t) = (0, 0);
+  let mut backtrack: Option<(usize, usize)> = None;
+  while t < text.len() {
+    if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+      p += 1;
+      t += 1;
+    } else if p < pattern.len() && pattern[p] == '*' {
+      backtrack = Some((p, t));
+      p += 1;
+    } else if let Some((star, matched)) = backtrack {
+      p = star + 1;
+      t = matched + 1;
+      backtrack = Some((star, t));
     } else {
-      // Queue the action to be applied when the webview is initialized
-      self
-        .pending_actions
-        .lock()
-        .unwrap()
-        .push(PendingWebviewAction::LoadUrl(url));
-      Ok(())
+      return false;
+    }
+  }
+  pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Rejects `fetch` and `XMLHttpRequest` calls whose absolute URL matches one of the patterns.
+fn request_blocker_script(patterns: &[String]) -> String {
+  format!(
+    r#"(function () {{
+  var patterns = {patterns}.map(function (p) {{
+    return new RegExp('^' + p.replace(/[.+^${{}}()|[\]\\]/g, '\\$&').replace(/\*/g, '.*').replace(/\?/g, '.') + '$');
+  }});
+  var blocked = function (url) {{
+    var absolute = new URL(url, location.href).href;
+    return patterns.some(function (re) {{ return re.test(absolute); }});
+  }};
+  var fetch = window.fetch;
+  window.fetch = function (input, init) {{
+    var url = input instanceof Request ? input.url : String(input);
+    if (blocked(url)) return Promise.reject(new TypeError('Blocked by webview: ' + url));
+    return fetch.call(this, input, init);
+  }};
+  var open = XMLHttpRequest.prototype.open;
+  XMLHttpRequest.prototype.open = function (method, url) {{
+    if (blocked(String(url))) throw new DOMException('Blocked by webview: ' + url, 'NetworkError');
+    return open.apply(this, arguments);
+  }};
+}})();"#,
+    patterns = serde_json::to_string(patterns).unwrap_or_else(|_| "[]".to_string())
+  )
+}
+
+/// Builds a script that installs the gesture guard once and updates its flags.
+fn gesture_guard_script(flags: GestureFlags) -> String {
+  format!(
+    r#"(function () {{
+  var g = window.__webview_gestures__;
+  if (!g) {{
+    g = window.__webview_gestures__ = {{ pinchZoom: true, swipeNavigation: true }};
+    var block = function (e) {{ if (!g.pinchZoom) e.preventDefault(); }};
+    var opts = {{ passive: false }};
+    window.addEventListener('wheel', function (e) {{ if (e.ctrlKey) block(e); }}, opts);
+    window.addEventListener('gesturestart', block, opts);
+    window.addEventListener('gesturechange', block, opts);
+    window.addEventListener('touchmove', function (e) {{ if (e.touches.length > 1) block(e); }}, opts);
+  }}
+  g.pinchZoom = {pinch_zoom};
+  g.swipeNavigation = {swipe_navigation};
+  var overscroll = function () {{
+    document.documentElement.style.overscrollBehaviorX = g.swipeNavigation ? '' : 'none';
+  }};
+  if (document.documentElement) overscroll();
+  else document.addEventListener('DOMContentLoaded', overscroll);
+}})();"#,
+    pinch_zoom = flags.pinch_zoom,
+    swipe_navigation = flags.swipe_navigation
+  )
+}
+
+fn logical_inner_size(window: &crate::tao::structs::Window) -> Option<(f64, f64)> {
+  let inner = window.inner.as_ref()?.lock().unwrap();
+  let size = inner.inner_size().to_logical::<f64>(inner.scale_factor());
+  Some((size.width, size.height))
+}
+
+#[cfg(target_os = "macos")]
+fn show_macos_about_panel(info: &AboutPanelInfo) {
+  use objc2::runtime::AnyObject;
+  use objc2::{AllocAnyThread, MainThreadMarker};
+  use objc2_app_kit::{
+    NSAboutPanelOptionApplicationIcon, NSAboutPanelOptionApplicationName,
+    NSAboutPanelOptionApplicationVersion, NSAboutPanelOptionCredits, NSApplication, NSImage,
+  };
+  use objc2_foundation::{NSAttributedString, NSData, NSDictionary, NSString};
+
+  let Some(mtm) = MainThreadMarker::new() else {
+    return;
+  };
+
+  let name = info.application_name.as_deref().map(NSString::from_str);
+  let version = info.version.as_deref().map(NSString::from_str);
+  let credits = info
+    .credits
+    .as_deref()
+    .map(|credits| NSAttributedString::from_nsstring(&NSString::from_str(credits)));
+  let copyright = info.copyright.as_deref().map(NSString::from_str);
+  let copyright_key = NSString::from_str("Copyright");
+  let icon = info
+    .icon
+    .as_deref()
+    .and_then(|bytes| NSImage::initWithData(NSImage::alloc(), &NSData::with_bytes(bytes)));
+
+  let mut keys: Vec<&NSString> = Vec::new();
+  let mut values: Vec<&AnyObject> = Vec::new();
+  unsafe {
+    if let Some(name) = &name {
+      keys.push(NSAboutPanelOptionApplicationName);
+      values.push(name);
+    }
+    if let Some(version) = &version {
+      keys.push(NSAboutPanelOptionApplicationVersion);
+      values.push(version);
+    }
+    if let Some(credits) = &credits {
+      keys.push(NSAboutPanelOptionCredits);
+      values.push(credits);
+    }
+    if let Some(icon) = &icon {
+      keys.push(NSAboutPanelOptionApplicationIcon);
+      values.push(icon);
+    }
+  }
+  if let Some(copyright) = &copyright {
+    keys.push(&copyright_key);
+    values.push(copyright);
+  }
+
+  let options = NSDictionary::from_slices(&keys, &values);
+  unsafe {
+    NSApplication::sharedApplication(mtm).orderFrontStandardAboutPanelWithOptions(&options)
+  };
+}
+
+#[cfg(target_os = "macos")]
+impl BrowserWindow {
+  fn with_ns_window(&self, f: impl FnOnce(&objc2_app_kit::NSWindow)) {
+    use tao::platform::macos::WindowExtMacOS;
+
+    let handle = self.inner.lock().unwrap();
+    if let Some(inner) = handle.as_ref().and_then(|win| win.inner.as_ref()) {
+      let window = inner.lock().unwrap();
+      f(unsafe { &*(window.ns_window() as *const objc2_app_kit::NSWindow) });
+    }
+  }
+}
+
+#[cfg(target_os = "windows")]
+fn apply_backdrop(window: &tao::window::Window, effect: BackdropEffect) -> Result<()> {
+  use tao::platform::windows::WindowExtWindows;
+
+  #[link(name = "dwmapi")]
+  extern "system" {
+    fn DwmSetWindowAttribute(
+      hwnd: isize,
+      attribute: u32,
+      value: *const std::ffi::c_void,
+      size: u32,
+    ) -> i32;
+  }
+
+  const DWMWA_SYSTEMBACKDROP_TYPE: u32 = 38;
+  let backdrop: u32 = match effect {
+    BackdropEffect::None => 1,
+    BackdropEffect::Mica => 2,
+    BackdropEffect::Blur | BackdropEffect::Acrylic => 3,
+    BackdropEffect::Tabbed => 4,
+  };
+  let result = unsafe {
+    DwmSetWindowAttribute(
+      window.hwnd(),
+      DWMWA_SYSTEMBACKDROP_TYPE,
+      &backdrop as *const u32 as *const std::ffi::c_void,
+      std::mem::size_of::<u32>() as u32,
+    )
+  };
+  if result < 0 {
+    return Err(napi::Error::new(
+      napi::Status::GenericFailure,
+      format!("Failed to set window backdrop (HRESULT {:#x})", result),
+    ));
+  }
+  Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn apply_backdrop(window: &tao::window::Window, effect: BackdropEffect) -> Result<()> {
+  use objc2::{ClassType, MainThreadMarker};
+  use objc2_app_kit::{
+    NSAutoresizingMaskOptions, NSView, NSVisualEffectBlendingMode, NSVisualEffectMaterial,
+    NSVisualEffectState, NSVisualEffectView, NSWindowOrderingMode,
+  };
+  use objc2_foundation::NSObjectProtocol;
+  use tao::platform::macos::WindowExtMacOS;
+
+  let mtm = MainThreadMarker::new().ok_or_else(|| {
+    napi::Error::new(
+      napi::Status::GenericFailure,
+      "Backdrop effects must be set on the main thread".to_string(),
+    )
+  })?;
+  let content_view = unsafe { &*(window.ns_view() as *const NSView) };
+
+  for subview in content_view.subviews().to_vec() {
+    if subview.isKindOfClass(NSVisualEffectView::class()) {
+      subview.removeFromSuperview();
+    }
+  }
+
+  let material = match effect {
+    BackdropEffect::None => return Ok(()),
+    BackdropEffect::Blur => NSVisualEffectMaterial::UnderWindowBackground,
+    BackdropEffect::Acrylic => NSVisualEffectMaterial::HUDWindow,
+    BackdropEffect::Mica => NSVisualEffectMaterial::Sidebar,
+    BackdropEffect::Tabbed => NSVisualEffectMaterial::Titlebar,
+  };
+  let effect_view = NSVisualEffectView::initWithFrame(mtm.alloc(), content_view.bounds());
+  effect_view.setMaterial(material);
+  effect_view.setBlendingMode(NSVisualEffectBlendingMode::BehindWindow);
+  effect_view.setState(NSVisualEffectState::Active);
+  effect_view.setAutoresizingMask(
+    NSAutoresizingMaskOptions::ViewWidthSizable | NSAutoresizingMaskOptions::ViewHeightSizable,
+  );
+  content_view.addSubview_positioned_relativeTo(&effect_view, NSWindowOrderingMode::Below, None);
+  Ok(())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn apply_backdrop(_window: &tao::window::Window, effect: BackdropEffect) -> Result<()> {
+  match effect {
+    BackdropEffect::None => Ok(()),
+    _ => Err(napi::Error::new(
+      napi::Status::GenericFailure,
+      "Backdrop effects are not supported on this platform".to_string(),
+    )),
+  }
+}
+
+#[cfg(target_os = "windows")]
+fn set_rounded_corners(window: &tao::window::Window, rounded: bool) {
+  use tao::platform::windows::WindowExtWindows;
+
+  #[link(name = "dwmapi")]
+  extern "system" {
+    fn DwmSetWindowAttribute(
+      hwnd: isize,
+      attribute: u32,
+      value: *const std::ffi::c_void,
+      size: u32,
+    ) -> i32;
+  }
+
+  const DWMWA_WINDOW_CORNER_PREFERENCE: u32 = 33;
+  const DWMWCP_DONOTROUND: u32 = 1;
+  const DWMWCP_ROUND: u32 = 2;
+  let preference = if rounded {
+    DWMWCP_ROUND
+  } else {
+    DWMWCP_DONOTROUND
+  };
+  // Fails before Windows 11, where corners are always square.
+  unsafe {
+    DwmSetWindowAttribute(
+      window.hwnd(),
+      DWMWA_WINDOW_CORNER_PREFERENCE,
+      &preference as *const u32 as *const std::ffi::c_void,
+      std::mem::size_of::<u32>() as u32,
+    );
+  }
+}
+
+#[cfg(target_os = "windows")]
+fn apply_window_opacity(window: &tao::window::Window, opacity: f64) {
+  use tao::platform::windows::WindowExtWindows;
+
+  #[link(name = "user32")]
+  extern "system" {
+    fn GetWindowLongPtrW(hwnd: isize, index: i32) -> isize;
+    fn SetWindowLongPtrW(hwnd: isize, index: i32, value: isize) -> isize;
+    fn SetLayeredWindowAttributes(hwnd: isize, key: u32, alpha: u8, flags: u32) -> i32;
+  }
+
+  const GWL_EXSTYLE: i32 = -20;
+  const WS_EX_LAYERED: isize = 0x0008_0000;
+  const LWA_ALPHA: u32 = 0x2;
+
+  let hwnd = window.hwnd();
+  unsafe {
+    let style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+    if style & WS_EX_LAYERED == 0 {
+      SetWindowLongPtrW(hwnd, GWL_EXSTYLE, style | WS_EX_LAYERED);
+    }
+    SetLayeredWindowAttributes(hwnd, 0, (opacity * 255.0).round() as u8, LWA_ALPHA);
+  }
+}
+
+/// Whether the key window shares a native tab group with other windows.
+#[cfg(target_os = "macos")]
+fn key_window_is_tabbed() -> bool {
+  use objc2::MainThreadMarker;
+  use objc2_app_kit::NSApplication;
+
+  MainThreadMarker::new()
+    .and_then(|mtm| NSApplication::sharedApplication(mtm).keyWindow())
+    .and_then(|window| window.tabbedWindows())
+    .is_some_and(|windows| windows.count() > 1)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn key_window_is_tabbed() -> bool {
+  false
+}
+
+#[cfg(target_os = "macos")]
+fn apply_window_opacity(window: &tao::window::Window, opacity: f64) {
+  use tao::platform::macos::WindowExtMacOS;
+
+  let ns_window = unsafe { &*(window.ns_window() as *const objc2_app_kit::NSWindow) };
+  ns_window.setAlphaValue(opacity);
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn apply_window_opacity(window: &tao::window::Window, opacity: f64) {
+  use std::ffi::c_void;
+  use tao::platform::unix::WindowExtUnix;
+
+  extern "C" {
+    fn gtk_widget_set_opacity(widget: *mut c_void, opacity: f64);
+  }
+
+  let window_ptr = window.gtk_window();
+  unsafe {
+    gtk_widget_set_opacity(*(window_ptr as *const _ as *const *mut c_void), opacity);
+  }
+}
+
+#[cfg(not(any(
+  target_os = "windows",
+  target_os = "macos",
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+)))]
+fn apply_window_opacity(_window: &tao::window::Window, _opacity: f64) {}
+
+#[cfg(target_os = "windows")]
+fn restack_window(window: &tao::window::Window, to_front: bool) {
+  use tao::platform::windows::WindowExtWindows;
+
+  #[link(name = "user32")]
+  extern "system" {
+    fn SetWindowPos(
+      hwnd: isize,
+      insert_after: isize,
+      x: i32,
+      y: i32,
+      cx: i32,
+      cy: i32,
+      flags: u32,
+    ) -> i32;
+  }
+
+  const HWND_TOP: isize = 0;
+  const HWND_BOTTOM: isize = 1;
+  const SWP_NOSIZE: u32 = 0x0001;
+  const SWP_NOMOVE: u32 = 0x0002;
+  const SWP_NOACTIVATE: u32 = 0x0010;
+
+  let insert_after = if to_front { HWND_TOP } else { HWND_BOTTOM };
+  unsafe {
+    SetWindowPos(
+      window.hwnd(),
+      insert_after,
+      0,
+      0,
+      0,
+      0,
+      SWP_NOSIZE | SWP_NOMOVE | SWP_NOACTIVATE,
+    );
+  }
+}
+
+#[cfg(target_os = "macos")]
+fn restack_window(window: &tao::window::Window, to_front: bool) {
+  use tao::platform::macos::WindowExtMacOS;
+
+  let ns_window = unsafe { &*(window.ns_window() as *const objc2_app_kit::NSWindow) };
+  if to_front {
+    ns_window.orderFront(None);
+  } else {
+    ns_window.orderBack(None);
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn restack_window(window: &tao::window::Window, to_front: bool) {
+  use std::ffi::c_void;
+  use tao::platform::unix::WindowExtUnix;
+
+  extern "C" {
+    fn gtk_widget_get_window(widget: *mut c_void) -> *mut c_void;
+    fn gdk_window_raise(window: *mut c_void);
+    fn gdk_window_lower(window: *mut c_void);
+  }
+
+  let window_ptr = window.gtk_window();
+  let window_ptr_raw = unsafe { *(window_ptr as *const _ as *const *mut c_void) };
+  unsafe {
+    let gdk_window = gtk_widget_get_window(window_ptr_raw);
+    if gdk_window.is_null() {
+      return;
+    }
+    if to_front {
+      gdk_window_raise(gdk_window);
+    } else {
+      gdk_window_lower(gdk_window);
     }
   }
+}
+
+#[cfg(not(any(
+  target_os = "windows",
+  target_os = "macos",
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+)))]
+fn restack_window(_window: &tao::window::Window, _to_front: bool) {}
+
+/// Checks that the window's screen is composited and offers an RGBA visual.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn supports_alpha(window: &tao::window::Window) -> bool {
+  use std::ffi::c_void;
+  use tao::platform::unix::WindowExtUnix;
+
+  extern "C" {
+    fn gtk_widget_get_screen(widget: *mut c_void) -> *mut c_void;
+    fn gdk_screen_is_composited(screen: *mut c_void) -> i32;
+    fn gdk_screen_get_rgba_visual(screen: *mut c_void) -> *mut c_void;
+  }
+
+  let window_ptr = window.gtk_window();
+  let window_ptr_raw = unsafe { *(window_ptr as *const _ as *const *mut c_void) };
+  unsafe {
+    let screen = gtk_widget_get_screen(window_ptr_raw);
+    !screen.is_null()
+      && gdk_screen_is_composited(screen) != 0
+      && !gdk_screen_get_rgba_visual(screen).is_null()
+  }
+}
 
-  #[napi]
-  pub fn load_html(&self, html: String) -> Result<()> {
-    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
-      webview.load_html(html)
-    } else {
-      // Queue the action to be applied when the webview is initialized
-      self
-        .pending_actions
-        .lock()
-        .unwrap()
-        .push(PendingWebviewAction::LoadHtml(html));
-      Ok(())
-    }
+#[cfg(not(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+)))]
+fn supports_alpha(_window: &tao::window::Window) -> bool {
+  true
+}
+
+#[cfg(target_os = "windows")]
+fn apply_window_shape(
+  window: &tao::window::Window,
+  regions: &[crate::tao::structs::Rectangle],
+) -> Result<()> {
+  use tao::platform::windows::WindowExtWindows;
+
+  #[link(name = "gdi32")]
+  extern "system" {
+    fn CreateRectRgn(left: i32, top: i32, right: i32, bottom: i32) -> isize;
+    fn CombineRgn(dest: isize, src1: isize, src2: isize, mode: i32) -> i32;
+    fn DeleteObject(object: isize) -> i32;
   }
 
-  #[napi]
-  pub fn evaluate_script(&self, js: String) -> Result<()> {
-    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
-      webview.evaluate_script(js)
-    } else {
-      // Queue the action to be applied when the webview is initialized
-      self
-        .pending_actions
-        .lock()
-        .unwrap()
-        .push(PendingWebviewAction::EvaluateScript(js));
-      Ok(())
-    }
+  #[link(name = "user32")]
+  extern "system" {
+    fn SetWindowRgn(hwnd: isize, region: isize, redraw: i32) -> i32;
   }
 
-  #[napi]
-  pub fn open_devtools(&self) {
-    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
-      let _ = webview.open_devtools();
-    } else {
-      // Queue the action to be applied when the webview is initialized
-      self
-        .pending_actions
-        .lock()
-        .unwrap()
-        .push(PendingWebviewAction::OpenDevtools);
+  const RGN_OR: i32 = 2;
+
+  let region = if regions.is_empty() {
+    0
+  } else {
+    let scale = window.scale_factor();
+    unsafe {
+      let region = CreateRectRgn(0, 0, 0, 0);
+      for rect in regions {
+        let left = (rect.origin.x * scale).round() as i32;
+        let top = (rect.origin.y * scale).round() as i32;
+        let right = ((rect.origin.x + rect.size.width) * scale).round() as i32;
+        let bottom = ((rect.origin.y + rect.size.height) * scale).round() as i32;
+        let part = CreateRectRgn(left, top, right, bottom);
+        CombineRgn(region, region, part, RGN_OR);
+        DeleteObject(part);
+      }
+      region
+    }
+  };
+
+  // On success the system owns the region.
+  if unsafe { SetWindowRgn(window.hwnd(), region, 1) } == 0 {
+    if region != 0 {
+      unsafe { DeleteObject(region) };
     }
+    return Err(napi::Error::new(
+      napi::Status::GenericFailure,
+      "Failed to set the window shape".to_string(),
+    ));
   }
+  Ok(())
+}
 
-  #[napi]
-  pub fn close_devtools(&self) {
-    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
-      let _ = webview.close_devtools();
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn apply_window_shape(
+  window: &tao::window::Window,
+  regions: &[crate::tao::structs::Rectangle],
+) -> Result<()> {
+  use std::ffi::c_void;
+  use tao::platform::unix::WindowExtUnix;
+
+  #[repr(C)]
+  struct CairoRectangleInt {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+  }
+
+  extern "C" {
+    fn cairo_region_create() -> *mut c_void;
+    fn cairo_region_union_rectangle(region: *mut c_void, rect: *const CairoRectangleInt) -> i32;
+    fn cairo_region_destroy(region: *mut c_void);
+    fn gtk_widget_shape_combine_region(widget: *mut c_void, region: *mut c_void);
+    fn gtk_widget_input_shape_combine_region(widget: *mut c_void, region: *mut c_void);
+  }
+
+  let window_ptr = window.gtk_window();
+  let window_ptr_raw = unsafe { *(window_ptr as *const _ as *const *mut c_void) };
+
+  unsafe {
+    let region = if regions.is_empty() {
+      std::ptr::null_mut()
     } else {
-      // Queue the action to be applied when the webview is initialized
-      self
-        .pending_actions
-        .lock()
-        .unwrap()
-        .push(PendingWebviewAction::CloseDevtools);
+      let region = cairo_region_create();
+      for rect in regions {
+        let rect = CairoRectangleInt {
+          x: rect.origin.x.round() as i32,
+          y: rect.origin.y.round() as i32,
+          width: rect.size.width.round() as i32,
+          height: rect.size.height.round() as i32,
+        };
+        cairo_region_union_rectangle(region, &rect);
+      }
+      region
+    };
+    gtk_widget_shape_combine_region(window_ptr_raw, region);
+    gtk_widget_input_shape_combine_region(window_ptr_raw, region);
+    if !region.is_null() {
+      cairo_region_destroy(region);
     }
   }
+  Ok(())
+}
 
-  #[napi]
-  pub fn is_devtools_open(&self) -> bool {
-    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
-      webview.is_devtools_open().unwrap_or(false)
-    } else {
-      // Check if we have a pending OpenDevtools action
-      let pending = self.pending_actions.lock().unwrap();
-      pending
-        .iter()
-        .any(|action| matches!(action, PendingWebviewAction::OpenDevtools))
+#[cfg(not(any(
+  target_os = "windows",
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+)))]
+fn apply_window_shape(
+  _window: &tao::window::Window,
+  _regions: &[crate::tao::structs::Rectangle],
+) -> Result<()> {
+  Err(napi::Error::new(
+    napi::Status::GenericFailure,
+    "Window shapes are not supported on this platform".to_string(),
+  ))
+}
+
+fn monitor_from_tao(monitor: &tao::monitor::MonitorHandle) -> Monitor {
+  let size = monitor.size();
+  let position = monitor.position();
+  Monitor {
+    name: monitor.name(),
+    scale_factor: monitor.scale_factor(),
+    size: Dimensions {
+      width: size.width as f64,
+      height: size.height as f64,
+    },
+    position: Position {
+      x: position.x as f64,
+      y: position.y as f64,
+    },
+    video_modes: monitor
+      .video_modes()
+      .map(|mode| VideoMode {
+        size: Dimensions {
+          width: mode.size().width as f64,
+          height: mode.size().height as f64,
+        },
+        bit_depth: mode.bit_depth() as u32,
+        refresh_rate: mode.refresh_rate() as u32,
+      })
+      .collect(),
+  }
+}
+
+fn cookie_data(cookie: &wry::cookie::Cookie) -> CookieData {
+  CookieData {
+    name: cookie.name().to_string(),
+    value: cookie.value().to_string(),
+    domain: cookie.domain().map(|domain| domain.to_string()),
+    path: cookie.path().map(|path| path.to_string()),
+    secure: cookie.secure(),
+    http_only: cookie.http_only(),
+    expires: cookie
+      .expires_datetime()
+      .map(|expires| expires.unix_timestamp() as f64),
+  }
+}
+
+fn wry_cookie(data: CookieData) -> Result<wry::cookie::Cookie<'static>> {
+  let mut cookie = wry::cookie::Cookie::new(data.name, data.value);
+  if let Some(domain) = data.domain {
+    cookie.set_domain(domain);
+  }
+  if let Some(path) = data.path {
+    cookie.set_path(path);
+  }
+  cookie.set_secure(data.secure);
+  cookie.set_http_only(data.http_only);
+  if let Some(expires) = data.expires {
+    let expires =
+      wry::cookie::time::OffsetDateTime::from_unix_timestamp(expires as i64).map_err(|e| {
+        napi::Error::new(
+          napi::Status::InvalidArg,
+          format!("Invalid cookie expiry: {}", e),
+        )
+      })?;
+    cookie.set_expires(expires);
+  }
+  Ok(cookie)
+}
+
+/// Converts a proxy option to wry's configuration, or `None` for a direct connection. Fails for
+/// endpoints that are not `host:port` and on macOS, where wry is built without proxy support.
+fn proxy_config(proxy: &crate::wry::enums::ProxyConfig) -> Result<Option<wry::ProxyConfig>> {
+  use crate::wry::enums::ProxyConfig;
+
+  let address = match proxy {
+    ProxyConfig::None => return Ok(None),
+    ProxyConfig::Http(address) | ProxyConfig::Https(address) | ProxyConfig::Socks5(address) => {
+      address
+    }
+  };
+  if cfg!(target_os = "macos") {
+    return Err(crate::wry::enums::Error::Unsupported.to_js_error());
+  }
+  let invalid = || {
+    napi::Error::new(
+      napi::Status::InvalidArg,
+      format!("Invalid proxy {:?}, expected host:port", address),
+    )
+  };
+  let (host, port) = address.rsplit_once(':').ok_or_else(invalid)?;
+  let host_valid = match host.strip_prefix('[') {
+    Some(ipv6) => ipv6
+      .strip_suffix(']')
+      .is_some_and(|ip| ip.parse::<std::net::Ipv6Addr>().is_ok()),
+    None => {
+      !host.is_empty()
+        && host
+          .chars()
+          .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
     }
+  };
+  if !host_valid || !matches!(port.parse::<u16>(), Ok(port) if port != 0) {
+    return Err(invalid());
+  }
+  let endpoint = wry::ProxyEndpoint {
+    host: host.to_string(),
+    port: port.to_string(),
+  };
+  Ok(Some(match proxy {
+    ProxyConfig::Socks5(_) => wry::ProxyConfig::Socks5(endpoint),
+    _ => wry::ProxyConfig::Http(endpoint),
+  }))
+}
+
+fn check_min_tls_version(version: TlsVersion) -> Result<()> {
+  if version == TlsVersion::Tls13 && !cfg!(target_os = "windows") {
+    Err(crate::wry::enums::Error::Unsupported.to_js_error())
+  } else {
+    Ok(())
   }
+}
 
-  #[napi]
-  pub fn reload(&self) {
-    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
-      let _ = webview.reload();
-    } else {
-      // Queue the action to be applied when the webview is initialized
-      self
-        .pending_actions
-        .lock()
-        .unwrap()
-        .push(PendingWebviewAction::Reload);
+fn check_cache_mode(mode: CacheMode) -> Result<()> {
+  let supported = match mode {
+    CacheMode::Default => true,
+    CacheMode::NoCache => cfg!(not(target_os = "macos")),
+    CacheMode::OnlyFromCache => false,
+  };
+  if supported {
+    Ok(())
+  } else {
+    Err(crate::wry::enums::Error::Unsupported.to_js_error())
+  }
+}
+
+fn titlebar_overlay_script(overlay: TitlebarOverlay) -> String {
+  format!(
+    r#"(function () {{
+  var apply = function () {{
+    var style = document.documentElement.style;
+    style.setProperty('--titlebar-overlay-height', '{}px');
+    style.setProperty('--titlebar-button-inset', '{}px');
+  }};
+  if (document.documentElement) apply();
+  else document.addEventListener('DOMContentLoaded', apply);
+}})();"#,
+    overlay.height, overlay.button_inset
+  )
+}
+
+#[cfg(target_os = "macos")]
+fn apply_titlebar_overlay(window: &tao::window::Window, overlay: TitlebarOverlay) {
+  use tao::platform::macos::WindowExtMacOS;
+
+  // Height of the traffic light buttons.
+  const BUTTON_HEIGHT: f64 = 14.0;
+
+  window.set_titlebar_transparent(true);
+  window.set_fullsize_content_view(true);
+  window.set_traffic_light_inset(tao::dpi::LogicalPosition::new(
+    overlay.button_inset,
+    ((overlay.height - BUTTON_HEIGHT) / 2.0).max(0.0),
+  ));
+}
+
+/// Upper bound used for a maximum size dimension that was not given. X11 cannot create larger
+/// windows.
+const MAX_WINDOW_DIMENSION: f64 = 32767.0;
+
+/// Builds a size limit from optional dimensions, filling a missing one with `fallback`.
+fn size_limit(
+  width: Option<f64>,
+  height: Option<f64>,
+  fallback: f64,
+) -> Option<tao::dpi::LogicalSize<f64>> {
+  if width.is_none() && height.is_none() {
+    return None;
+  }
+  Some(tao::dpi::LogicalSize::new(
+    width.unwrap_or(fallback),
+    height.unwrap_or(fallback),
+  ))
+}
+
+/// Maps a fullscreen type to tao, using the first video mode of the window's current monitor for
+/// exclusive fullscreen and falling back to borderless when the monitor reports none.
+fn tao_fullscreen(window: &tao::window::Window, kind: FullscreenType) -> tao::window::Fullscreen {
+  match kind {
+    FullscreenType::Exclusive => window
+      .current_monitor()
+      .and_then(|monitor| monitor.video_modes().next())
+      .map(tao::window::Fullscreen::Exclusive)
+      .unwrap_or(tao::window::Fullscreen::Borderless(None)),
+    FullscreenType::Borderless => tao::window::Fullscreen::Borderless(None),
+  }
+}
+
+fn drag_drop_event_data(event: wry::DragDropEvent) -> DragDropEventData {
+  use crate::wry::enums::DragDropEvent;
+
+  let position = |(x, y): (i32, i32)| {
+    Some(Position {
+      x: x as f64,
+      y: y as f64,
+    })
+  };
+  let paths = |paths: Vec<std::path::PathBuf>| {
+    paths
+      .into_iter()
+      .map(|path| path.to_string_lossy().into_owned())
+      .collect()
+  };
+  match event {
+    wry::DragDropEvent::Enter {
+      paths: dragged,
+      position: at,
+    } => DragDropEventData {
+      kind: DragDropEvent::Entered,
+      paths: paths(dragged),
+      position: position(at),
+    },
+    wry::DragDropEvent::Over { position: at } => DragDropEventData {
+      kind: DragDropEvent::Hovered,
+      paths: Vec::new(),
+      position: position(at),
+    },
+    wry::DragDropEvent::Drop {
+      paths: dropped,
+      position: at,
+    } => DragDropEventData {
+      kind: DragDropEvent::Dropped,
+      paths: paths(dropped),
+      position: position(at),
+    },
+    _ => DragDropEventData {
+      kind: DragDropEvent::Left,
+      paths: Vec::new(),
+      position: None,
+    },
+  }
+}
+
+/// Forwards a custom protocol request to the JS handler and answers with the response it returns.
+fn respond_custom_protocol(
+  handler: &Mutex<Option<CustomProtocolHandler>>,
+  request: wry::http::Request<Vec<u8>>,
+  responder: wry::RequestAsyncResponder,
+) {
+  let uri = request.uri().to_string();
+  let content_type = mime_type(request.uri().path());
+  let message = IpcMessage {
+    method: request.method().to_string(),
+    headers: request
+      .headers()
+      .iter()
+      .map(|(key, value)| HeaderData {
+        key: key.to_string(),
+        value: value.to_str().ok().map(|value| value.to_string()),
+      })
+      .collect(),
+    body: request.into_body().into(),
+    uri,
+    window_id: None,
+    label: None,
+    request_id: None,
+  };
+
+  let handler = handler.lock().unwrap();
+  let Some(handler) = handler.as_ref() else {
+    responder.respond(status_response(404));
+    return;
+  };
+  // The responder must be answered exactly once, also when the call cannot be queued.
+  let responder = Arc::new(Mutex::new(Some(responder)));
+  let pending = responder.clone();
+  let status = handler.call_with_return_value(
+    Ok(message),
+    ThreadsafeFunctionCallMode::NonBlocking,
+    move |result, _env| {
+      if let Some(responder) = pending.lock().unwrap().take() {
+        let response = result.and_then(|response| custom_protocol_response(response, content_type));
+        responder.respond(response.unwrap_or_else(|_| status_response(500)));
+      }
+      Ok(())
+    },
+  );
+  if status != napi::Status::Ok {
+    if let Some(responder) = responder.lock().unwrap().take() {
+      responder.respond(status_response(500));
     }
   }
+}
 
-  #[napi]
-  pub fn print(&self) {
-    if let Some(webview) = self.inner.lock().unwrap().as_ref() {
-      let _ = webview.print();
-    } else {
-      // Queue the action to be applied when the webview is initialized
-      self
-        .pending_actions
-        .lock()
-        .unwrap()
-        .push(PendingWebviewAction::Print);
+/// Builds the HTTP response for a `CustomProtocolResponse`, using `content_type` unless the
+/// handler set a `Content-Type` header.
+fn custom_protocol_response(
+  response: CustomProtocolResponse,
+  content_type: &'static str,
+) -> Result<wry::http::Response<Vec<u8>>> {
+  let mut builder = wry::http::Response::builder().status(response.status.unwrap_or(200));
+  let headers = builder.headers_mut().ok_or_else(|| {
+    napi::Error::new(
+      napi::Status::InvalidArg,
+      format!("Invalid status code: {:?}", response.status),
+    )
+  })?;
+  for header in response.headers.unwrap_or_default() {
+    let (name, value) =
+      crate::wry::structs::header_pair(&header.key, header.value.as_deref().unwrap_or_default())?;
+    headers.append(name, value);
+  }
+  if !headers.contains_key(wry::http::header::CONTENT_TYPE) {
+    headers.insert(
+      wry::http::header::CONTENT_TYPE,
+      wry::http::HeaderValue::from_static(content_type),
+    );
+  }
+  builder
+    .body(response.body.map(|body| body.to_vec()).unwrap_or_default())
+    .map_err(|e| napi::Error::new(napi::Status::InvalidArg, e.to_string()))
+}
+
+/// An empty response with `status`.
+fn status_response(status: u16) -> wry::http::Response<Vec<u8>> {
+  wry::http::Response::builder()
+    .status(status)
+    .body(Vec::new())
+    .unwrap_or_default()
+}
+
+/// Scheme serving the files of `WebviewOptions.assetRoot`.
+const ASSET_SCHEME: &str = "asset";
+
+/// Answers an `asset` request with the file below `root`, off the event loop thread. Paths that
+/// would leave `root` are answered with 403.
+fn respond_asset(
+  root: &std::path::Path,
+  request: wry::http::Request<Vec<u8>>,
+  responder: wry::RequestAsyncResponder,
+) {
+  let path = request.uri().path().to_string();
+  let root = root.to_path_buf();
+  std::thread::spawn(move || responder.respond(asset_response(&root, &path)));
+}
+
+/// Reads the file below `root` at the URL path `path`, serving `index.html` for directories.
+fn asset_response(root: &std::path::Path, path: &str) -> wry::http::Response<Vec<u8>> {
+  let path = percent_decode(path);
+  let relative = std::path::Path::new(path.trim_start_matches('/'));
+  let escapes = relative
+    .components()
+    .any(|component| !matches!(component, std::path::Component::Normal(_)));
+  let mut file = root.join(relative);
+  if file.is_dir() {
+    file.push("index.html");
+  }
+  let (status, content_type, body) = if escapes {
+    (403, "text/plain", Vec::new())
+  } else {
+    match std::fs::read(&file) {
+      Ok(body) => (200, mime_type(&file.to_string_lossy()), body),
+      Err(_) => (404, "text/plain", Vec::new()),
+    }
+  };
+  wry::http::Response::builder()
+    .status(status)
+    .header(wry::http::header::CONTENT_TYPE, content_type)
+    .body(body)
+    .unwrap_or_default()
+}
+
+/// Decodes `%XX` escapes of a URL path, keeping malformed escapes as they are.
+fn percent_decode(path: &str) -> String {
+  let bytes = path.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+  while i < bytes.len() {
+    let hex = bytes
+      .get(i + 1..i + 3)
+      .and_then(|hex| std::str::from_utf8(hex).ok())
+      .filter(|_| bytes[i] == b'%')
+      .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+    match hex {
+      Some(byte) => {
+        decoded.push(byte);
+        i += 3;
+      }
+      None => {
+        decoded.push(bytes[i]);
+        i += 1;
+      }
     }
   }
+  String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Inserts `<base href="base_url">` at the start of the head of `html`. Without a head it goes
+/// after the `<html>` tag or the doctype, as anything before the doctype switches the page to
+/// quirks mode.
+fn with_base_url(html: &str, base_url: &str) -> String {
+  let base = format!(
+    "<base href=\"{}\">",
+    base_url.replace('&', "&amp;").replace('"', "&quot;")
+  );
+  let lower = html.to_ascii_lowercase();
+  let end_of_tag = |tags: &[&str]| {
+    tags
+      .iter()
+      .filter_map(|tag| lower.find(tag))
+      .min()
+      .and_then(|start| lower[start..].find('>').map(|end| start + end + 1))
+  };
+  // `<head` alone would also match `<header>`.
+  let insert_at = end_of_tag(&["<head>", "<head "])
+    .or_else(|| end_of_tag(&["<html>", "<html "]))
+    .or_else(|| end_of_tag(&["<!doctype"]))
+    .unwrap_or(0);
+  format!("{}{}{}", &html[..insert_at], base, &html[insert_at..])
+}
+
+/// Guesses the `Content-Type` of a custom protocol response from the path extension.
+fn mime_type(path: &str) -> &'static str {
+  let extension = path
+    .rsplit_once('.')
+    .map(|(_, ext)| ext)
+    .unwrap_or_default();
+  match extension.to_ascii_lowercase().as_str() {
+    "html" | "htm" => "text/html",
+    "js" | "mjs" => "text/javascript",
+    "css" => "text/css",
+    "json" => "application/json",
+    "svg" => "image/svg+xml",
+    "png" => "image/png",
+    "jpg" | "jpeg" => "image/jpeg",
+    "gif" => "image/gif",
+    "webp" => "image/webp",
+    "ico" => "image/x-icon",
+    "wasm" => "application/wasm",
+    "woff" => "font/woff",
+    "woff2" => "font/woff2",
+    "txt" => "text/plain",
+    "" => "text/html",
+    _ => "application/octet-stream",
+  }
+}
+
+fn theme_from_tao(theme: tao::window::Theme) -> Theme {
+  match theme {
+    tao::window::Theme::Dark => Theme::Dark,
+    _ => Theme::Light,
+  }
 }
 
 #[napi]
 pub fn get_webview_version() -> String {
   wry::webview_version().unwrap_or("unknown".to_string())
 }
+
+/// Checks whether the system webview runtime is installed. On Windows a missing WebView2
+/// runtime is reported as unavailable together with its download URL.
+#[napi]
+pub fn check_webview_runtime() -> Result<WebviewRuntimeStatus> {
+  match wry::webview_version() {
+    Ok(version) => Ok(WebviewRuntimeStatus {
+      available: true,
+      version: Some(version),
+      install_url: None,
+    }),
+    // Only a missing runtime; other failures, e.g. a broken installation, are errors.
+    #[cfg(target_os = "windows")]
+    Err(wry::Error::WebView2Error(webview2_com::Error::WindowsError(e)))
+      if e.code() == windows::Win32::Foundation::ERROR_FILE_NOT_FOUND.to_hresult() =>
+    {
+      Ok(WebviewRuntimeStatus {
+        available: false,
+        version: None,
+        install_url: Some("https://developer.microsoft.com/microsoft-edge/webview2/".to_string()),
+      })
+    }
+    Err(e) => Err(napi::Error::new(
+      napi::Status::GenericFailure,
+      format!("Failed to query the webview runtime: {}", e),
+    )),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn geometry(x: f64, y: f64, width: f64, height: f64) -> WindowGeometry {
+    WindowGeometry {
+      x,
+      y,
+      width,
+      height,
+      maximized: false,
+      fullscreen: false,
+    }
+  }
+
+  fn bounds(x: f64, y: f64, width: f64, height: f64) -> PhysicalBounds {
+    (
+      tao::dpi::PhysicalPosition::new(x, y),
+      tao::dpi::PhysicalSize::new(width, height),
+    )
+  }
+
+  fn monitor(x: f64, y: f64, width: f64, height: f64, scale_factor: f64) -> MonitorBounds {
+    MonitorBounds {
+      bounds: bounds(x, y, width, height),
+      scale_factor,
+    }
+  }
+
+  #[cfg(not(target_os = "macos"))]
+  #[test]
+  fn proxy_config_accepts_host_and_port() {
+    use crate::wry::enums::ProxyConfig;
+
+    let Ok(Some(wry::ProxyConfig::Http(endpoint))) =
+      proxy_config(&ProxyConfig::Http("proxy.example.com:8080".to_string()))
+    else {
+      panic!("expected an HTTP proxy");
+    };
+    assert_eq!(endpoint.host, "proxy.example.com");
+    assert_eq!(endpoint.port, "8080");
+
+    let Ok(Some(wry::ProxyConfig::Socks5(endpoint))) =
+      proxy_config(&ProxyConfig::Socks5("[::1]:1080".to_string()))
+    else {
+      panic!("expected a SOCKS5 proxy");
+    };
+    assert_eq!(endpoint.host, "[::1]");
+    assert_eq!(endpoint.port, "1080");
+
+    assert!(matches!(
+      proxy_config(&ProxyConfig::Https("10.0.0.1:3128".to_string())),
+      Ok(Some(wry::ProxyConfig::Http(_)))
+    ));
+    assert!(matches!(proxy_config(&ProxyConfig::None), Ok(None)));
+  }
+
+  #[cfg(not(target_os = "macos"))]
+  #[test]
+  fn proxy_config_rejects_invalid_addresses() {
+    use crate::wry::enums::ProxyConfig;
+
+    for address in [
+      "proxy.example.com",
+      "proxy.example.com:",
+      ":8080",
+      "proxy.example.com:0",
+      "proxy.example.com:65536",
+      "proxy example.com:8080",
+      "http://proxy.example.com:8080",
+      "::1:1080",
+      "[::1:1080",
+      "[not-an-ip]:1080",
+    ] {
+      let error = proxy_config(&ProxyConfig::Http(address.to_string()))
+        .err()
+        .unwrap_or_else(|| panic!("{:?} was accepted", address));
+      assert_eq!(error.status, napi::Status::InvalidArg, "{:?}", address);
+    }
+  }
+
+  #[cfg(target_os = "macos")]
+  #[test]
+  fn proxy_config_is_unsupported() {
+    use crate::wry::enums::ProxyConfig;
+
+    assert!(proxy_config(&ProxyConfig::Http("proxy.example.com:8080".to_string())).is_err());
+    assert!(matches!(proxy_config(&ProxyConfig::None), Ok(None)));
+  }
+
+  #[test]
+  fn control_flow_without_a_mode_waits_for_events() {
+    let mut timing = LoopTiming::new(None, None, None);
+    assert_eq!(
+      timing.control_flow(None),
+      tao::event_loop::ControlFlow::Wait
+    );
+
+    let wake_at = std::time::Instant::now() + std::time::Duration::from_secs(1);
+    assert_eq!(
+      timing.control_flow(Some(wake_at)),
+      tao::event_loop::ControlFlow::WaitUntil(wake_at)
+    );
+  }
+
+  #[test]
+  fn control_flow_follows_the_configured_mode() {
+    assert_eq!(
+      LoopTiming::new(Some(ControlFlow::Poll), None, None).control_flow(None),
+      tao::event_loop::ControlFlow::Poll
+    );
+    assert_eq!(
+      LoopTiming::new(Some(ControlFlow::Exit), None, None).control_flow(None),
+      tao::event_loop::ControlFlow::Exit
+    );
+    assert_eq!(
+      LoopTiming::new(Some(ControlFlow::ExitWithCode), None, Some(3)).control_flow(None),
+      tao::event_loop::ControlFlow::ExitWithCode(3)
+    );
+  }
+
+  #[test]
+  fn control_flow_wait_until_keeps_its_tick() {
+    let mut timing = LoopTiming::new(Some(ControlFlow::WaitUntil), Some(60_000), None);
+    let start = std::time::Instant::now();
+    let tao::event_loop::ControlFlow::WaitUntil(tick) = timing.control_flow(None) else {
+      panic!("expected WaitUntil");
+    };
+    assert!(tick >= start + std::time::Duration::from_secs(60));
+    // Events arriving before the tick do not push it back.
+    assert_eq!(
+      timing.control_flow(None),
+      tao::event_loop::ControlFlow::WaitUntil(tick)
+    );
+    // An earlier wake-up, e.g. for an animation frame, wins.
+    let wake_at = start + std::time::Duration::from_millis(16);
+    assert_eq!(
+      timing.control_flow(Some(wake_at)),
+      tao::event_loop::ControlFlow::WaitUntil(wake_at)
+    );
+  }
+
+  #[test]
+  fn fit_to_monitors_keeps_reachable_windows() {
+    let monitors = [
+      monitor(0.0, 0.0, 1920.0, 1080.0, 1.0),
+      monitor(1920.0, 0.0, 1280.0, 1024.0, 1.0),
+    ];
+    let requested = bounds(2000.0, 100.0, 800.0, 600.0);
+    assert_eq!(
+      fit_to_monitors(requested, &monitors, Some(monitors[0])),
+      requested
+    );
+    // Partly off-screen, but enough of the window is left to grab it.
+    let requested = bounds(1800.0, -100.0, 800.0, 600.0);
+    assert_eq!(
+      fit_to_monitors(requested, &monitors[..1], Some(monitors[0])),
+      requested
+    );
+  }
+
+  #[test]
+  fn fit_to_monitors_moves_lost_windows_onto_the_primary_monitor() {
+    let monitors = [
+      monitor(-1280.0, 0.0, 1280.0, 1024.0, 1.0),
+      monitor(0.0, 0.0, 1920.0, 1080.0, 1.0),
+    ];
+    // Left on a monitor that has been disconnected.
+    assert_eq!(
+      fit_to_monitors(
+        bounds(3000.0, 200.0, 800.0, 600.0),
+        &monitors,
+        Some(monitors[1])
+      ),
+      bounds(1120.0, 200.0, 800.0, 600.0)
+    );
+    // Only a sliver overlaps, less than `MIN_VISIBLE_EXTENT`.
+    assert_eq!(
+      fit_to_monitors(
+        bounds(1900.0, 100.0, 800.0, 600.0),
+        &monitors,
+        Some(monitors[1])
+      ),
+      bounds(1120.0, 100.0, 800.0, 600.0)
+    );
+    // Without a primary monitor the first one is used.
+    assert_eq!(
+      fit_to_monitors(bounds(-5000.0, 0.0, 800.0, 600.0), &monitors, None),
+      bounds(-1280.0, 0.0, 800.0, 600.0)
+    );
+  }
+
+  #[test]
+  fn fit_to_monitors_shrinks_windows_larger_than_the_monitor() {
+    let monitors = [monitor(0.0, 0.0, 1280.0, 720.0, 1.0)];
+    assert_eq!(
+      fit_to_monitors(
+        bounds(5000.0, 5000.0, 1920.0, 1080.0),
+        &monitors,
+        Some(monitors[0])
+      ),
+      bounds(0.0, 0.0, 1280.0, 720.0)
+    );
+  }
+
+  #[test]
+  fn fit_to_monitors_keeps_the_geometry_without_monitors() {
+    let requested = bounds(5000.0, 5000.0, 800.0, 600.0);
+    assert_eq!(fit_to_monitors(requested, &[], None), requested);
+  }
+
+  #[test]
+  fn fit_to_monitors_scales_the_visible_extent_per_monitor() {
+    // A 4K monitor at 200% next to a 1080p monitor at 100%.
+    let monitors = [
+      monitor(0.0, 0.0, 3840.0, 2160.0, 2.0),
+      monitor(3840.0, 0.0, 1920.0, 1080.0, 1.0),
+    ];
+    // 60 physical pixels are enough on the 100% monitor...
+    let requested = bounds(5700.0, 100.0, 800.0, 600.0);
+    assert_eq!(
+      fit_to_monitors(requested, &monitors, Some(monitors[0])),
+      requested
+    );
+    // ...but only 30 logical pixels on the 200% one.
+    assert_eq!(
+      fit_to_monitors(
+        bounds(-740.0, 100.0, 800.0, 600.0),
+        &monitors[..1],
+        Some(monitors[0])
+      ),
+      bounds(0.0, 100.0, 800.0, 600.0)
+    );
+  }
+
+  #[test]
+  fn scale_factor_at_picks_the_monitor_containing_the_scaled_position() {
+    let monitors = [
+      monitor(0.0, 0.0, 3840.0, 2160.0, 2.0),
+      monitor(3840.0, 0.0, 1920.0, 1080.0, 1.0),
+    ];
+    let at = |x: f64, y: f64| scale_factor_at(&geometry(x, y, 800.0, 600.0), &monitors, None);
+    assert_eq!(at(1000.0, 100.0), 2.0);
+    assert_eq!(at(4000.0, 100.0), 1.0);
+    // Off every monitor, the primary monitor's factor is used.
+    assert_eq!(
+      scale_factor_at(
+        &geometry(-3000.0, 0.0, 800.0, 600.0),
+        &monitors,
+        Some(monitors[1])
+      ),
+      1.0
+    );
+    assert_eq!(
+      scale_factor_at(&geometry(-3000.0, 0.0, 800.0, 600.0), &monitors, None),
+      1.0
+    );
+  }
+
+  #[test]
+  fn window_id_string_is_the_decimal_low_level_id() {
+    let id = unsafe { tao::window::WindowId::dummy() };
+    assert_eq!(
+      window_id_string(id),
+      crate::tao::structs::window_id_u64(id).to_string()
+    );
+    assert!(window_id_string(id).chars().all(|c| c.is_ascii_digit()));
+  }
+
+  #[test]
+  fn check_path_component_accepts_plain_names() {
+    assert!(check_path_component("profile name", "work").is_ok());
+    assert!(check_path_component("application id", "com.example.editor").is_ok());
+  }
+
+  #[test]
+  fn check_path_component_rejects_paths_and_drive_prefixes() {
+    for name in [
+      "",
+      ".",
+      "..",
+      "a/b",
+      "a\\b",
+      "C:",
+      "C:evil",
+      "name:stream",
+      "a\0b",
+    ] {
+      assert!(
+        check_path_component("profile name", name).is_err(),
+        "{:?}",
+        name
+      );
+    }
+  }
+
+  #[test]
+  fn percent_decode_decodes_escapes() {
+    assert_eq!(percent_decode("/my%20file%2Ehtml"), "/my file.html");
+    assert_eq!(percent_decode("/%C3%A9t%C3%A9"), "/été");
+  }
+
+  #[test]
+  fn percent_decode_keeps_malformed_escapes() {
+    assert_eq!(percent_decode("/100%"), "/100%");
+    assert_eq!(percent_decode("/%zz%4"), "/%zz%4");
+  }
+
+  #[test]
+  fn with_base_url_starts_the_head() {
+    assert_eq!(
+      with_base_url(
+        "<html><head lang=\"en\"><title>t</title></head></html>",
+        "https://a/"
+      ),
+      "<html><head lang=\"en\"><base href=\"https://a/\"><title>t</title></head></html>"
+    );
+    assert_eq!(
+      with_base_url("<header></header><head></head>", "https://a/"),
+      "<header></header><head><base href=\"https://a/\"></head>"
+    );
+  }
+
+  #[test]
+  fn with_base_url_without_a_head_follows_the_doctype() {
+    assert_eq!(
+      with_base_url(
+        "<!DOCTYPE html><html lang=\"en\"><p>x</p></html>",
+        "https://a/"
+      ),
+      "<!DOCTYPE html><html lang=\"en\"><base href=\"https://a/\"><p>x</p></html>"
+    );
+    assert_eq!(
+      with_base_url("<!doctype html><p>x</p>", "https://a/"),
+      "<!doctype html><base href=\"https://a/\"><p>x</p>"
+    );
+    assert_eq!(
+      with_base_url("<p>x</p>", "https://a/?a=1&b=\"2\""),
+      "<base href=\"https://a/?a=1&amp;b=&quot;2&quot;\"><p>x</p>"
+    );
+  }
+
+  #[test]
+  fn asset_response_serves_files_below_the_root() {
+    let root = std::env::temp_dir().join(format!("webview-assets-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("sub dir")).unwrap();
+    std::fs::write(root.join("index.html"), "<p>index</p>").unwrap();
+    std::fs::write(root.join("sub dir").join("app.js"), "run()").unwrap();
+
+    let index = asset_response(&root, "/");
+    assert_eq!(index.status(), 200);
+    assert_eq!(
+      index.headers()[wry::http::header::CONTENT_TYPE],
+      "text/html"
+    );
+    assert_eq!(index.body(), b"<p>index</p>");
+
+    let script = asset_response(&root, "/sub%20dir/app.js");
+    assert_eq!(script.status(), 200);
+    assert_eq!(
+      script.headers()[wry::http::header::CONTENT_TYPE],
+      "text/javascript"
+    );
+    assert_eq!(script.body(), b"run()");
+
+    assert_eq!(asset_response(&root, "/missing.css").status(), 404);
+    assert_eq!(asset_response(&root, "/../index.html").status(), 403);
+    assert_eq!(
+      asset_response(&root, "/sub%20dir/%2E%2E/index.html").status(),
+      403
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+  }
+}
@@ -41,6 +41,15 @@ pub use tao::structs::{
 };
 pub use tao::types::{AxisId, ButtonId, DeviceId, Result as TaoResult, WindowId, RGBA as TaoRGBA};
 
+// Window capture
+pub mod capture;
+
+// System sleep and wake notifications
+pub mod power;
+
+// Native menus
+pub mod menu;
+
 // High-level API adapter
 pub mod high_level;
 pub use high_level::*;
@@ -0,0 +1,258 @@
+//! Native menus built with muda, used by `BrowserWindow::set_menu` and
+//! `BrowserWindow::popup_menu`.
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// A clickable menu entry.
+#[napi(object)]
+pub struct MenuItemOptions {
+  /// Identifier reported as `MenuEvent.id` when the item is selected.
+  pub id: String,
+  pub text: String,
+  /// Keyboard shortcut, e.g. `CmdOrCtrl+S` or `Alt+Shift+F4`.
+  pub accelerator: Option<String>,
+  /// Defaults to `true`.
+  pub enabled: Option<bool>,
+}
+
+/// Selection of a menu item, passed to the handler set with `Application.onMenuEvent`.
+#[napi(object)]
+pub struct MenuEvent {
+  /// The `id` the item was created with.
+  pub id: String,
+}
+
+/// A menu bar or context menu.
+#[napi]
+pub struct Menu {
+  pub(crate) inner: muda::Menu,
+}
+
+#[napi]
+impl Menu {
+  /// Creates an empty menu.
+  #[napi(constructor)]
+  pub fn new() -> Result<Self> {
+    Ok(Self {
+      inner: muda::Menu::new(),
+    })
+  }
+
+  /// Appends an item.
+  #[napi]
+  pub fn append_item(&self, item: MenuItemOptions) -> Result<()> {
+    self.inner.append(&menu_item(item)?).map_err(menu_error)
+  }
+
+  /// Appends a separator line.
+  #[napi]
+  pub fn append_separator(&self) -> Result<()> {
+    self
+      .inner
+      .append(&muda::PredefinedMenuItem::separator())
+      .map_err(menu_error)
+  }
+
+  /// Appends a submenu. On macOS, items of a menu bar must all be submenus.
+  #[napi]
+  pub fn append_submenu(&self, submenu: &Submenu) -> Result<()> {
+    self.inner.append(&submenu.inner).map_err(menu_error)
+  }
+}
+
+/// A nested menu, shown as a menu bar entry or as a cascading item.
+#[napi]
+pub struct Submenu {
+  pub(crate) inner: muda::Submenu,
+}
+
+#[napi]
+impl Submenu {
+  /// Creates an empty submenu titled `text`.
+  #[napi(constructor)]
+  pub fn new(text: String, enabled: Option<bool>) -> Result<Self> {
+    Ok(Self {
+      inner: muda::Submenu::new(text, enabled.unwrap_or(true)),
+    })
+  }
+
+  /// Appends an item.
+  #[napi]
+  pub fn append_item(&self, item: MenuItemOptions) -> Result<()> {
+    self.inner.append(&menu_item(item)?).map_err(menu_error)
+  }
+
+  /// Appends a separator line.
+  #[napi]
+  pub fn append_separator(&self) -> Result<()> {
+    self
+      .inner
+      .append(&muda::PredefinedMenuItem::separator())
+      .map_err(menu_error)
+  }
+
+  /// Appends a nested submenu.
+  #[napi]
+  pub fn append_submenu(&self, submenu: &Submenu) -> Result<()> {
+    self.inner.append(&submenu.inner).map_err(menu_error)
+  }
+}
+
+fn menu_item(item: MenuItemOptions) -> Result<muda::MenuItem> {
+  let accelerator = match item.accelerator {
+    Some(accelerator) => Some(
+      accelerator
+        .parse::<muda::accelerator::Accelerator>()
+        .map_err(|e| {
+          napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("Invalid accelerator {:?}: {}", accelerator, e),
+          )
+        })?,
+    ),
+    None => None,
+  };
+  Ok(muda::MenuItem::with_id(
+    item.id,
+    item.text,
+    item.enabled.unwrap_or(true),
+    accelerator,
+  ))
+}
+
+/// Whether `items` or their submenus contain the item with `id`.
+pub(crate) fn contains_item(items: &[muda::MenuItemKind], id: &str) -> bool {
+  items.iter().any(|item| {
+    item.id().0 == id
+      || item
+        .as_submenu()
+        .is_some_and(|submenu| contains_item(&submenu.items(), id))
+  })
+}
+
+fn menu_error(e: muda::Error) -> napi::Error {
+  napi::Error::new(napi::Status::GenericFailure, format!("Menu error: {}", e))
+}
+
+/// Shows `menu` as the menu bar of `window`.
+#[cfg(target_os = "windows")]
+pub(crate) fn attach_menu(window: &tao::window::Window, menu: &muda::Menu) -> Result<()> {
+  use tao::platform::windows::WindowExtWindows;
+
+  unsafe { menu.init_for_hwnd(window.hwnd()) }.map_err(menu_error)?;
+  WINDOW_MENUS.with(|menus| {
+    let mut menus = menus.borrow_mut();
+    menus.retain(|(hwnd, _)| *hwnd != window.hwnd());
+    menus.push((window.hwnd(), menu.clone()));
+  });
+  Ok(())
+}
+
+#[cfg(target_os = "windows")]
+thread_local! {
+  /// Menu bars attached with `attach_menu`, by top-level window handle.
+  static WINDOW_MENUS: std::cell::RefCell<Vec<(isize, muda::Menu)>> =
+    const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Message hook of the event loop that runs the accelerators of the menu bar of the window
+/// a keyboard message is for. Returns `true` when the message was translated into a menu
+/// command and must not be dispatched.
+#[cfg(target_os = "windows")]
+pub(crate) fn translate_accelerator(msg: *const std::ffi::c_void) -> bool {
+  const GA_ROOT: u32 = 2;
+
+  #[link(name = "user32")]
+  extern "system" {
+    fn GetAncestor(hwnd: isize, flags: u32) -> isize;
+    fn TranslateAcceleratorW(hwnd: isize, accelerators: isize, msg: *const std::ffi::c_void)
+      -> i32;
+  }
+
+  // `hwnd` is the first field of `MSG`.
+  let hwnd = unsafe { *(msg as *const isize) };
+  if hwnd == 0 {
+    return false;
+  }
+  let root = unsafe { GetAncestor(hwnd, GA_ROOT) };
+  WINDOW_MENUS.with(|menus| {
+    menus
+      .borrow()
+      .iter()
+      .find(|(menu_hwnd, _)| *menu_hwnd == root)
+      .is_some_and(|(_, menu)| unsafe { TranslateAcceleratorW(root, menu.haccel(), msg) } != 0)
+  })
+}
+
+/// Shows `menu` as the application menu, which macOS shares between all windows.
+#[cfg(target_os = "macos")]
+pub(crate) fn attach_menu(_window: &tao::window::Window, menu: &muda::Menu) -> Result<()> {
+  menu.init_for_nsapp();
+  Ok(())
+}
+
+/// Shows `menu` as the menu bar of `window`, above its webviews.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+pub(crate) fn attach_menu(window: &tao::window::Window, menu: &muda::Menu) -> Result<()> {
+  use tao::platform::unix::WindowExtUnix;
+
+  menu
+    .init_for_gtk_window(window.gtk_window(), window.default_vbox())
+    .map_err(menu_error)
+}
+
+/// Shows `menu` as a context menu of `window` at `position`, or at the cursor.
+#[cfg(target_os = "windows")]
+pub(crate) fn show_context_menu(
+  window: &tao::window::Window,
+  menu: &muda::Menu,
+  position: Option<muda::dpi::Position>,
+) {
+  use muda::ContextMenu;
+  use tao::platform::windows::WindowExtWindows;
+
+  unsafe {
+    menu.show_context_menu_for_hwnd(window.hwnd(), position);
+  }
+}
+
+/// Shows `menu` as a context menu of `window` at `position`, or at the cursor.
+#[cfg(target_os = "macos")]
+pub(crate) fn show_context_menu(
+  window: &tao::window::Window,
+  menu: &muda::Menu,
+  position: Option<muda::dpi::Position>,
+) {
+  use muda::ContextMenu;
+  use tao::platform::macos::WindowExtMacOS;
+
+  unsafe {
+    menu.show_context_menu_for_nsview(window.ns_view(), position);
+  }
+}
+
+/// Shows `menu` as a context menu of `window` at `position`, or at the cursor.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+pub(crate) fn show_context_menu(
+  window: &tao::window::Window,
+  menu: &muda::Menu,
+  position: Option<muda::dpi::Position>,
+) {
+  use muda::ContextMenu;
+  use tao::platform::unix::WindowExtUnix;
+
+  menu.show_context_menu_for_gtk_window(window.gtk_window().as_ref(), position);
+}
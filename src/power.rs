@@ -0,0 +1,272 @@
+//! System sleep and wake notifications delivered as `Application` events.
+
+/// A change of the system power state.
+#[derive(Clone, Copy)]
+pub(crate) enum PowerEvent {
+  Suspend,
+  Resume,
+}
+
+pub(crate) type PowerListener = Box<dyn Fn(PowerEvent) + Send + Sync>;
+
+/// Listeners registered with `watch_power_events`, by id.
+static LISTENERS: std::sync::Mutex<Vec<(u64, PowerListener)>> = std::sync::Mutex::new(Vec::new());
+static NEXT_LISTENER_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+static SUBSCRIBE: std::sync::Once = std::sync::Once::new();
+
+/// Keeps a listener registered with `watch_power_events`; dropping it unregisters the listener.
+pub(crate) struct PowerWatch {
+  id: u64,
+}
+
+impl Drop for PowerWatch {
+  fn drop(&mut self) {
+    LISTENERS.lock().unwrap().retain(|(id, _)| *id != self.id);
+  }
+}
+
+/// Delivers sleep and wake notifications to `listener` until the returned guard is dropped. The
+/// system notifications are subscribed to once per process, on the first call. The listener is
+/// called on a system thread.
+pub(crate) fn watch_power_events(listener: PowerListener) -> PowerWatch {
+  SUBSCRIBE.call_once(subscribe);
+  let id = NEXT_LISTENER_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+  LISTENERS.lock().unwrap().push((id, listener));
+  PowerWatch { id }
+}
+
+/// Calls every registered listener with `event`.
+fn dispatch(event: PowerEvent) {
+  for (_, listener) in LISTENERS.lock().unwrap().iter() {
+    listener(event);
+  }
+}
+
+/// Subscribes `dispatch` to sleep and wake notifications for the rest of the process, through
+/// `PowerRegisterSuspendResumeNotification`.
+#[cfg(target_os = "windows")]
+fn subscribe() {
+  use std::ffi::c_void;
+
+  #[repr(C)]
+  struct DeviceNotifySubscribeParameters {
+    callback: unsafe extern "system" fn(*mut c_void, u32, *mut c_void) -> u32,
+    context: *mut c_void,
+  }
+
+  #[link(name = "powrprof")]
+  extern "system" {
+    fn PowerRegisterSuspendResumeNotification(
+      flags: u32,
+      recipient: *mut c_void,
+      registration: *mut *mut c_void,
+    ) -> u32;
+  }
+
+  const DEVICE_NOTIFY_CALLBACK: u32 = 2;
+  const PBT_APMSUSPEND: u32 = 0x4;
+  const PBT_APMRESUMEAUTOMATIC: u32 = 0x12;
+
+  unsafe extern "system" fn on_power_change(
+    _context: *mut c_void,
+    kind: u32,
+    _setting: *mut c_void,
+  ) -> u32 {
+    match kind {
+      PBT_APMSUSPEND => dispatch(PowerEvent::Suspend),
+      // Sent on every resume, unlike PBT_APMRESUMESUSPEND which needs user input first.
+      PBT_APMRESUMEAUTOMATIC => dispatch(PowerEvent::Resume),
+      _ => {}
+    }
+    0
+  }
+
+  // The registration lives as long as the process, so its parameters are leaked.
+  let parameters = Box::leak(Box::new(DeviceNotifySubscribeParameters {
+    callback: on_power_change,
+    context: std::ptr::null_mut(),
+  }));
+  let mut registration = std::ptr::null_mut();
+  unsafe {
+    PowerRegisterSuspendResumeNotification(
+      DEVICE_NOTIFY_CALLBACK,
+      parameters as *mut DeviceNotifySubscribeParameters as *mut c_void,
+      &mut registration,
+    );
+  }
+}
+
+/// Subscribes `dispatch` to sleep and wake notifications for the rest of the process, through
+/// `IORegisterForSystemPower` on the main run loop.
+#[cfg(target_os = "macos")]
+fn subscribe() {
+  use std::ffi::c_void;
+  use std::sync::atomic::{AtomicU32, Ordering};
+
+  #[link(name = "IOKit", kind = "framework")]
+  extern "C" {
+    fn IORegisterForSystemPower(
+      refcon: *mut c_void,
+      notify_port: *mut *mut c_void,
+      callback: unsafe extern "C" fn(*mut c_void, u32, u32, *mut c_void),
+      notifier: *mut u32,
+    ) -> u32;
+    fn IONotificationPortGetRunLoopSource(notify_port: *mut c_void) -> *mut c_void;
+    fn IOAllowPowerChange(kernel_port: u32, notification_id: isize) -> i32;
+  }
+
+  #[link(name = "CoreFoundation", kind = "framework")]
+  extern "C" {
+    static kCFRunLoopCommonModes: *const c_void;
+    fn CFRunLoopGetMain() -> *mut c_void;
+    fn CFRunLoopAddSource(run_loop: *mut c_void, source: *mut c_void, mode: *const c_void);
+  }
+
+  const IO_MESSAGE_CAN_SYSTEM_SLEEP: u32 = 0xe000_0270;
+  const IO_MESSAGE_SYSTEM_WILL_SLEEP: u32 = 0xe000_0280;
+  const IO_MESSAGE_SYSTEM_HAS_POWERED_ON: u32 = 0xe000_0300;
+
+  // Sleep has to be acknowledged on the connection returned by the registration.
+  static ROOT_PORT: AtomicU32 = AtomicU32::new(0);
+
+  unsafe extern "C" fn on_power_change(
+    _refcon: *mut c_void,
+    _service: u32,
+    message: u32,
+    argument: *mut c_void,
+  ) {
+    match message {
+      IO_MESSAGE_CAN_SYSTEM_SLEEP => {
+        IOAllowPowerChange(ROOT_PORT.load(Ordering::Relaxed), argument as isize);
+      }
+      IO_MESSAGE_SYSTEM_WILL_SLEEP => {
+        dispatch(PowerEvent::Suspend);
+        IOAllowPowerChange(ROOT_PORT.load(Ordering::Relaxed), argument as isize);
+      }
+      IO_MESSAGE_SYSTEM_HAS_POWERED_ON => dispatch(PowerEvent::Resume),
+      _ => {}
+    }
+  }
+
+  let mut notify_port = std::ptr::null_mut();
+  let mut notifier = 0;
+  unsafe {
+    let root_port = IORegisterForSystemPower(
+      std::ptr::null_mut(),
+      &mut notify_port,
+      on_power_change,
+      &mut notifier,
+    );
+    if root_port == 0 {
+      return;
+    }
+    ROOT_PORT.store(root_port, Ordering::Relaxed);
+    CFRunLoopAddSource(
+      CFRunLoopGetMain(),
+      IONotificationPortGetRunLoopSource(notify_port),
+      kCFRunLoopCommonModes,
+    );
+  }
+}
+
+/// Subscribes `dispatch` to sleep and wake notifications for the rest of the process, through the
+/// `PrepareForSleep` signal of systemd-logind on the system D-Bus. Nothing is delivered on
+/// systems without logind or without a system bus.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn subscribe() {
+  use std::ffi::{c_char, c_void};
+
+  extern "C" {
+    fn g_bus_get_sync(
+      bus_type: i32,
+      cancellable: *mut c_void,
+      error: *mut *mut c_void,
+    ) -> *mut c_void;
+    fn g_dbus_connection_signal_subscribe(
+      connection: *mut c_void,
+      sender: *const c_char,
+      interface_name: *const c_char,
+      member: *const c_char,
+      object_path: *const c_char,
+      arg0: *const c_char,
+      flags: i32,
+      callback: unsafe extern "C" fn(
+        *mut c_void,
+        *const c_char,
+        *const c_char,
+        *const c_char,
+        *const c_char,
+        *mut c_void,
+        *mut c_void,
+      ),
+      user_data: *mut c_void,
+      user_data_free: *mut c_void,
+    ) -> u32;
+    fn g_variant_get_child_value(value: *mut c_void, index: usize) -> *mut c_void;
+    fn g_variant_get_boolean(value: *mut c_void) -> i32;
+    fn g_variant_unref(value: *mut c_void);
+    fn g_error_free(error: *mut c_void);
+  }
+
+  const G_BUS_TYPE_SYSTEM: i32 = 1;
+
+  unsafe extern "C" fn on_prepare_for_sleep(
+    _connection: *mut c_void,
+    _sender: *const c_char,
+    _object_path: *const c_char,
+    _interface_name: *const c_char,
+    _signal_name: *const c_char,
+    parameters: *mut c_void,
+    _user_data: *mut c_void,
+  ) {
+    // The signal carries `true` before sleeping and `false` after waking up.
+    let starting = g_variant_get_child_value(parameters, 0);
+    let suspending = g_variant_get_boolean(starting) != 0;
+    g_variant_unref(starting);
+    dispatch(if suspending {
+      PowerEvent::Suspend
+    } else {
+      PowerEvent::Resume
+    });
+  }
+
+  unsafe {
+    let mut error = std::ptr::null_mut();
+    let connection = g_bus_get_sync(G_BUS_TYPE_SYSTEM, std::ptr::null_mut(), &mut error);
+    if connection.is_null() {
+      if !error.is_null() {
+        g_error_free(error);
+      }
+      return;
+    }
+    g_dbus_connection_signal_subscribe(
+      connection,
+      c"org.freedesktop.login1".as_ptr(),
+      c"org.freedesktop.login1.Manager".as_ptr(),
+      c"PrepareForSleep".as_ptr(),
+      c"/org/freedesktop/login1".as_ptr(),
+      std::ptr::null(),
+      0,
+      on_prepare_for_sleep,
+      std::ptr::null_mut(),
+      std::ptr::null_mut(),
+    );
+  }
+}
+
+#[cfg(not(any(
+  target_os = "windows",
+  target_os = "macos",
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+)))]
+fn subscribe() {}
@@ -314,6 +314,7 @@ pub enum ModifiersState {
 
 /// Cursor icon.
 #[napi]
+#[derive(Clone, Copy)]
 pub enum CursorIcon {
   Default,
   Crosshair,
@@ -617,6 +618,7 @@ pub enum ProgressState {
 
 /// Resize direction for window resizing.
 #[napi]
+#[derive(Clone, Copy)]
 pub enum ResizeDirection {
   East,
   North,
@@ -7,7 +7,7 @@ use napi_derive::napi;
 use std::sync::{Arc, Mutex};
 
 use crate::tao::enums::{
-  CursorIcon, ModifiersState, MouseButton, MouseButtonState, TaoTheme, WindowEvent,
+  CursorIcon, ModifiersState, MouseButton, MouseButtonState, ResizeDirection, TaoTheme, WindowEvent,
 };
 use crate::tao::types::Result;
 
@@ -493,8 +493,9 @@ pub struct EventLoopWindowTarget {
 /// Window for displaying content.
 #[napi]
 pub struct Window {
-  #[allow(dead_code)]
   pub(crate) inner: Option<Arc<Mutex<tao::window::Window>>>,
+  /// Last cursor set with `set_cursor_icon`, since tao cannot report it.
+  pub(crate) cursor_icon: Mutex<CursorIcon>,
 }
 
 #[napi]
@@ -502,23 +503,17 @@ impl Window {
   /// Creates a new window with default attributes.
   #[napi(constructor)]
   pub fn new() -> Result<Self> {
-    Ok(Self { inner: None })
+    Ok(Self {
+      inner: None,
+      cursor_icon: Mutex::new(CursorIcon::Default),
+    })
   }
 
   /// Gets the window ID.
   #[napi(getter)]
   pub fn id(&self) -> Result<u64> {
     if let Some(inner) = &self.inner {
-      let id = inner.lock().unwrap().id();
-      let mut id_val: u64 = 0;
-      unsafe {
-        std::ptr::copy_nonoverlapping(
-          &id as *const _ as *const u8,
-          &mut id_val as *mut _ as *mut u8,
-          std::mem::size_of_val(&id).min(8),
-        );
-      }
-      Ok(id_val)
+      Ok(window_id_u64(inner.lock().unwrap().id()))
     } else {
       Ok(0)
     }
@@ -716,6 +711,15 @@ impl Window {
     Ok(())
   }
 
+  /// Sets whether the window is shown on all workspaces. Ignored on Windows.
+  #[napi]
+  pub fn set_visible_on_all_workspaces(&self, visible: bool) -> Result<()> {
+    if let Some(inner) = &self.inner {
+      inner.lock().unwrap().set_visible_on_all_workspaces(visible);
+    }
+    Ok(())
+  }
+
   /// Gets whether the window is focused.
   #[napi]
   pub fn is_focused(&self) -> Result<bool> {
@@ -735,10 +739,10 @@ impl Window {
     Ok(())
   }
 
-  /// Gets the current cursor icon.
+  /// Gets the cursor icon last set with `setCursorIcon`.
   #[napi]
   pub fn cursor_icon(&self) -> Result<CursorIcon> {
-    Ok(CursorIcon::Default)
+    Ok(*self.cursor_icon.lock().unwrap())
   }
 
   /// Sets the cursor icon.
@@ -776,6 +780,7 @@ impl Window {
       };
       inner.lock().unwrap().set_cursor_icon(tao_cursor);
     }
+    *self.cursor_icon.lock().unwrap() = cursor;
     Ok(())
   }
 
@@ -821,6 +826,23 @@ impl Window {
     }
   }
 
+  /// Resizes the window with the mouse from the edge or corner in `direction`, while a button
+  /// is held.
+  #[napi]
+  pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<bool> {
+    if let Some(inner) = &self.inner {
+      Ok(
+        inner
+          .lock()
+          .unwrap()
+          .drag_resize_window(tao_resize_direction(direction))
+          .is_ok(),
+      )
+    } else {
+      Ok(false)
+    }
+  }
+
   /// Sets the window theme.
   #[napi]
   pub fn set_theme(&self, theme: TaoTheme) -> Result<()> {
@@ -879,11 +901,12 @@ impl Window {
     Ok(())
   }
 
-  /// Closes the window.
+  /// Closes the window. tao destroys a window when it is dropped, so the window is hidden right
+  /// away and destroyed once no other handle to it remains.
   #[napi]
-  pub fn close(&self) -> Result<()> {
-    if let Some(inner) = &self.inner {
-      inner.lock().unwrap().request_redraw();
+  pub fn close(&mut self) -> Result<()> {
+    if let Some(inner) = self.inner.take() {
+      inner.lock().unwrap().set_visible(false);
     }
     Ok(())
   }
@@ -1090,6 +1113,34 @@ impl WindowBuilder {
 
     Ok(Window {
       inner: Some(Arc::new(Mutex::new(window))),
+      cursor_icon: Mutex::new(CursorIcon::Default),
     })
   }
 }
+
+/// Packs a tao window id into the `u64` reported as `Window.id`.
+pub(crate) fn window_id_u64(id: tao::window::WindowId) -> u64 {
+  let mut id_val: u64 = 0;
+  unsafe {
+    std::ptr::copy_nonoverlapping(
+      &id as *const _ as *const u8,
+      &mut id_val as *mut _ as *mut u8,
+      std::mem::size_of_val(&id).min(8),
+    );
+  }
+  id_val
+}
+
+/// Maps a resize direction to its tao equivalent.
+pub(crate) fn tao_resize_direction(direction: ResizeDirection) -> tao::window::ResizeDirection {
+  match direction {
+    ResizeDirection::East => tao::window::ResizeDirection::East,
+    ResizeDirection::North => tao::window::ResizeDirection::North,
+    ResizeDirection::Northeast => tao::window::ResizeDirection::NorthEast,
+    ResizeDirection::Northwest => tao::window::ResizeDirection::NorthWest,
+    ResizeDirection::South => tao::window::ResizeDirection::South,
+    ResizeDirection::Southeast => tao::window::ResizeDirection::SouthEast,
+    ResizeDirection::Southwest => tao::window::ResizeDirection::SouthWest,
+    ResizeDirection::West => tao::window::ResizeDirection::West,
+  }
+}
@@ -29,6 +29,30 @@ pub enum DragDropEvent {
   Dropped,
 }
 
+/// Kind of capability requested by web content.
+#[napi]
+#[derive(Clone, Copy)]
+pub enum PermissionKind {
+  Camera,
+  Microphone,
+  /// Camera and microphone requested together by one `getUserMedia` call. Only reported on
+  /// Linux; WebView2 asks for them as two separate requests.
+  CameraAndMicrophone,
+  Geolocation,
+  Notifications,
+  /// Any other capability, such as clipboard or pointer lock access.
+  Other,
+}
+
+/// Answer to a permission request.
+#[napi]
+pub enum PermissionDecision {
+  Grant,
+  Deny,
+  /// Leaves the request to the engine, which prompts or denies as it normally would.
+  Default,
+}
+
 /// Error type for webview operations.
 #[napi]
 pub enum Error {
@@ -70,14 +94,17 @@ pub enum PageLoadEvent {
   Completed,
 }
 
-/// Proxy configuration.
+/// Proxy configuration. Each proxy is given as `host:port`, e.g. `proxy.corp:3128` or
+/// `[::1]:1080`.
 #[napi]
+#[derive(Clone)]
 pub enum ProxyConfig {
   /// Direct connection (no proxy).
   None,
-  /// HTTP proxy.
+  /// HTTP proxy, reached with HTTP CONNECT.
   Http(String),
-  /// HTTPS proxy.
+  /// Proxy for HTTPS traffic. Engines tunnel HTTPS through HTTP CONNECT, so this behaves like
+  /// `Http`.
   Https(String),
   /// SOCKS5 proxy.
   Socks5(String),
@@ -17,15 +17,9 @@ use crate::wry::types::Result;
   target_os = "netbsd",
   target_os = "openbsd"
 ))]
-use tao::platform::unix::WindowExtUnix;
-#[cfg(any(
-  target_os = "linux",
-  target_os = "dragonfly",
-  target_os = "freebsd",
-  target_os = "netbsd",
-  target_os = "openbsd"
-))]
 use wry::WebViewBuilderExtUnix;
+#[cfg(target_os = "windows")]
+use wry::WebViewBuilderExtWindows;
 
 /// An initialization script to be run when creating a webview.
 #[napi(object)]
@@ -107,9 +101,10 @@ pub struct RequestAsyncResponder {
 
 /// The web context for a webview.
 #[napi]
+#[derive(Clone)]
 pub struct WebContext {
   #[allow(clippy::arc_with_non_send_sync)]
-  inner: Arc<Mutex<wry::WebContext>>,
+  pub(crate) inner: Arc<Mutex<wry::WebContext>>,
 }
 
 #[napi]
@@ -205,12 +200,74 @@ pub struct WebViewAttributes {
 
 pub type IpcHandler = ThreadsafeFunction<String>;
 
+/// Handler for IPC messages reserved by the crate itself.
+/// Returns `true` when the message was consumed and must not reach the JS listeners.
+pub(crate) type InternalIpcHandler = Box<dyn Fn(&wry::http::Request<String>) -> bool>;
+
+/// Decides whether a navigation to the given URL is allowed.
+pub(crate) type NavigationFilter = Box<dyn Fn(&str) -> bool>;
+
+/// Called when a page starts or finishes loading, with the page URL.
+pub(crate) type PageLoadHandler = Box<dyn Fn(&wry::PageLoadEvent, &str)>;
+
+/// Called when a download starts with its URL and destination path. Returns whether to allow it.
+pub(crate) type DownloadStartedHandler = Box<dyn FnMut(String, &mut std::path::PathBuf) -> bool>;
+
+/// Called when a download finishes with its URL, final path and whether it succeeded.
+pub(crate) type DownloadCompletedHandler = Box<dyn Fn(String, Option<std::path::PathBuf>, bool)>;
+
+/// Called with the URL, received bytes and, when known, total size of a download as it makes
+/// progress, at most once per `DOWNLOAD_PROGRESS_INTERVAL` and once more when it has finished.
+pub(crate) type DownloadProgressHandler = Box<dyn Fn(&str, u64, Option<u64>)>;
+
+/// Minimum time between two progress notifications of one download.
+pub(crate) const DOWNLOAD_PROGRESS_INTERVAL: std::time::Duration =
+  std::time::Duration::from_millis(250);
+
+/// Called with the new document title.
+pub(crate) type TitleChangedHandler = Box<dyn Fn(String)>;
+
+/// Answers a request to a custom protocol through the responder.
+pub(crate) type CustomProtocolHandler =
+  Box<dyn Fn(wry::http::Request<Vec<u8>>, wry::RequestAsyncResponder)>;
+
+/// Called for drag and drop events over the webview. Returns whether the event was consumed.
+pub(crate) type DragDropHandler = Box<dyn Fn(wry::DragDropEvent) -> bool>;
+
+/// Called with the requesting origin when web content asks for a capability. Returns whether to
+/// grant it, or `None` to leave the decision to the engine.
+pub(crate) type PermissionHandler =
+  Box<dyn Fn(&str, crate::wry::enums::PermissionKind) -> Option<bool>>;
+
 /// Builder for creating webviews.
 #[napi]
 pub struct WebViewBuilder {
   attributes: WebViewAttributes,
   ipc_handler: Option<IpcHandler>,
   ipc_handlers: Vec<IpcHandler>,
+  internal_ipc_handler: Option<InternalIpcHandler>,
+  navigation_filters: Vec<NavigationFilter>,
+  download_started_handler: Option<DownloadStartedHandler>,
+  download_completed_handler: Option<DownloadCompletedHandler>,
+  #[cfg_attr(target_os = "macos", allow(dead_code))]
+  download_progress_handler: Option<DownloadProgressHandler>,
+  page_load_handlers: Vec<PageLoadHandler>,
+  title_changed_handler: Option<TitleChangedHandler>,
+  custom_protocols: Vec<(String, CustomProtocolHandler)>,
+  drag_drop_handler: Option<DragDropHandler>,
+  #[cfg_attr(target_os = "macos", allow(dead_code))]
+  permission_handler: Option<PermissionHandler>,
+  accept_language: Option<String>,
+  headers: wry::http::HeaderMap,
+  proxy_config: Option<wry::ProxyConfig>,
+  #[cfg_attr(target_os = "macos", allow(dead_code))]
+  cache_disabled: bool,
+  #[cfg_attr(target_os = "macos", allow(dead_code))]
+  gpu_disabled: bool,
+  #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+  tls13_required: bool,
+  #[allow(clippy::arc_with_non_send_sync)]
+  web_context: Option<Arc<Mutex<wry::WebContext>>>,
   #[allow(dead_code)]
   inner: Option<wry::WebViewBuilder<'static>>,
 }
@@ -253,6 +310,23 @@ impl WebViewBuilder {
       },
       ipc_handler: None,
       ipc_handlers: Vec::new(),
+      internal_ipc_handler: None,
+      navigation_filters: Vec::new(),
+      download_started_handler: None,
+      download_completed_handler: None,
+      download_progress_handler: None,
+      page_load_handlers: Vec::new(),
+      title_changed_handler: None,
+      custom_protocols: Vec::new(),
+      drag_drop_handler: None,
+      permission_handler: None,
+      accept_language: None,
+      headers: wry::http::HeaderMap::new(),
+      proxy_config: None,
+      cache_disabled: false,
+      gpu_disabled: false,
+      tls13_required: false,
+      web_context: None,
       inner: None,
     })
   }
@@ -470,6 +544,168 @@ impl WebViewBuilder {
     Ok(self)
   }
 
+  /// Sets the handler for crate-internal IPC messages.
+  pub(crate) fn set_internal_ipc_handler(&mut self, handler: InternalIpcHandler) {
+    self.internal_ipc_handler = Some(handler);
+  }
+
+  /// Adds a filter that can deny navigations. A navigation proceeds only if every filter allows it.
+  pub(crate) fn add_navigation_filter(&mut self, filter: NavigationFilter) {
+    self.navigation_filters.push(filter);
+  }
+
+  /// Sets the `Accept-Language` value, e.g. `de-DE,de;q=0.9,en;q=0.8`.
+  ///
+  /// WebView2 and WebKitGTK apply it to every request. WebKitGTK sets it on the web context, so
+  /// it also affects webviews sharing that context. Other backends only send it with the initial
+  /// page request.
+  #[napi]
+  pub fn with_accept_language(&mut self, accept_language: String) -> Result<&Self> {
+    self.accept_language = Some(accept_language);
+    Ok(self)
+  }
+
+  /// Adds an HTTP header sent with the initial page request, e.g. an `Authorization` token.
+  /// Later navigations and subresource requests do not carry it.
+  #[napi]
+  pub fn with_header(&mut self, name: String, value: String) -> Result<&Self> {
+    let (name, value) = header_pair(&name, &value)?;
+    self.headers.append(name, value);
+    Ok(self)
+  }
+
+  /// Routes the webview's traffic through a proxy. WebKitGTK sets it on the web context, so it
+  /// also affects webviews sharing that context.
+  pub(crate) fn set_proxy_config(&mut self, config: wry::ProxyConfig) {
+    self.proxy_config = Some(config);
+  }
+
+  /// Disables the HTTP cache. WebView2 shrinks the disk cache to a single byte and WebKitGTK
+  /// switches its web context to the document viewer cache model, which also affects webviews
+  /// sharing that context. Not supported by WKWebView.
+  pub(crate) fn set_cache_disabled(&mut self, disabled: bool) {
+    self.cache_disabled = disabled;
+  }
+
+  /// Disables hardware acceleration. WebView2 gets `--disable-gpu` and WebKitGTK stops using
+  /// hardware acceleration and accelerated 2D canvas. WKWebView offers no such control.
+  pub(crate) fn set_gpu_disabled(&mut self, disabled: bool) {
+    self.gpu_disabled = disabled;
+  }
+
+  /// Refuses connections older than TLS 1.3. Only WebView2 can be configured, through
+  /// `--ssl-version-min`; other engines ignore it.
+  pub(crate) fn set_tls13_required(&mut self, required: bool) {
+    self.tls13_required = required;
+  }
+
+  /// Headers for the initial page request: `Accept-Language` followed by those added with
+  /// `with_header`.
+  fn initial_headers(&self) -> Option<wry::http::HeaderMap> {
+    let mut headers = wry::http::HeaderMap::new();
+    if let Some(accept_language) = &self.accept_language {
+      if let Ok(value) = wry::http::HeaderValue::from_str(accept_language) {
+        headers.insert(wry::http::header::ACCEPT_LANGUAGE, value);
+      }
+    }
+    for (name, value) in &self.headers {
+      headers.append(name.clone(), value.clone());
+    }
+    (!headers.is_empty()).then_some(headers)
+  }
+
+  /// Builds the WebView2 browser arguments, starting with the defaults wry would use.
+  #[cfg(target_os = "windows")]
+  fn browser_args(&self) -> String {
+    let mut args = "--disable-features=msWebOOUI,msPdfOOUI,msSmartScreenProtection".to_string();
+    if self.attributes.autoplay {
+      args.push_str(" --autoplay-policy=no-user-gesture-required");
+    }
+    if let Some(accept_language) = &self.accept_language {
+      args.push_str(&format!(
+        " --accept-lang={}",
+        language_list(accept_language).join(",")
+      ));
+    }
+    // wry only adds the proxy to its own default arguments.
+    match &self.proxy_config {
+      Some(wry::ProxyConfig::Http(endpoint)) => {
+        args.push_str(&format!(
+          " --proxy-server=http://{}:{}",
+          endpoint.host, endpoint.port
+        ));
+      }
+      Some(wry::ProxyConfig::Socks5(endpoint)) => {
+        args.push_str(&format!(
+          " --proxy-server=socks5://{}:{}",
+          endpoint.host, endpoint.port
+        ));
+      }
+      None => {}
+    }
+    if self.cache_disabled {
+      args.push_str(" --disk-cache-size=1 --media-cache-size=1");
+    }
+    if self.gpu_disabled {
+      args.push_str(" --disable-gpu");
+    }
+    if self.tls13_required {
+      args.push_str(" --ssl-version-min=tls1.3");
+    }
+    args
+  }
+
+  /// Sets the web context, sharing its data directory with other webviews using it.
+  #[napi]
+  pub fn with_web_context(&mut self, context: &WebContext) -> Result<&Self> {
+    self.web_context = Some(context.inner.clone());
+    Ok(self)
+  }
+
+  /// Adds a handler called when a page starts or finishes loading.
+  pub(crate) fn add_page_load_handler(&mut self, handler: PageLoadHandler) {
+    self.page_load_handlers.push(handler);
+  }
+
+  /// Sets the handler called when the document title changes.
+  pub(crate) fn set_title_changed_handler(&mut self, handler: TitleChangedHandler) {
+    self.title_changed_handler = Some(handler);
+  }
+
+  /// Registers a handler for requests to the `name://` scheme.
+  pub(crate) fn add_custom_protocol(&mut self, name: String, handler: CustomProtocolHandler) {
+    self.custom_protocols.push((name, handler));
+  }
+
+  /// Sets the handler for drag and drop events.
+  pub(crate) fn set_drag_drop_handler(&mut self, handler: DragDropHandler) {
+    self.drag_drop_handler = Some(handler);
+  }
+
+  /// Sets the handler deciding camera, microphone, geolocation and other permission requests.
+  /// Only WebKitGTK is wired up; WebView2 and WKWebView keep their default behavior.
+  pub(crate) fn set_permission_handler(&mut self, handler: PermissionHandler) {
+    self.permission_handler = Some(handler);
+  }
+
+  /// Sets the handlers called when a download starts and completes.
+  #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+  pub(crate) fn set_download_handlers(
+    &mut self,
+    started: DownloadStartedHandler,
+    completed: DownloadCompletedHandler,
+  ) {
+    self.download_started_handler = Some(started);
+    self.download_completed_handler = Some(completed);
+  }
+
+  /// Sets the handler reporting download progress from the engine's own events. Not available on
+  /// macOS, where WKWebView does not report progress to wry.
+  #[cfg_attr(target_os = "macos", allow(dead_code))]
+  pub(crate) fn set_download_progress_handler(&mut self, handler: DownloadProgressHandler) {
+    self.download_progress_handler = Some(handler);
+  }
+
   /// Builds the webview on an existing window.
   #[napi]
   pub fn build_on_window(
@@ -486,63 +722,10 @@ impl WebViewBuilder {
     })?;
     let window_inner = window_lock.lock().unwrap();
 
-    let mut webview_builder = wry::WebViewBuilder::new();
-
-    webview_builder = webview_builder.with_transparent(self.attributes.transparent);
-
-    if let Some(bg_color) = &self.attributes.background_color {
-      if bg_color.len() >= 4 {
-        webview_builder = webview_builder.with_background_color((
-          bg_color[0],
-          bg_color[1],
-          bg_color[2],
-          bg_color[3],
-        ));
-      }
-    } else if self.attributes.transparent {
-      // Explicitly transparent background if transparent is requested and no color provided
-      webview_builder = webview_builder.with_background_color((0, 0, 0, 0));
-    }
-
-    // Set bounds if provided
-    webview_builder = webview_builder.with_bounds(wry::Rect {
-      position: tao::dpi::LogicalPosition::new(self.attributes.x as f64, self.attributes.y as f64)
-        .into(),
-      size: tao::dpi::LogicalSize::new(self.attributes.width as f64, self.attributes.height as f64)
-        .into(),
-    });
-
-    // Set URL or HTML
-    if let Some(url) = &self.attributes.url {
-      webview_builder = webview_builder.with_url(url);
-    } else if let Some(html) = &self.attributes.html {
-      webview_builder = webview_builder.with_html(html);
-    }
-
-    webview_builder = webview_builder.with_devtools(self.attributes.devtools);
-
-    // Set other attributes
-    webview_builder = webview_builder.with_hotkeys_zoom(self.attributes.hotkeys_zoom);
-    #[cfg(any(
-      target_os = "windows",
-      target_os = "macos",
-      target_os = "ios",
-      target_os = "android"
-    ))]
-    {
-      webview_builder = webview_builder.with_incognito(self.attributes.incognito);
-    }
-    webview_builder = webview_builder.with_autoplay(self.attributes.autoplay);
-    webview_builder = webview_builder.with_clipboard(self.attributes.clipboard);
-    webview_builder = webview_builder
-      .with_back_forward_navigation_gestures(self.attributes.back_forward_navigation_gestures);
-
-    // Apply initialization scripts
-    for script in &self.attributes.initialization_scripts {
-      webview_builder = webview_builder.with_initialization_script(&script.js);
-    }
-
-    // Build the webview
+    let web_context = self.web_context.clone();
+    let mut web_context_guard = web_context.as_ref().map(|context| context.lock().unwrap());
+    let (webview_builder, ipc_listeners) =
+      self.configure(web_context_guard.as_deref_mut(), ipc_listeners_override)?;
     #[cfg(any(
       target_os = "linux",
       target_os = "dragonfly",
@@ -550,53 +733,7 @@ impl WebViewBuilder {
       target_os = "netbsd",
       target_os = "openbsd"
     ))]
-    {
-      extern "C" {
-        fn gtk_bin_get_child(bin: *mut std::ffi::c_void) -> *mut std::ffi::c_void;
-        fn gtk_container_remove(container: *mut std::ffi::c_void, widget: *mut std::ffi::c_void);
-        fn gtk_widget_show_all(widget: *mut std::ffi::c_void);
-      }
-
-      let window_ptr = window_inner.gtk_window();
-      let window_ptr_raw = unsafe { *(window_ptr as *const _ as *const *mut std::ffi::c_void) };
-
-      unsafe {
-        let child = gtk_bin_get_child(window_ptr_raw);
-        if !child.is_null() {
-          gtk_container_remove(window_ptr_raw, child);
-        }
-      }
-
-      // IPC Handler
-      let (webview_builder_with_ipc, listeners) = setup_ipc_handler(
-        self.ipc_handler.take(),
-        self.ipc_handlers.drain(..).collect(),
-        webview_builder,
-        ipc_listeners_override,
-      );
-      let ipc_listeners = listeners;
-      webview_builder = webview_builder_with_ipc;
-
-      let webview = webview_builder.build_gtk(window_ptr).map_err(|e| {
-        napi::Error::new(
-          napi::Status::GenericFailure,
-          format!("Failed to create webview: {}", e),
-        )
-      })?;
-
-      unsafe {
-        gtk_widget_show_all(window_ptr_raw);
-      }
-
-      #[allow(clippy::arc_with_non_send_sync)]
-      let webview_inner = Arc::new(Mutex::new(webview));
-      Ok(WebView {
-        inner: Some(webview_inner),
-        label,
-        ipc_listeners,
-      })
-    }
-
+    let webview = build_gtk_webview(webview_builder, &window_inner);
     #[cfg(not(any(
       target_os = "linux",
       target_os = "dragonfly",
@@ -604,31 +741,8 @@ impl WebViewBuilder {
       target_os = "netbsd",
       target_os = "openbsd"
     )))]
-    {
-      // IPC Handler
-      let (webview_builder_with_ipc, listeners) = setup_ipc_handler(
-        self.ipc_handler.take(),
-        self.ipc_handlers.drain(..).collect(),
-        webview_builder,
-        ipc_listeners_override,
-      );
-      let ipc_listeners = listeners;
-      webview_builder = webview_builder_with_ipc;
-
-      let webview = webview_builder.build(&*window_inner).map_err(|e| {
-        napi::Error::new(
-          napi::Status::GenericFailure,
-          format!("Failed to create webview: {}", e),
-        )
-      })?;
-      #[allow(clippy::arc_with_non_send_sync)]
-      let webview_inner = Arc::new(Mutex::new(webview));
-      Ok(WebView {
-        inner: Some(webview_inner),
-        label,
-        ipc_listeners,
-      })
-    }
+    let webview = webview_builder.build(&*window_inner);
+    self.finish(webview, label, ipc_listeners)
   }
 
   /// Builds the webview.
@@ -676,10 +790,53 @@ impl WebViewBuilder {
       )
     })?;
 
-    // Create webview builder
-    let mut webview_builder = wry::WebViewBuilder::new();
+    let web_context = self.web_context.clone();
+    let mut web_context_guard = web_context.as_ref().map(|context| context.lock().unwrap());
+    let (webview_builder, ipc_listeners) =
+      self.configure(web_context_guard.as_deref_mut(), ipc_listeners_override)?;
+    #[cfg(any(
+      target_os = "linux",
+      target_os = "dragonfly",
+      target_os = "freebsd",
+      target_os = "netbsd",
+      target_os = "openbsd"
+    ))]
+    let webview = build_gtk_webview(webview_builder, &window);
+    #[cfg(not(any(
+      target_os = "linux",
+      target_os = "dragonfly",
+      target_os = "freebsd",
+      target_os = "netbsd",
+      target_os = "openbsd"
+    )))]
+    let webview = webview_builder.build(&window);
+    self.finish(webview, label, ipc_listeners)
+  }
+}
+
+impl WebViewBuilder {
+  /// Creates a `wry::WebViewBuilder` with everything but the window applied: attributes,
+  /// headers, browser arguments, handlers, custom protocols and proxy configuration. Handlers are
+  /// moved out of `self`.
+  fn configure<'a>(
+    &mut self,
+    web_context: Option<&'a mut wry::WebContext>,
+    ipc_listeners_override: Option<Arc<Mutex<Vec<IpcHandler>>>>,
+  ) -> Result<(wry::WebViewBuilder<'a>, Arc<Mutex<Vec<IpcHandler>>>)> {
+    #[cfg(target_os = "windows")]
+    let browser_args = {
+      let args = self.browser_args();
+      let data_directory = web_context
+        .as_ref()
+        .and_then(|context| context.data_directory());
+      check_browser_args(data_directory, &args)?;
+      args
+    };
+    let mut webview_builder = match web_context {
+      Some(context) => wry::WebViewBuilder::new_with_web_context(context),
+      None => wry::WebViewBuilder::new(),
+    };
 
-    // Set transparency and background color
     webview_builder = webview_builder.with_transparent(self.attributes.transparent);
 
     if let Some(bg_color) = &self.attributes.background_color {
@@ -696,7 +853,7 @@ impl WebViewBuilder {
       webview_builder = webview_builder.with_background_color((0, 0, 0, 0));
     }
 
-    // Set bounds
+    // Set bounds if provided
     webview_builder = webview_builder.with_bounds(wry::Rect {
       position: tao::dpi::LogicalPosition::new(self.attributes.x as f64, self.attributes.y as f64)
         .into(),
@@ -725,6 +882,9 @@ impl WebViewBuilder {
       webview_builder = webview_builder.with_incognito(self.attributes.incognito);
     }
     webview_builder = webview_builder.with_autoplay(self.attributes.autoplay);
+    if let Some(config) = &self.proxy_config {
+      webview_builder = webview_builder.with_proxy_config(config.clone());
+    }
     webview_builder = webview_builder.with_clipboard(self.attributes.clipboard);
     webview_builder = webview_builder
       .with_back_forward_navigation_gestures(self.attributes.back_forward_navigation_gestures);
@@ -734,7 +894,71 @@ impl WebViewBuilder {
       webview_builder = webview_builder.with_initialization_script(&script.js);
     }
 
-    // Build the webview
+    if !self.navigation_filters.is_empty() {
+      let filters = std::mem::take(&mut self.navigation_filters);
+      webview_builder = webview_builder
+        .with_navigation_handler(move |url| filters.iter().all(|filter| filter(&url)));
+    }
+
+    if let Some(headers) = self.initial_headers() {
+      webview_builder = webview_builder.with_headers(headers);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+      webview_builder = webview_builder.with_additional_browser_args(browser_args);
+    }
+
+    if !self.page_load_handlers.is_empty() {
+      let handlers = std::mem::take(&mut self.page_load_handlers);
+      webview_builder = webview_builder.with_on_page_load_handler(move |event, url| {
+        for handler in &handlers {
+          handler(&event, &url);
+        }
+      });
+    }
+
+    if let Some(handler) = self.download_started_handler.take() {
+      webview_builder = webview_builder.with_download_started_handler(handler);
+    }
+    if let Some(handler) = self.download_completed_handler.take() {
+      webview_builder = webview_builder.with_download_completed_handler(handler);
+    }
+    if let Some(handler) = self.title_changed_handler.take() {
+      webview_builder = webview_builder.with_document_title_changed_handler(handler);
+    }
+    if let Some(handler) = self.drag_drop_handler.take() {
+      webview_builder = webview_builder.with_drag_drop_handler(handler);
+    }
+    for (name, handler) in self.custom_protocols.drain(..) {
+      webview_builder = webview_builder
+        .with_asynchronous_custom_protocol(name, move |_, request, responder| {
+          handler(request, responder)
+        });
+    }
+
+    Ok(setup_ipc_handler(
+      self.ipc_handler.take(),
+      self.ipc_handlers.drain(..).collect(),
+      self.internal_ipc_handler.take(),
+      webview_builder,
+      ipc_listeners_override,
+    ))
+  }
+
+  /// Wraps the built webview, applying the settings engines only take after it exists.
+  fn finish(
+    &mut self,
+    webview: wry::Result<wry::WebView>,
+    label: String,
+    ipc_listeners: Arc<Mutex<Vec<IpcHandler>>>,
+  ) -> Result<WebView> {
+    let webview = webview.map_err(|e| {
+      napi::Error::new(
+        napi::Status::GenericFailure,
+        format!("Failed to create webview: {}", e),
+      )
+    })?;
     #[cfg(any(
       target_os = "linux",
       target_os = "dragonfly",
@@ -743,84 +967,45 @@ impl WebViewBuilder {
       target_os = "openbsd"
     ))]
     {
-      extern "C" {
-        fn gtk_bin_get_child(bin: *mut std::ffi::c_void) -> *mut std::ffi::c_void;
-        fn gtk_container_remove(container: *mut std::ffi::c_void, widget: *mut std::ffi::c_void);
-        fn gtk_widget_show_all(widget: *mut std::ffi::c_void);
+      if let Some(accept_language) = &self.accept_language {
+        set_preferred_languages(&webview, accept_language);
       }
-
-      let window_ptr = window.gtk_window();
-      let window_ptr_raw = unsafe { *(window_ptr as *const _ as *const *mut std::ffi::c_void) };
-
-      unsafe {
-        let child = gtk_bin_get_child(window_ptr_raw);
-        if !child.is_null() {
-          gtk_container_remove(window_ptr_raw, child);
-        }
+      if self.cache_disabled {
+        disable_cache(&webview);
       }
-
-      // IPC Handler
-      let (webview_builder_with_ipc, listeners) = setup_ipc_handler(
-        self.ipc_handler.take(),
-        self.ipc_handlers.drain(..).collect(),
-        webview_builder,
-        ipc_listeners_override,
-      );
-      let ipc_listeners = listeners;
-      webview_builder = webview_builder_with_ipc;
-
-      let webview = webview_builder.build_gtk(window_ptr).map_err(|e| {
-        napi::Error::new(
-          napi::Status::GenericFailure,
-          format!("Failed to create webview: {}", e),
-        )
-      })?;
-
-      unsafe {
-        gtk_widget_show_all(window_ptr_raw);
+      if self.gpu_disabled {
+        disable_gpu(&webview);
+      }
+      if let Some(handler) = self.permission_handler.take() {
+        connect_permission_requests(&webview, handler);
+      }
+      if let Some(handler) = self.download_progress_handler.take() {
+        connect_download_progress(&webview, handler);
       }
-
-      #[allow(clippy::arc_with_non_send_sync)]
-      let webview_inner = Arc::new(Mutex::new(webview));
-      Ok(WebView {
-        inner: Some(webview_inner),
-        label,
-        ipc_listeners,
-      })
     }
-
-    #[cfg(not(any(
-      target_os = "linux",
-      target_os = "dragonfly",
-      target_os = "freebsd",
-      target_os = "netbsd",
-      target_os = "openbsd"
-    )))]
+    #[cfg(target_os = "windows")]
     {
-      // IPC Handler
-      let (webview_builder_with_ipc, listeners) = setup_ipc_handler(
-        self.ipc_handler.take(),
-        self.ipc_handlers.drain(..).collect(),
-        webview_builder,
-        ipc_listeners_override,
-      );
-      let ipc_listeners = listeners;
-      webview_builder = webview_builder_with_ipc;
-
-      let webview = webview_builder.build(&window).map_err(|e| {
+      let mut connected = Ok(());
+      if let Some(handler) = self.permission_handler.take() {
+        connected = connect_permission_requests(&webview, handler);
+      }
+      if let Some(handler) = self.download_progress_handler.take() {
+        connected = connected.and_then(|()| connect_download_progress(&webview, handler));
+      }
+      connected.map_err(|e| {
         napi::Error::new(
           napi::Status::GenericFailure,
           format!("Failed to create webview: {}", e),
         )
       })?;
-      #[allow(clippy::arc_with_non_send_sync)]
-      let webview_inner = Arc::new(Mutex::new(webview));
-      Ok(WebView {
-        inner: Some(webview_inner),
-        label,
-        ipc_listeners,
-      })
     }
+    #[allow(clippy::arc_with_non_send_sync)]
+    let webview_inner = Arc::new(Mutex::new(webview));
+    Ok(WebView {
+      inner: Some(webview_inner),
+      label,
+      ipc_listeners,
+    })
   }
 }
 
@@ -893,6 +1078,38 @@ impl WebView {
     Ok(())
   }
 
+  /// Clears all browsing data, including cookies, storage and the cache.
+  #[napi]
+  pub fn clear_all_browsing_data(&self) -> Result<()> {
+    if let Some(inner) = &self.inner {
+      inner
+        .lock()
+        .unwrap()
+        .clear_all_browsing_data()
+        .map_err(|e| {
+          napi::Error::new(
+            napi::Status::GenericFailure,
+            format!("Failed to clear browsing data: {}", e),
+          )
+        })?;
+    }
+    Ok(())
+  }
+
+  /// Gets the URL of the current page.
+  #[napi]
+  pub fn url(&self) -> Result<String> {
+    match &self.inner {
+      Some(inner) => inner.lock().unwrap().url().map_err(|e| {
+        napi::Error::new(
+          napi::Status::GenericFailure,
+          format!("Failed to get the URL: {}", e),
+        )
+      }),
+      None => Ok(String::new()),
+    }
+  }
+
   /// Prints the current page.
   #[napi]
   pub fn print(&self) -> Result<()> {
@@ -920,6 +1137,27 @@ impl WebView {
     Ok(())
   }
 
+  /// Sets the position and size of the webview in logical pixels.
+  #[napi]
+  pub fn set_bounds(&self, bounds: Rect) -> Result<()> {
+    if let Some(inner) = &self.inner {
+      inner
+        .lock()
+        .unwrap()
+        .set_bounds(wry::Rect {
+          position: tao::dpi::LogicalPosition::new(bounds.x as f64, bounds.y as f64).into(),
+          size: tao::dpi::LogicalSize::new(bounds.width as f64, bounds.height as f64).into(),
+        })
+        .map_err(|e| {
+          napi::Error::new(
+            napi::Status::GenericFailure,
+            format!("Failed to set the webview bounds: {}", e),
+          )
+        })?;
+    }
+    Ok(())
+  }
+
   /// Registers a callback for IPC messages.
   #[napi(ts_args_type = "callback: (error: Error | null, message: string) => void")]
   pub fn on(&self, callback: IpcHandler) -> Result<()> {
@@ -942,12 +1180,667 @@ impl WebView {
   }
 }
 
-fn setup_ipc_handler(
+/// Builds a webview into tao's default vbox of `window`, below the menu bar set with
+/// `BrowserWindow.setMenu`, replacing a webview built into the window before, and shows it.
+/// Windows created without the vbox get their only child replaced instead.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn build_gtk_webview(
+  builder: wry::WebViewBuilder,
+  window: &tao::window::Window,
+) -> wry::Result<wry::WebView> {
+  use std::ffi::{c_char, c_void, CStr};
+  use tao::platform::unix::WindowExtUnix;
+
+  #[repr(C)]
+  struct GList {
+    data: *mut c_void,
+    next: *mut GList,
+    prev: *mut GList,
+  }
+
+  extern "C" {
+    fn gtk_bin_get_child(bin: *mut c_void) -> *mut c_void;
+    fn gtk_container_get_children(container: *mut c_void) -> *mut GList;
+    fn gtk_container_remove(container: *mut c_void, widget: *mut c_void);
+    fn g_list_free(list: *mut GList);
+    fn g_type_name_from_instance(instance: *mut c_void) -> *const c_char;
+    fn gtk_widget_show_all(widget: *mut c_void);
+  }
+
+  let window_ptr = window.gtk_window();
+  let window_raw = unsafe { *(window_ptr as *const _ as *const *mut c_void) };
+  let Some(vbox) = window.default_vbox() else {
+    unsafe {
+      let child = gtk_bin_get_child(window_raw);
+      if !child.is_null() {
+        gtk_container_remove(window_raw, child);
+      }
+    }
+    let webview = builder.build_gtk(window_ptr)?;
+    unsafe { gtk_widget_show_all(window_raw) };
+    return Ok(webview);
+  };
+  let vbox_raw = unsafe { *(vbox as *const _ as *const *mut c_void) };
+  unsafe {
+    let children = gtk_container_get_children(vbox_raw);
+    let mut node = children;
+    while !node.is_null() {
+      let widget = (*node).data;
+      let type_name = g_type_name_from_instance(widget);
+      if !type_name.is_null() && CStr::from_ptr(type_name).to_bytes() == b"WebKitWebView" {
+        gtk_container_remove(vbox_raw, widget);
+      }
+      node = (*node).next;
+    }
+    g_list_free(children);
+  }
+  let webview = builder.build_gtk(vbox)?;
+  unsafe { gtk_widget_show_all(window_raw) };
+  Ok(webview)
+}
+
+/// WebView2 browser arguments of the webviews created so far, by user data folder, `None` being
+/// wry's default folder. All webviews of a folder share one browser process, started with the
+/// arguments of the first one; WebView2 refuses to create webviews with different arguments.
+#[cfg(target_os = "windows")]
+static BROWSER_ARGS: Mutex<Vec<(Option<std::path::PathBuf>, String)>> = Mutex::new(Vec::new());
+
+/// Fails if a webview using `data_directory` was created with other browser arguments than
+/// `args`, with an error naming the options behind them.
+#[cfg(target_os = "windows")]
+fn check_browser_args(data_directory: Option<&std::path::Path>, args: &str) -> Result<()> {
+  let mut used = BROWSER_ARGS.lock().unwrap();
+  match used
+    .iter()
+    .find(|(directory, _)| directory.as_deref() == data_directory)
+  {
+    Some((_, first)) if first != args => Err(napi::Error::new(
+      napi::Status::InvalidArg,
+      format!(
+        "Webviews sharing the data directory {} must use the same acceptLanguage, cacheMode, \
+         disableGpu, minTlsVersion and proxy options; the first webview used {:?}, this one {:?}",
+        data_directory.map_or("of the executable".to_string(), |directory| format!(
+          "{:?}",
+          directory
+        )),
+        first,
+        args
+      ),
+    )),
+    Some(_) => Ok(()),
+    None => {
+      used.push((
+        data_directory.map(std::path::Path::to_path_buf),
+        args.to_string(),
+      ));
+      Ok(())
+    }
+  }
+}
+
+/// Parses a header name and value, rejecting characters HTTP does not allow.
+pub(crate) fn header_pair(
+  name: &str,
+  value: &str,
+) -> Result<(wry::http::HeaderName, wry::http::HeaderValue)> {
+  let invalid = |what: &str| {
+    napi::Error::new(
+      napi::Status::InvalidArg,
+      format!("Invalid header {}: {:?}", what, name),
+    )
+  };
+  let header_name =
+    wry::http::HeaderName::from_bytes(name.as_bytes()).map_err(|_| invalid("name"))?;
+  let header_value = wry::http::HeaderValue::from_str(value).map_err(|_| invalid("value"))?;
+  Ok((header_name, header_value))
+}
+
+/// Extracts the language tags from an `Accept-Language` value, dropping quality weights.
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+fn language_list(accept_language: &str) -> Vec<String> {
+  accept_language
+    .split(',')
+    .filter_map(|part| part.split(';').next())
+    .map(|tag| tag.trim().to_string())
+    .filter(|tag| !tag.is_empty())
+    .collect()
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn set_preferred_languages(webview: &wry::WebView, accept_language: &str) {
+  use std::ffi::{c_char, c_void, CString};
+  use wry::WebViewExtUnix;
+
+  extern "C" {
+    fn webkit_web_view_get_context(web_view: *mut c_void) -> *mut c_void;
+    fn webkit_web_context_set_preferred_languages(
+      context: *mut c_void,
+      languages: *const *const c_char,
+    );
+  }
+
+  let languages: Vec<CString> = language_list(accept_language)
+    .into_iter()
+    .filter_map(|tag| CString::new(tag).ok())
+    .collect();
+  let mut pointers: Vec<*const c_char> = languages.iter().map(|tag| tag.as_ptr()).collect();
+  pointers.push(std::ptr::null());
+
+  let view = webview.webview();
+  let view_raw = unsafe { *(&view as *const _ as *const *mut c_void) };
+  unsafe {
+    let context = webkit_web_view_get_context(view_raw);
+    webkit_web_context_set_preferred_languages(context, pointers.as_ptr());
+  }
+}
+
+/// Switches the web context to `WEBKIT_CACHE_MODEL_DOCUMENT_VIEWER`, which disables caching.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn disable_cache(webview: &wry::WebView) {
+  use std::ffi::c_void;
+  use wry::WebViewExtUnix;
+
+  extern "C" {
+    fn webkit_web_view_get_context(web_view: *mut c_void) -> *mut c_void;
+    fn webkit_web_context_set_cache_model(context: *mut c_void, cache_model: i32);
+  }
+
+  const WEBKIT_CACHE_MODEL_DOCUMENT_VIEWER: i32 = 0;
+
+  let view = webview.webview();
+  let view_raw = unsafe { *(&view as *const _ as *const *mut c_void) };
+  unsafe {
+    let context = webkit_web_view_get_context(view_raw);
+    webkit_web_context_set_cache_model(context, WEBKIT_CACHE_MODEL_DOCUMENT_VIEWER);
+  }
+}
+
+/// Turns off WebKitGTK hardware acceleration and accelerated 2D canvas.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn disable_gpu(webview: &wry::WebView) {
+  use std::ffi::c_void;
+  use wry::WebViewExtUnix;
+
+  extern "C" {
+    fn webkit_web_view_get_settings(web_view: *mut c_void) -> *mut c_void;
+    fn webkit_settings_set_hardware_acceleration_policy(settings: *mut c_void, policy: i32);
+    fn webkit_settings_set_enable_accelerated_2d_canvas(settings: *mut c_void, enabled: i32);
+  }
+
+  const WEBKIT_HARDWARE_ACCELERATION_POLICY_NEVER: i32 = 2;
+
+  let view = webview.webview();
+  let view_raw = unsafe { *(&view as *const _ as *const *mut c_void) };
+  unsafe {
+    let settings = webkit_web_view_get_settings(view_raw);
+    webkit_settings_set_hardware_acceleration_policy(
+      settings,
+      WEBKIT_HARDWARE_ACCELERATION_POLICY_NEVER,
+    );
+    webkit_settings_set_enable_accelerated_2d_canvas(settings, 0);
+  }
+}
+
+/// Routes the `permission-request` signal of the WebKitGTK view to `handler`.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn connect_permission_requests(webview: &wry::WebView, handler: PermissionHandler) {
+  use crate::wry::enums::PermissionKind;
+  use std::ffi::{c_char, c_void, CStr};
+  use wry::WebViewExtUnix;
+
+  extern "C" {
+    fn g_signal_connect_data(
+      instance: *mut c_void,
+      signal: *const c_char,
+      callback: *const c_void,
+      data: *mut c_void,
+      destroy_data: unsafe extern "C" fn(*mut c_void, *mut c_void),
+      flags: i32,
+    ) -> u64;
+    fn g_type_check_instance_is_a(instance: *mut c_void, type_: usize) -> i32;
+    fn webkit_user_media_permission_request_get_type() -> usize;
+    fn webkit_geolocation_permission_request_get_type() -> usize;
+    fn webkit_notification_permission_request_get_type() -> usize;
+    fn webkit_user_media_permission_is_for_audio_device(request: *mut c_void) -> i32;
+    fn webkit_user_media_permission_is_for_video_device(request: *mut c_void) -> i32;
+    fn webkit_permission_request_allow(request: *mut c_void);
+    fn webkit_permission_request_deny(request: *mut c_void);
+    fn webkit_web_view_get_uri(web_view: *mut c_void) -> *const c_char;
+  }
+
+  unsafe extern "C" fn on_permission_request(
+    web_view: *mut c_void,
+    request: *mut c_void,
+    data: *mut c_void,
+  ) -> i32 {
+    let handler = &*(data as *const PermissionHandler);
+    let kind = if g_type_check_instance_is_a(
+      request,
+      webkit_user_media_permission_request_get_type(),
+    ) != 0
+    {
+      let audio = webkit_user_media_permission_is_for_audio_device(request) != 0;
+      let video = webkit_user_media_permission_is_for_video_device(request) != 0;
+      match (audio, video) {
+        (true, true) => PermissionKind::CameraAndMicrophone,
+        (true, false) => PermissionKind::Microphone,
+        _ => PermissionKind::Camera,
+      }
+    } else if g_type_check_instance_is_a(request, webkit_geolocation_permission_request_get_type())
+      != 0
+    {
+      PermissionKind::Geolocation
+    } else if g_type_check_instance_is_a(request, webkit_notification_permission_request_get_type())
+      != 0
+    {
+      PermissionKind::Notifications
+    } else {
+      PermissionKind::Other
+    };
+    let uri = webkit_web_view_get_uri(web_view);
+    let uri = if uri.is_null() {
+      String::new()
+    } else {
+      CStr::from_ptr(uri).to_string_lossy().into_owned()
+    };
+    match handler(&origin_of(&uri), kind) {
+      Some(true) => webkit_permission_request_allow(request),
+      Some(false) => webkit_permission_request_deny(request),
+      // Returning false lets the default handler decide.
+      None => return 0,
+    }
+    1
+  }
+
+  unsafe extern "C" fn free_handler(data: *mut c_void, _closure: *mut c_void) {
+    drop(Box::from_raw(data as *mut PermissionHandler));
+  }
+
+  let view = webview.webview();
+  let view_raw = unsafe { *(&view as *const _ as *const *mut c_void) };
+  unsafe {
+    g_signal_connect_data(
+      view_raw,
+      c"permission-request".as_ptr(),
+      on_permission_request as *const c_void,
+      Box::into_raw(Box::new(handler)) as *mut c_void,
+      free_handler,
+      0,
+    );
+  }
+}
+
+/// Routes the `PermissionRequested` event of the WebView2 control to `handler`.
+#[cfg(target_os = "windows")]
+fn connect_permission_requests(
+  webview: &wry::WebView,
+  handler: PermissionHandler,
+) -> windows::core::Result<()> {
+  use crate::wry::enums::PermissionKind;
+  use webview2_com::Microsoft::Web::WebView2::Win32::*;
+  use webview2_com::{take_pwstr, PermissionRequestedEventHandler};
+  use windows::core::PWSTR;
+  use wry::WebViewExtWindows;
+
+  let mut token = 0;
+  unsafe {
+    webview.webview().add_PermissionRequested(
+      &PermissionRequestedEventHandler::create(Box::new(move |_, args| {
+        let Some(args) = args else { return Ok(()) };
+        let mut kind = COREWEBVIEW2_PERMISSION_KIND::default();
+        args.PermissionKind(&mut kind)?;
+        // WebView2 asks for the camera and the microphone of one `getUserMedia` call separately.
+        let kind = match kind {
+          COREWEBVIEW2_PERMISSION_KIND_CAMERA => PermissionKind::Camera,
+          COREWEBVIEW2_PERMISSION_KIND_MICROPHONE => PermissionKind::Microphone,
+          COREWEBVIEW2_PERMISSION_KIND_GEOLOCATION => PermissionKind::Geolocation,
+          COREWEBVIEW2_PERMISSION_KIND_NOTIFICATIONS => PermissionKind::Notifications,
+          _ => PermissionKind::Other,
+        };
+        let mut uri = PWSTR::null();
+        args.Uri(&mut uri)?;
+        let uri = take_pwstr(uri);
+        match handler(&origin_of(&uri), kind) {
+          Some(true) => args.SetState(COREWEBVIEW2_PERMISSION_STATE_ALLOW),
+          Some(false) => args.SetState(COREWEBVIEW2_PERMISSION_STATE_DENY),
+          None => Ok(()),
+        }
+      })),
+      &mut token,
+    )
+  }
+}
+
+/// Whether a progress notification is due, `DOWNLOAD_PROGRESS_INTERVAL` after the one sent at
+/// `last`. Records the time of the notification if so.
+#[cfg(any(
+  target_os = "windows",
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn progress_due(last: &std::cell::Cell<Option<std::time::Instant>>) -> bool {
+  let now = std::time::Instant::now();
+  if last
+    .get()
+    .is_some_and(|last| now.duration_since(last) < DOWNLOAD_PROGRESS_INTERVAL)
+  {
+    return false;
+  }
+  last.set(Some(now));
+  true
+}
+
+/// Reports the `BytesReceivedChanged` and `StateChanged` events of WebView2 downloads to
+/// `handler`. The downloads themselves are left to WebView2.
+#[cfg(target_os = "windows")]
+fn connect_download_progress(
+  webview: &wry::WebView,
+  handler: DownloadProgressHandler,
+) -> windows::core::Result<()> {
+  use std::cell::Cell;
+  use std::rc::Rc;
+  use webview2_com::Microsoft::Web::WebView2::Win32::*;
+  use webview2_com::{
+    take_pwstr, BytesReceivedChangedEventHandler, DownloadStartingEventHandler,
+    StateChangedEventHandler,
+  };
+  use windows::core::{Interface, PWSTR};
+  use wry::WebViewExtWindows;
+
+  /// The URL, received bytes and total size, if known, of a download.
+  fn progress(
+    operation: &ICoreWebView2DownloadOperation,
+  ) -> windows::core::Result<(String, u64, Option<u64>)> {
+    let mut uri = PWSTR::null();
+    let mut received = 0;
+    let mut total = 0;
+    unsafe {
+      operation.Uri(&mut uri)?;
+      operation.BytesReceived(&mut received)?;
+      operation.TotalBytesToReceive(&mut total)?;
+    }
+    // The total is 0 when the server sent no `Content-Length`.
+    let total = (total > 0).then_some(total as u64);
+    Ok((take_pwstr(uri), received.max(0) as u64, total))
+  }
+
+  let handler: Rc<DownloadProgressHandler> = Rc::new(handler);
+  let webview: ICoreWebView2_4 = webview.webview().cast()?;
+  let mut token = 0;
+  unsafe {
+    webview.add_DownloadStarting(
+      &DownloadStartingEventHandler::create(Box::new(move |_, args| {
+        let Some(args) = args else { return Ok(()) };
+        let operation = args.DownloadOperation()?;
+        let last = Rc::new(Cell::new(None));
+
+        let received_handler = handler.clone();
+        let received_last = last.clone();
+        operation.add_BytesReceivedChanged(
+          &BytesReceivedChangedEventHandler::create(Box::new(move |operation, _| {
+            let Some(operation) = operation else {
+              return Ok(());
+            };
+            if progress_due(&received_last) {
+              let (url, received, total) = progress(&operation)?;
+              received_handler(&url, received, total);
+            }
+            Ok(())
+          })),
+          &mut 0,
+        )?;
+
+        let state_handler = handler.clone();
+        operation.add_StateChanged(
+          &StateChangedEventHandler::create(Box::new(move |operation, _| {
+            let Some(operation) = operation else {
+              return Ok(());
+            };
+            let mut state = COREWEBVIEW2_DOWNLOAD_STATE::default();
+            operation.State(&mut state)?;
+            if state == COREWEBVIEW2_DOWNLOAD_STATE_COMPLETED {
+              let (url, received, _) = progress(&operation)?;
+              state_handler(&url, received, Some(received));
+            }
+            Ok(())
+          })),
+          &mut 0,
+        )
+      })),
+      &mut token,
+    )
+  }
+}
+
+/// Reports the `received-data` and `finished` signals of WebKitGTK downloads started by the
+/// webview to `handler`. The downloads themselves are left to WebKitGTK.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn connect_download_progress(webview: &wry::WebView, handler: DownloadProgressHandler) {
+  use std::cell::Cell;
+  use std::ffi::{c_char, c_void, CStr};
+  use std::rc::Rc;
+  use wry::WebViewExtUnix;
+
+  extern "C" {
+    fn g_signal_connect_data(
+      instance: *mut c_void,
+      signal: *const c_char,
+      callback: *const c_void,
+      data: *mut c_void,
+      destroy_data: unsafe extern "C" fn(*mut c_void, *mut c_void),
+      flags: i32,
+    ) -> u64;
+    fn g_signal_connect_object(
+      instance: *mut c_void,
+      signal: *const c_char,
+      callback: *const c_void,
+      gobject: *mut c_void,
+      flags: i32,
+    ) -> u64;
+    fn g_object_set_data_full(
+      object: *mut c_void,
+      key: *const c_char,
+      data: *mut c_void,
+      destroy: unsafe extern "C" fn(*mut c_void),
+    );
+    fn g_object_get_data(object: *mut c_void, key: *const c_char) -> *mut c_void;
+    fn webkit_web_view_get_context(web_view: *mut c_void) -> *mut c_void;
+    fn webkit_download_get_web_view(download: *mut c_void) -> *mut c_void;
+    fn webkit_download_get_request(download: *mut c_void) -> *mut c_void;
+    fn webkit_download_get_response(download: *mut c_void) -> *mut c_void;
+    fn webkit_download_get_received_data_length(download: *mut c_void) -> u64;
+    fn webkit_uri_request_get_uri(request: *mut c_void) -> *const c_char;
+    fn webkit_uri_response_get_content_length(response: *mut c_void) -> u64;
+  }
+
+  /// Progress of one download, shared by its signal handlers.
+  struct Download {
+    handler: Rc<DownloadProgressHandler>,
+    last: Cell<Option<std::time::Instant>>,
+    failed: Cell<bool>,
+  }
+
+  const HANDLER_KEY: &CStr = c"webview-download-progress";
+
+  unsafe fn report(download: *mut c_void, state: &Download, finished: bool) {
+    let request = webkit_download_get_request(download);
+    let uri = if request.is_null() {
+      std::ptr::null()
+    } else {
+      webkit_uri_request_get_uri(request)
+    };
+    let url = if uri.is_null() {
+      String::new()
+    } else {
+      CStr::from_ptr(uri).to_string_lossy().into_owned()
+    };
+    let received = webkit_download_get_received_data_length(download);
+    let total = if finished {
+      Some(received)
+    } else {
+      let response = webkit_download_get_response(download);
+      // The content length is 0 when the server sent no `Content-Length`.
+      Some(response)
+        .filter(|response| !response.is_null())
+        .map(|response| webkit_uri_response_get_content_length(response))
+        .filter(|length| *length > 0)
+    };
+    (state.handler)(&url, received, total);
+  }
+
+  unsafe extern "C" fn on_received_data(download: *mut c_void, _length: u64, data: *mut c_void) {
+    let state = &*(data as *const Rc<Download>);
+    if progress_due(&state.last) {
+      report(download, state, false);
+    }
+  }
+
+  unsafe extern "C" fn on_failed(_download: *mut c_void, _error: *mut c_void, data: *mut c_void) {
+    let state = &*(data as *const Rc<Download>);
+    state.failed.set(true);
+  }
+
+  // `finished` is emitted after `failed` too.
+  unsafe extern "C" fn on_finished(download: *mut c_void, data: *mut c_void) {
+    let state = &*(data as *const Rc<Download>);
+    if !state.failed.get() {
+      report(download, state, true);
+    }
+  }
+
+  unsafe extern "C" fn free_download(data: *mut c_void, _closure: *mut c_void) {
+    drop(Box::from_raw(data as *mut Rc<Download>));
+  }
+
+  unsafe extern "C" fn free_handler(data: *mut c_void) {
+    drop(Box::from_raw(data as *mut Rc<DownloadProgressHandler>));
+  }
+
+  // Downloads are reported by the web context, which may be shared with other webviews.
+  unsafe extern "C" fn on_download_started(
+    _context: *mut c_void,
+    download: *mut c_void,
+    web_view: *mut c_void,
+  ) {
+    if webkit_download_get_web_view(download) != web_view {
+      return;
+    }
+    let handler =
+      g_object_get_data(web_view, HANDLER_KEY.as_ptr()) as *const Rc<DownloadProgressHandler>;
+    let Some(handler) = handler.as_ref() else {
+      return;
+    };
+    let state = Rc::new(Download {
+      handler: handler.clone(),
+      last: Cell::new(None),
+      failed: Cell::new(false),
+    });
+    let signals: [(&CStr, *const c_void); 3] = [
+      (c"received-data", on_received_data as *const c_void),
+      (c"failed", on_failed as *const c_void),
+      (c"finished", on_finished as *const c_void),
+    ];
+    for (signal, callback) in signals {
+      g_signal_connect_data(
+        download,
+        signal.as_ptr(),
+        callback,
+        Box::into_raw(Box::new(state.clone())) as *mut c_void,
+        free_download,
+        0,
+      );
+    }
+  }
+
+  let view = webview.webview();
+  let view_raw = unsafe { *(&view as *const _ as *const *mut c_void) };
+  let handler: Rc<DownloadProgressHandler> = Rc::new(handler);
+  unsafe {
+    // The handler lives on the view, and the connection to the context ends with the view.
+    g_object_set_data_full(
+      view_raw,
+      HANDLER_KEY.as_ptr(),
+      Box::into_raw(Box::new(handler)) as *mut c_void,
+      free_handler,
+    );
+    g_signal_connect_object(
+      webkit_web_view_get_context(view_raw),
+      c"download-started".as_ptr(),
+      on_download_started as *const c_void,
+      view_raw,
+      0,
+    );
+  }
+}
+
+/// The `scheme://host[:port]` origin of `url`, or `url` itself if it cannot be parsed.
+#[cfg(any(
+  target_os = "windows",
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn origin_of(url: &str) -> String {
+  match url.parse::<wry::http::Uri>() {
+    Ok(uri) => match (uri.scheme_str(), uri.host()) {
+      (Some(scheme), Some(host)) => match uri.port_u16() {
+        Some(port) => format!("{}://{}:{}", scheme, host, port),
+        None => format!("{}://{}", scheme, host),
+      },
+      _ => url.to_string(),
+    },
+    Err(_) => url.to_string(),
+  }
+}
+
+fn setup_ipc_handler<'a>(
   builder_ipc_handler: Option<IpcHandler>,
   additional_handlers: Vec<IpcHandler>,
-  webview_builder: wry::WebViewBuilder<'static>,
+  internal_handler: Option<InternalIpcHandler>,
+  webview_builder: wry::WebViewBuilder<'a>,
   ipc_listeners_override: Option<Arc<Mutex<Vec<IpcHandler>>>>,
-) -> (wry::WebViewBuilder<'static>, Arc<Mutex<Vec<IpcHandler>>>) {
+) -> (wry::WebViewBuilder<'a>, Arc<Mutex<Vec<IpcHandler>>>) {
   let ipc_listeners = ipc_listeners_override.unwrap_or_else(|| Arc::new(Mutex::new(Vec::new())));
   if let Some(ipc_handler) = builder_ipc_handler {
     ipc_listeners.lock().unwrap().push(ipc_handler);
@@ -958,6 +1851,12 @@ fn setup_ipc_handler(
 
   let listeners_clone = ipc_listeners.clone();
   let webview_builder = webview_builder.with_ipc_handler(move |req| {
+    if let Some(internal_handler) = &internal_handler {
+      if internal_handler(&req) {
+        return;
+      }
+    }
+
     let msg = req.into_body();
 
     // Check if we have any listeners registered
@@ -981,3 +1880,45 @@ fn setup_ipc_handler(
 
   (webview_builder, ipc_listeners)
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn header_pair_accepts_valid_headers() {
+    let (name, value) = header_pair("X-Api-Key", "secret value").unwrap();
+    assert_eq!(name, "x-api-key");
+    assert_eq!(value, "secret value");
+  }
+
+  #[test]
+  fn header_pair_rejects_invalid_headers() {
+    for (name, value) in [
+      ("", "value"),
+      ("Bad Name", "value"),
+      ("X-Bad:", "value"),
+      ("X-Ok", "line\nbreak"),
+      ("X-Ok", "carriage\rreturn"),
+    ] {
+      let error = header_pair(name, value)
+        .err()
+        .unwrap_or_else(|| panic!("{:?}: {:?} was accepted", name, value));
+      assert_eq!(error.status, napi::Status::InvalidArg);
+    }
+  }
+
+  #[test]
+  fn language_list_drops_quality_weights() {
+    assert_eq!(
+      language_list("en-US,en;q=0.9, fr-CA ;q=0.8,de"),
+      ["en-US", "en", "fr-CA", "de"]
+    );
+  }
+
+  #[test]
+  fn language_list_skips_empty_entries() {
+    assert_eq!(language_list(""), Vec::<String>::new());
+    assert_eq!(language_list(" , ;q=0.5,ja"), ["ja"]);
+  }
+}